@@ -1,4 +1,4 @@
-use crate::{BorderStyle, Edges, Length, Size};
+use crate::{BorderStyle, Edges, Length, Margin, Size};
 
 impl Edges<u16> {
     pub const ZERO: Self = Self {
@@ -21,6 +21,26 @@ impl Edges<u16> {
     }
 }
 
+impl Edges<i16> {
+    #[inline]
+    #[must_use]
+    pub const fn horizontal(&self) -> i16 {
+        self.left.saturating_add(self.right)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn vertical(&self) -> i16 {
+        self.top.saturating_add(self.bottom)
+    }
+
+    /// Negative sides taken as 0, which is what a [`ResolvedBox`] holds.
+    #[must_use]
+    pub fn clamped(&self) -> Edges<u16> {
+        self.map(|side| u16::try_from(*side).unwrap_or(0))
+    }
+}
+
 impl Edges<Length> {
     #[must_use]
     pub fn resolve(&self, parent_width: u16) -> Edges<u16> {
@@ -33,6 +53,19 @@ impl Edges<Length> {
     }
 }
 
+impl Edges<Margin> {
+    /// Margins keep their sign, [`clamped`](Edges::clamped) drops it.
+    #[must_use]
+    pub fn resolve(&self, parent_width: u16) -> Edges<i16> {
+        Edges {
+            top: self.top.resolve(parent_width),
+            right: self.right.resolve(parent_width),
+            bottom: self.bottom.resolve(parent_width),
+            left: self.left.resolve(parent_width),
+        }
+    }
+}
+
 impl Edges<BorderStyle> {
     #[must_use]
     pub fn to_widths(&self) -> Edges<u16> {
@@ -1,3 +1,5 @@
+use std::ops::{Add, Mul, Sub};
+
 use crate::brief::box_model::ResolvedBox;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -27,10 +29,52 @@ impl Point {
     pub const ZERO: Self = Self { x: 0, y: 0 };
 
     #[inline]
-    #[must_use] 
+    #[must_use]
     pub const fn new(x: u16, y: u16) -> Self {
         Self { x, y }
     }
+
+    /// Straight-line distance in cells
+    #[must_use]
+    pub fn distance(self, other: Self) -> f32 {
+        let dx = f32::from(self.x) - f32::from(other.x);
+        let dy = f32::from(self.y) - f32::from(other.y);
+
+        dx.hypot(dy)
+    }
+
+    /// Interpolate towards `other`, see [`lerp`] for how `t` is treated.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(lerp(self.x, other.x, t), lerp(self.y, other.y, t))
+    }
+}
+
+// Cells can't go negative, so point and size arithmetic saturates instead of
+// wrapping
+
+impl Add for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_add(rhs.x), self.y.saturating_add(rhs.y))
+    }
+}
+
+impl Sub for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x.saturating_sub(rhs.x), self.y.saturating_sub(rhs.y))
+    }
+}
+
+impl Mul<u16> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: u16) -> Self {
+        Self::new(self.x.saturating_mul(rhs), self.y.saturating_mul(rhs))
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -50,6 +94,297 @@ impl Size {
     pub const fn new(width: u16, height: u16) -> Self {
         Self { width, height }
     }
+
+    #[inline]
+    #[must_use]
+    pub const fn area(self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Interpolate towards `other`, see [`lerp`] for how `t` is treated.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(
+            lerp(self.width, other.width, t),
+            lerp(self.height, other.height, t),
+        )
+    }
+}
+
+impl Add for Size {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.width.saturating_add(rhs.width),
+            self.height.saturating_add(rhs.height),
+        )
+    }
+}
+
+impl Sub for Size {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.width.saturating_sub(rhs.width),
+            self.height.saturating_sub(rhs.height),
+        )
+    }
+}
+
+impl Mul<u16> for Size {
+    type Output = Self;
+
+    fn mul(self, rhs: u16) -> Self {
+        Self::new(
+            self.width.saturating_mul(rhs),
+            self.height.saturating_mul(rhs),
+        )
+    }
+}
+
+/// An axis-aligned box of cells, `x`/`y` being its top-left corner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Rect {
+    pub const ZERO: Self = Self {
+        x: 0,
+        y: 0,
+        width: 0,
+        height: 0,
+    };
+
+    #[inline]
+    #[must_use]
+    pub const fn new(x: u16, y: u16, width: u16, height: u16) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Rect spanning two opposite corners given in any order. The corners are
+    /// edges, so equal coordinates give an empty rect.
+    #[must_use]
+    pub fn from_points(a: Point, b: Point) -> Self {
+        let x = a.x.min(b.x);
+        let y = a.y.min(b.y);
+
+        Self::new(x, y, a.x.max(b.x) - x, a.y.max(b.y) - y)
+    }
+
+    /// First column past the right edge
+    #[inline]
+    #[must_use]
+    pub const fn right(self) -> u16 {
+        self.x.saturating_add(self.width)
+    }
+
+    /// First row past the bottom edge
+    #[inline]
+    #[must_use]
+    pub const fn bottom(self) -> u16 {
+        self.y.saturating_add(self.height)
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn area(self) -> u32 {
+        self.width as u32 * self.height as u32
+    }
+
+    /// Middle cell, rounding down on odd sizes
+    #[inline]
+    #[must_use]
+    pub const fn center(self) -> Point {
+        Point::new(
+            self.x.saturating_add(self.width / 2),
+            self.y.saturating_add(self.height / 2),
+        )
+    }
+
+    /// Whether `point` is inside, counting the left/top edges but not the
+    /// right/bottom ones.
+    #[inline]
+    #[must_use]
+    pub const fn contains(self, point: Point) -> bool {
+        point.x >= self.x && point.x < self.right() && point.y >= self.y && point.y < self.bottom()
+    }
+
+    /// Whether `other` lies entirely inside, with the same edge rules as
+    /// [`Self::contains`].
+    #[inline]
+    #[must_use]
+    pub const fn contains_rect(self, other: Self) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    /// The cell inside this rect nearest to `point`.
+    #[must_use]
+    pub fn clamp_point(self, point: Point) -> Point {
+        let max_x = self.right().saturating_sub(1).max(self.x);
+        let max_y = self.bottom().saturating_sub(1).max(self.y);
+
+        Point::new(point.x.clamp(self.x, max_x), point.y.clamp(self.y, max_y))
+    }
+
+    /// Shift by a signed offset, stopping at the edges of the cell grid.
+    #[inline]
+    #[must_use]
+    pub const fn translate(self, dx: i16, dy: i16) -> Self {
+        Self::new(
+            self.x.saturating_add_signed(dx),
+            self.y.saturating_add_signed(dy),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Split into left and right parts `at` cells from the left edge. An
+    /// offset past the right edge leaves the right part zero-width.
+    #[must_use]
+    pub fn split_h(self, at: u16) -> (Self, Self) {
+        let at = at.min(self.width);
+
+        (
+            Self::new(self.x, self.y, at, self.height),
+            Self::new(
+                self.x.saturating_add(at),
+                self.y,
+                self.width - at,
+                self.height,
+            ),
+        )
+    }
+
+    /// Split into top and bottom parts `at` cells from the top edge. An
+    /// offset past the bottom edge leaves the bottom part zero-height.
+    #[must_use]
+    pub fn split_v(self, at: u16) -> (Self, Self) {
+        let at = at.min(self.height);
+
+        (
+            Self::new(self.x, self.y, self.width, at),
+            Self::new(
+                self.x,
+                self.y.saturating_add(at),
+                self.width,
+                self.height - at,
+            ),
+        )
+    }
+
+    /// Grow by `dx` on the left and right and `dy` on the top and bottom,
+    /// stopping at the edges of the cell grid.
+    #[must_use]
+    pub const fn inflate(self, dx: u16, dy: u16) -> Self {
+        let x = self.x.saturating_sub(dx);
+        let y = self.y.saturating_sub(dy);
+
+        Self::new(
+            x,
+            y,
+            self.right().saturating_add(dx) - x,
+            self.bottom().saturating_add(dy) - y,
+        )
+    }
+
+    /// Shrink by `dx` on the left and right and `dy` on the top and bottom.
+    /// An axis shrunk past nothing collapses to zero at its center.
+    #[must_use]
+    pub const fn deflate(self, dx: u16, dy: u16) -> Self {
+        let center = self.center();
+
+        let (x, width) = if self.width > dx.saturating_mul(2) {
+            (self.x.saturating_add(dx), self.width - dx * 2)
+        } else {
+            (center.x, 0)
+        };
+
+        let (y, height) = if self.height > dy.saturating_mul(2) {
+            (self.y.saturating_add(dy), self.height - dy * 2)
+        } else {
+            (center.y, 0)
+        };
+
+        Self::new(x, y, width, height)
+    }
+
+    /// Interpolate position and size towards `other`, see [`lerp`] for how
+    /// `t` is treated.
+    #[must_use]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self::new(
+            lerp(self.x, other.x, t),
+            lerp(self.y, other.y, t),
+            lerp(self.width, other.width, t),
+            lerp(self.height, other.height, t),
+        )
+    }
+
+    /// Smallest rect containing both `self` and `other`
+    #[must_use]
+    pub fn union(self, other: Self) -> Self {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+
+        Self::new(
+            x,
+            y,
+            self.right().max(other.right()) - x,
+            self.bottom().max(other.bottom()) - y,
+        )
+    }
+
+    /// Union of every non-empty rect, or `None` if they are all empty.
+    #[must_use]
+    pub fn bounding_box(rects: &[Self]) -> Option<Self> {
+        rects
+            .iter()
+            .copied()
+            .filter(|rect| !rect.is_empty())
+            .reduce(Self::union)
+    }
+}
+
+/// Linear interpolation between two cell values, rounded to the nearest cell.
+///
+/// `t` of `0.0` gives `a` and `1.0` gives `b`. Values outside that range
+/// extrapolate past either end, saturating at the bounds of `u16`.
+#[must_use]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn lerp(a: u16, b: u16, t: f32) -> u16 {
+    let a = f32::from(a);
+    let b = f32::from(b);
+
+    (b - a)
+        .mul_add(t, a)
+        .round()
+        .clamp(0.0, f32::from(u16::MAX)) as u16
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -125,3 +460,226 @@ impl Constraints {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point_ops() {
+        let a = Point::new(3, 7);
+        let b = Point::new(5, 2);
+
+        assert_eq!(a + b, Point::new(8, 9));
+        assert_eq!(a - b, Point::new(0, 5));
+        assert_eq!(a * 3, Point::new(9, 21));
+        assert_eq!(Point::new(u16::MAX, 1) + b, Point::new(u16::MAX, 3));
+        assert_eq!(Point::new(u16::MAX, 1) * 2, Point::new(u16::MAX, 2));
+    }
+
+    #[test]
+    fn size_ops() {
+        let a = Size::new(10, 4);
+        let b = Size::new(3, 6);
+
+        assert_eq!(a + b, Size::new(13, 10));
+        assert_eq!(a - b, Size::new(7, 0));
+        assert_eq!(a * 2, Size::new(20, 8));
+        assert_eq!(Size::new(u16::MAX, 1) + b, Size::new(u16::MAX, 7));
+    }
+
+    #[test]
+    fn size_area_and_is_empty() {
+        assert_eq!(Size::new(10, 4).area(), 40);
+        assert!(Size::ZERO.is_empty());
+        assert!(Size::new(0, 4).is_empty());
+        assert!(!Size::new(1, 1).is_empty());
+    }
+
+    #[test]
+    fn rect_from_points() {
+        let a = Point::new(10, 8);
+        let b = Point::new(4, 2);
+
+        assert_eq!(Rect::from_points(a, b), Rect::new(4, 2, 6, 6));
+        assert_eq!(Rect::from_points(b, a), Rect::new(4, 2, 6, 6));
+        assert_eq!(
+            Rect::from_points(Point::new(2, 9), Point::new(7, 3)),
+            Rect::new(2, 3, 5, 6)
+        );
+        assert!(Rect::from_points(a, a).is_empty());
+    }
+
+    #[test]
+    fn rect_center_and_area() {
+        let rect = Rect::new(0, 0, 10, 10);
+        assert_eq!(rect.center(), Point::new(5, 5));
+        assert_eq!(rect.area(), 100);
+
+        let rect = Rect::new(4, 2, 5, 3);
+        assert_eq!(rect.center(), Point::new(6, 3));
+        assert_eq!(rect.area(), 15);
+
+        let wide = Rect::new(0, 0, u16::MAX, u16::MAX);
+        assert_eq!(wide.area(), u32::from(u16::MAX) * u32::from(u16::MAX));
+    }
+
+    #[test]
+    fn rect_is_empty() {
+        assert!(Rect::ZERO.is_empty());
+        assert!(Rect::new(3, 3, 0, 4).is_empty());
+        assert!(Rect::new(3, 3, 4, 0).is_empty());
+        assert!(!Rect::new(3, 3, 1, 1).is_empty());
+    }
+
+    #[test]
+    fn rect_inflate() {
+        let rect = Rect::new(5, 5, 4, 2);
+        assert_eq!(rect.inflate(1, 2), Rect::new(4, 3, 6, 6));
+
+        // Clamped at the origin without losing the far edge
+        assert_eq!(rect.inflate(10, 0), Rect::new(0, 5, 19, 2));
+    }
+
+    #[test]
+    fn rect_deflate() {
+        let rect = Rect::new(2, 2, 10, 6);
+        assert_eq!(rect.deflate(1, 1), Rect::new(3, 3, 8, 4));
+        assert_eq!(rect.deflate(1, 1).inflate(1, 1), rect);
+
+        assert_eq!(rect.deflate(20, 3), Rect::new(7, 5, 0, 0));
+        assert_eq!(rect.deflate(5, 0), Rect::new(7, 2, 0, 6));
+    }
+
+    #[test]
+    fn point_distance() {
+        assert!((Point::new(0, 0).distance(Point::new(3, 4)) - 5.0).abs() < f32::EPSILON);
+        assert!((Point::new(3, 4).distance(Point::new(0, 0)) - 5.0).abs() < f32::EPSILON);
+        assert!(Point::new(7, 7).distance(Point::new(7, 7)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn rect_translate() {
+        let rect = Rect::new(10, 10, 3, 2);
+
+        assert_eq!(rect.translate(5, 1), Rect::new(15, 11, 3, 2));
+        assert_eq!(rect.translate(-4, -10), Rect::new(6, 0, 3, 2));
+        assert_eq!(rect.translate(-20, 0), Rect::new(0, 10, 3, 2));
+    }
+
+    #[test]
+    fn rect_contains() {
+        let rect = Rect::new(2, 2, 4, 3);
+
+        assert!(rect.contains(Point::new(2, 2)));
+        assert!(rect.contains(Point::new(5, 4)));
+        assert!(!rect.contains(Point::new(6, 4)));
+        assert!(!rect.contains(Point::new(5, 5)));
+    }
+
+    #[test]
+    fn rect_contains_rect() {
+        let outer = Rect::new(2, 2, 10, 10);
+
+        assert!(outer.contains_rect(outer));
+        assert!(outer.contains_rect(Rect::new(2, 2, 1, 1)));
+        // Touching the right and bottom edges from the inside
+        assert!(outer.contains_rect(Rect::new(8, 8, 4, 4)));
+        assert!(!outer.contains_rect(Rect::new(8, 8, 5, 4)));
+        assert!(!outer.contains_rect(Rect::new(1, 2, 3, 3)));
+    }
+
+    #[test]
+    fn rect_clamp_point() {
+        let rect = Rect::new(2, 2, 4, 3);
+
+        assert_eq!(rect.clamp_point(Point::new(3, 3)), Point::new(3, 3));
+        assert_eq!(rect.clamp_point(Point::new(0, 0)), Point::new(2, 2));
+        assert_eq!(rect.clamp_point(Point::new(100, 100)), Point::new(5, 4));
+        assert_eq!(
+            Rect::new(4, 4, 0, 0).clamp_point(Point::new(9, 0)),
+            Point::new(4, 4)
+        );
+    }
+
+    #[test]
+    fn rect_split_h() {
+        let rect = Rect::new(5, 2, 100, 10);
+
+        assert_eq!(
+            rect.split_h(30),
+            (Rect::new(5, 2, 30, 10), Rect::new(35, 2, 70, 10))
+        );
+        assert_eq!(
+            rect.split_h(200),
+            (Rect::new(5, 2, 100, 10), Rect::new(105, 2, 0, 10))
+        );
+    }
+
+    #[test]
+    fn rect_split_v() {
+        let rect = Rect::new(5, 2, 10, 100);
+
+        assert_eq!(
+            rect.split_v(30),
+            (Rect::new(5, 2, 10, 30), Rect::new(5, 32, 10, 70))
+        );
+        assert_eq!(
+            rect.split_v(200),
+            (Rect::new(5, 2, 10, 100), Rect::new(5, 102, 10, 0))
+        );
+    }
+
+    #[test]
+    fn lerp_cells() {
+        assert_eq!(lerp(10, 20, 0.0), 10);
+        assert_eq!(lerp(10, 20, 0.5), 15);
+        assert_eq!(lerp(10, 20, 1.0), 20);
+        assert_eq!(lerp(20, 10, 0.25), 18);
+
+        // Extrapolates, saturating at the edges
+        assert_eq!(lerp(10, 20, 1.5), 25);
+        assert_eq!(lerp(10, 20, -2.0), 0);
+    }
+
+    #[test]
+    fn geometry_lerp() {
+        let from = Rect::new(0, 0, 10, 4);
+        let to = Rect::new(20, 10, 30, 8);
+
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 0.5), Rect::new(10, 5, 20, 6));
+        assert_eq!(from.lerp(to, 1.0), to);
+
+        assert_eq!(
+            Point::new(0, 10).lerp(Point::new(10, 0), 0.5),
+            Point::new(5, 5)
+        );
+        assert_eq!(Size::new(2, 2).lerp(Size::new(4, 8), 1.0), Size::new(4, 8));
+        assert_eq!(Size::new(2, 2).lerp(Size::new(4, 8), 0.0), Size::new(2, 2));
+    }
+
+    #[test]
+    fn rect_union() {
+        let a = Rect::new(2, 3, 4, 2);
+        let b = Rect::new(5, 1, 10, 1);
+
+        assert_eq!(a.union(b), Rect::new(2, 1, 13, 4));
+        assert_eq!(a.union(b), b.union(a));
+        assert_eq!(a.union(a), a);
+    }
+
+    #[test]
+    fn rect_bounding_box_skips_empty() {
+        let rects = [
+            Rect::new(4, 4, 2, 2),
+            Rect::ZERO,
+            Rect::new(10, 8, 0, 5),
+            Rect::new(6, 5, 3, 3),
+        ];
+
+        assert_eq!(Rect::bounding_box(&rects), Some(Rect::new(4, 4, 5, 4)));
+        assert_eq!(Rect::bounding_box(&[Rect::ZERO]), None);
+        assert_eq!(Rect::bounding_box(&[]), None);
+    }
+}
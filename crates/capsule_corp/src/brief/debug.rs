@@ -0,0 +1,71 @@
+use std::fmt::Write;
+
+use crate::{CapsuleDocument, CapsuleElement, CapsuleNode, Edges};
+
+/// Render the computed layout below `root` as an indented tree, one node per
+/// line, for debugging.
+///
+/// Each line shows the node's location relative to its parent's content box,
+/// its border box size, padding and border (top, right, bottom, left) and
+/// content size, all in cells.
+#[must_use]
+pub fn debug_tree<D: CapsuleDocument>(document: &D, root: D::NodeId) -> String {
+    let mut out = String::new();
+    write_node(document, root, 0, &mut out);
+    out
+}
+
+fn write_node<D: CapsuleDocument>(document: &D, node: D::NodeId, depth: usize, out: &mut String) {
+    let layout = document.get_node(node).layout();
+    let resolved = layout.resolved_box;
+    let border_box = resolved.border_box_size();
+
+    let _ = writeln!(
+        out,
+        "{:indent$}{} @ ({}, {}) {}x{} padding {} border {} content {}x{}",
+        "",
+        label(document, node),
+        layout.location.x,
+        layout.location.y,
+        border_box.width,
+        border_box.height,
+        edges(resolved.padding),
+        edges(resolved.border),
+        resolved.content_size.width,
+        resolved.content_size.height,
+        indent = depth * 2,
+    );
+
+    for child in document.children(node) {
+        write_node(document, child, depth + 1, out);
+    }
+}
+
+fn label<D: CapsuleDocument>(document: &D, node: D::NodeId) -> String {
+    if document.get_node(node).text_content().is_some() {
+        return "#text".to_string();
+    }
+
+    let Some(element) = document.get_element(node) else {
+        return "#document".to_string();
+    };
+
+    let mut label = element.tag_name().to_string();
+
+    if let Some(id) = element.id() {
+        let _ = write!(label, "#{id}");
+    }
+
+    element.each_class(|class| {
+        let _ = write!(label, ".{class}");
+    });
+
+    label
+}
+
+fn edges(edges: Edges<u16>) -> String {
+    format!(
+        "{} {} {} {}",
+        edges.top, edges.right, edges.bottom, edges.left
+    )
+}
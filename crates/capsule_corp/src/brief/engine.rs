@@ -1,6 +1,10 @@
 use crate::{
     AvailableSpace, CapsuleDocument, CapsuleNode, Constraints, Display, Edges, Layout, Point, Size,
-    brief::{box_model::ResolvedBox, flex, resolve::resolve_size_constraints, text::measure_text},
+    WhiteSpace,
+    brief::{
+        box_model::ResolvedBox, flex, grid, position, resolve::resolve_size_constraints,
+        text::measure_text,
+    },
 };
 
 pub fn compute_layout<D: CapsuleDocument>(document: &mut D, root: D::NodeId, viewport: Size) {
@@ -8,6 +12,31 @@ pub fn compute_layout<D: CapsuleDocument>(document: &mut D, root: D::NodeId, vie
 
     let resolved_box = compute_node_box(document, root, constraints, true);
 
+    // The viewport is the containing block for anything without a positioned
+    // ancestor
+    let positioned = document
+        .get_node(root)
+        .computed_style()
+        .is_some_and(|style| style.position.is_positioned());
+
+    if !positioned {
+        position::layout_absolute_descendants(
+            document,
+            root,
+            viewport,
+            Point::new(
+                resolved_box
+                    .border
+                    .left
+                    .saturating_add(resolved_box.padding.left),
+                resolved_box
+                    .border
+                    .top
+                    .saturating_add(resolved_box.padding.top),
+            ),
+        );
+    }
+
     document.get_node_mut(root).set_layout(Layout {
         order: 0,
         location: Point::ZERO,
@@ -30,12 +59,15 @@ pub fn compute_node_box<D: CapsuleDocument>(
     }
 
     if let Some(text) = node.text_content() {
-        let parent_style = document
-            .parent(node_id)
-            .and_then(|parent| document.get_node(parent).computed_style())
-            .cloned()
-            .unwrap_or_default();
-        let size = measure_text(text, parent_style.white_space, constraints.width);
+        let white_space = if node.is_preformatted() {
+            WhiteSpace::Pre
+        } else {
+            document
+                .parent(node_id)
+                .and_then(|parent| document.get_node(parent).computed_style())
+                .map_or_else(WhiteSpace::default, |style| style.white_space)
+        };
+        let size = measure_text(text, white_space, constraints.width);
         document.get_node_mut(node_id).clear_needs_layout();
         return size.into();
     }
@@ -58,41 +90,82 @@ pub fn compute_node_box<D: CapsuleDocument>(
 
     let size_constraints = resolve_size_constraints(&style, parent_width, parent_height);
 
-    let margin = style.margin.resolve(parent_width);
+    let margin = style.margin.resolve(parent_width).clamped();
     let border = style.border_style.to_widths();
     let padding = style.padding.resolve(parent_width);
 
     let border_padding_h = border.horizontal().saturating_add(padding.horizontal());
     let border_padding_v = border.vertical().saturating_add(padding.vertical());
-    let content_constraints = constraints.shrink(border_padding_h, border_padding_v);
+    let available = constraints.shrink(border_padding_h, border_padding_v);
+
+    // Children lay out inside the box's own size when it has one, so their
+    // percentages (and the container's gaps) resolve against it
+    let content_constraints = Constraints::new(
+        size_constraints.width.map_or(available.width, |w| {
+            AvailableSpace::Definite(size_constraints.clamp_width(w))
+        }),
+        size_constraints.height.map_or(available.height, |h| {
+            AvailableSpace::Definite(size_constraints.clamp_height(h))
+        }),
+    );
 
     let content_size = match style.display {
         Display::Block => layout_block(document, node_id, content_constraints),
         Display::Flex => flex::layout(document, node_id, &style, content_constraints),
         Display::Inline => layout_inline(document, node_id, content_constraints),
-        Display::Grid => layout_grid(document, node_id, content_constraints),
+        Display::Grid => grid::layout(document, node_id, &style, content_constraints),
         Display::None => unreachable!(),
     };
 
-    let final_content_size = Size::new(
-        size_constraints.width.map_or_else(
-            || {
-                size_constraints
-                    .clamp_width(content_size.width.saturating_add(border_padding_h))
-                    .saturating_sub(border_padding_h)
-            },
-            |w| size_constraints.clamp_width(w),
-        ),
-        size_constraints.height.map_or_else(
-            || {
-                size_constraints
-                    .clamp_height(content_size.height.saturating_add(border_padding_v))
-                    .saturating_sub(border_padding_v)
-            },
-            |h| size_constraints.clamp_height(h),
-        ),
+    let width = size_constraints.width.map_or_else(
+        || {
+            size_constraints
+                .clamp_width(content_size.width.saturating_add(border_padding_h))
+                .saturating_sub(border_padding_h)
+        },
+        |w| size_constraints.clamp_width(w),
+    );
+
+    // With only a height given, aspect-ratio decides the width. Otherwise the
+    // width wins and the height follows from it.
+    let width = match (size_constraints.width, size_constraints.height) {
+        (None, Some(h)) => style
+            .aspect_ratio
+            .width_for(h)
+            .map_or(width, |w| size_constraints.clamp_width(w)),
+        _ => width,
+    };
+
+    let height = size_constraints.height.map_or_else(
+        || {
+            style.aspect_ratio.height_for(width).map_or_else(
+                || {
+                    size_constraints
+                        .clamp_height(content_size.height.saturating_add(border_padding_v))
+                        .saturating_sub(border_padding_v)
+                },
+                |h| size_constraints.clamp_height(h),
+            )
+        },
+        |h| size_constraints.clamp_height(h),
     );
 
+    let final_content_size = Size::new(width, height);
+
+    position::offset_relative_children(document, node_id, final_content_size);
+
+    if style.position.is_positioned() {
+        position::layout_absolute_descendants(
+            document,
+            node_id,
+            Size::new(
+                width.saturating_add(padding.horizontal()),
+                height.saturating_add(padding.vertical()),
+            ),
+            Point::new(padding.left, padding.top),
+        );
+    }
+
     ResolvedBox {
         margin,
         border,
@@ -101,53 +174,102 @@ pub fn compute_node_box<D: CapsuleDocument>(
     }
 }
 
+/// Lays `node` out only to find out how big it would be. The subtree is left
+/// laid out for constraints the real pass won't use, so it's marked dirty to
+/// keep that pass from reusing it.
+pub fn measure_node_box<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    constraints: Constraints,
+) -> ResolvedBox {
+    let resolved_box = compute_node_box(document, node, constraints, true);
+    mark_subtree_needs_layout(document, node);
+
+    resolved_box
+}
+
+fn mark_subtree_needs_layout<D: CapsuleDocument>(document: &mut D, node: D::NodeId) {
+    document.get_node_mut(node).mark_needs_layout();
+
+    let children: Vec<_> = document.children(node).collect();
+    for child in children {
+        mark_subtree_needs_layout(document, child);
+    }
+}
+
 fn layout_block<D: CapsuleDocument>(
     document: &mut D,
     node: D::NodeId,
     constraints: Constraints,
 ) -> Size {
     let available_width = constraints.width.as_definite().unwrap_or(0);
-    let mut y = 0u16;
+    // Negative margins can pull the cursor above the top, only the
+    // locations handed out get clamped
+    let mut y = 0i32;
+    // Without a definite width the block is being measured, and it's as wide
+    // as its widest child
+    let mut intrinsic_width = 0u16;
 
     let children: Vec<_> = document.children(node).collect();
 
     for child in children {
         let style = document.get_node(child).computed_style();
 
-        if style.is_some_and(|s| matches!(s.display, Display::None)) {
+        if style.is_some_and(|s| !s.in_flow()) {
             continue;
         }
 
-        let child_margin = style.map_or(Edges::ZERO, |s| s.margin.resolve(available_width));
+        let child_margin = style.map_or_else(Edges::default, |s| s.margin.resolve(available_width));
 
-        y = y.saturating_add(child_margin.top);
+        y += i32::from(child_margin.top);
+        let child_y = clamp_cells(y);
 
-        let child_available_width = available_width
-            .saturating_sub(child_margin.left)
-            .saturating_sub(child_margin.right);
+        let child_available_width =
+            available_width.saturating_add_signed(child_margin.horizontal().saturating_neg());
 
         let child_constraints = Constraints::new(
-            AvailableSpace::Definite(child_available_width),
-            constraints.height.shrink(y),
+            match constraints.width {
+                AvailableSpace::Definite(_) => AvailableSpace::Definite(child_available_width),
+                intrinsic => intrinsic,
+            },
+            constraints.height.shrink(child_y),
         );
 
         let child_box = compute_node_box(document, child, child_constraints, false);
+        let child_height = child_box.border_box_size().height;
+
+        intrinsic_width = intrinsic_width.max(
+            child_box
+                .border_box_size()
+                .width
+                .saturating_add_signed(child_margin.horizontal()),
+        );
 
         document.get_node_mut(child).set_layout(Layout {
             order: 0,
-            location: Point::new(child_margin.left, y),
+            location: Point::new(clamp_cells(i32::from(child_margin.left)), child_y),
             scrollbar_size: Size::ZERO,
             resolved_box: ResolvedBox {
-                margin: child_margin,
+                margin: child_margin.clamped(),
                 ..child_box
             },
         });
 
-        y = y.saturating_add(child_box.border_box_size().height);
-        y = y.saturating_add(child_margin.bottom);
+        y += i32::from(child_height) + i32::from(child_margin.bottom);
     }
 
-    Size::new(available_width, y)
+    let width = if constraints.width.is_definite() {
+        available_width
+    } else {
+        intrinsic_width
+    };
+
+    Size::new(width, clamp_cells(y))
+}
+
+/// A block cursor that margins may have pulled below zero, as a location.
+fn clamp_cells(value: i32) -> u16 {
+    u16::try_from(value.max(0)).unwrap_or(u16::MAX)
 }
 
 fn layout_inline<D: CapsuleDocument>(
@@ -167,11 +289,12 @@ fn layout_inline<D: CapsuleDocument>(
     for child in children {
         let style = document.get_node(child).computed_style();
 
-        if style.is_some_and(|s| matches!(s.display, Display::None)) {
+        if style.is_some_and(|s| !s.in_flow()) {
             continue;
         }
 
-        let child_margin = style.map_or(Edges::ZERO, |s| s.margin.resolve(available_width));
+        let child_margin =
+            style.map_or(Edges::ZERO, |s| s.margin.resolve(available_width).clamped());
         let child_constraints = Constraints::new(
             AvailableSpace::Definite(
                 available_width
@@ -220,11 +343,3 @@ fn layout_inline<D: CapsuleDocument>(
 
     Size::new(max_width, y)
 }
-
-fn layout_grid<D: CapsuleDocument>(
-    _document: &mut D,
-    _node: D::NodeId,
-    _constraints: Constraints,
-) -> Size {
-    todo!("grid")
-}
@@ -65,7 +65,7 @@ fn justify_line<NodeId: Copy>(
 
     let mut main_position = start_offset;
     for item in &mut line.items {
-        item.main_position = main_position.saturating_add(item.margin.left);
+        item.main_position = main_position.saturating_add_signed(item.margin.left);
         main_position = main_position
             .saturating_add(item.outer_main_size())
             .saturating_add(between_space);
@@ -99,16 +99,15 @@ fn align_line<NodeId: Copy>(line: &mut FlexLine<NodeId>, default_align: AlignIte
             FlexEnd | End => free_space,
             Center => free_space / 2,
             Stretch => {
-                item.cross_size = line_cross_size
-                    .saturating_sub(item.margin.top)
-                    .saturating_sub(item.margin.bottom);
+                item.cross_size =
+                    line_cross_size.saturating_add_signed(item.margin.vertical().saturating_neg());
                 0
             }
         };
 
         item.cross_position = line_cross_position
             .saturating_add(cross_offset)
-            .saturating_add(item.margin.top);
+            .saturating_add_signed(item.margin.top);
     }
 }
 
@@ -139,7 +138,7 @@ mod tests {
             min_main_size: 0,
             max_main_size: None,
             hypothetical_main_size: main_size,
-            margin: Edges::ZERO,
+            margin: Edges::default(),
             resolved_box: ResolvedBox::ZERO,
             align_self: AlignSelf::Auto,
             frozen: true,
@@ -1,7 +1,10 @@
 use crate::{
     AlignSelf, AvailableSpace, CapsuleDocument, CapsuleNode, ComputedStyle, Constraints, Dimension,
-    Display, Edges, FlexDirection,
-    brief::{engine::compute_node_box, flex::core::FlexItem},
+    Edges, FlexDirection,
+    brief::{
+        engine::{compute_node_box, measure_node_box},
+        flex::core::FlexItem,
+    },
 };
 
 pub fn collect_flex_items<D: CapsuleDocument>(
@@ -43,7 +46,7 @@ pub fn collect_flex_items<D: CapsuleDocument>(
                 min_main_size: main_size,
                 max_main_size: Some(main_size),
                 hypothetical_main_size: main_size,
-                margin: Edges::ZERO,
+                margin: Edges::default(),
                 resolved_box,
                 frozen: true, // text doesn't grow/shrink
                 main_size,
@@ -61,25 +64,20 @@ pub fn collect_flex_items<D: CapsuleDocument>(
             .cloned()
             .expect("non-text node must have style");
 
-        if matches!(style.display, Display::None) {
+        if !style.in_flow() {
             continue;
         }
 
         let margin = style.margin.resolve(available_main_cells);
-        let flex_basis =
-            resolve_flex_basis(&style.flex_basis, is_row, &style, available_main_cells);
 
-        let (min_main, max_main) = if is_row {
-            (
-                style.min_width.resolve(available_main_cells).unwrap_or(0),
-                style.max_width.resolve(available_main_cells),
-            )
-        } else {
-            (
-                style.min_height.resolve(available_main_cells).unwrap_or(0),
-                style.max_height.resolve(available_main_cells),
-            )
-        };
+        let (flex_basis, min_main, max_main) = resolve_main_sizes(
+            document,
+            child,
+            &style,
+            is_row,
+            available_main_cells,
+            available_cross,
+        );
 
         let hypothetical_main_size = clamp(flex_basis, min_main, max_main);
 
@@ -125,24 +123,69 @@ pub fn collect_flex_items<D: CapsuleDocument>(
     items
 }
 
-fn resolve_flex_basis(
-    flex_basis: &Dimension,
-    is_row: bool,
+/// The flex basis and the min and max sizes along the main axis.
+fn resolve_main_sizes<D: CapsuleDocument>(
+    document: &mut D,
+    child: D::NodeId,
     style: &ComputedStyle,
-    available: u16,
-) -> u16 {
-    match flex_basis {
-        Dimension::Length(length) => length.resolve(available),
-        Dimension::Auto => {
-            let size = if is_row { &style.width } else { &style.height };
-
-            match size {
-                Dimension::Length(length) => length.resolve(available),
-                _ => 0,
-            }
-        }
-        Dimension::None => 0,
-    }
+    is_row: bool,
+    available_main: u16,
+    available_cross: AvailableSpace,
+) -> (u16, u16, Option<u16>) {
+    let (size, min_size, max_size) = if is_row {
+        (&style.width, &style.min_width, &style.max_width)
+    } else {
+        (&style.height, &style.min_height, &style.max_height)
+    };
+
+    let mut resolve = |dimension: &Dimension| {
+        resolve_main_size(
+            document,
+            child,
+            dimension,
+            is_row,
+            available_main,
+            available_cross,
+        )
+    };
+
+    let flex_basis = match &style.flex_basis {
+        Dimension::Auto => resolve(size),
+        basis => resolve(basis),
+    };
+
+    (
+        flex_basis.unwrap_or(0),
+        resolve(min_size).unwrap_or(0),
+        resolve(max_size),
+    )
+}
+
+/// Resolves a main axis size. `min-content` and `max-content` lay the item
+/// out to find out how big its content is.
+fn resolve_main_size<D: CapsuleDocument>(
+    document: &mut D,
+    child: D::NodeId,
+    dimension: &Dimension,
+    is_row: bool,
+    available_main: u16,
+    available_cross: AvailableSpace,
+) -> Option<u16> {
+    let intrinsic = match dimension {
+        Dimension::MinContent => AvailableSpace::MinContent,
+        Dimension::MaxContent => AvailableSpace::MaxContent,
+        _ => return dimension.resolve(available_main),
+    };
+
+    let constraints = if is_row {
+        Constraints::new(intrinsic, available_cross)
+    } else {
+        Constraints::new(available_cross, intrinsic)
+    };
+
+    let size = measure_node_box(document, child, constraints).border_box_size();
+
+    Some(if is_row { size.width } else { size.height })
 }
 
 fn clamp(value: u16, min: u16, max: Option<u16>) -> u16 {
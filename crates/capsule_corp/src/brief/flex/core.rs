@@ -10,7 +10,7 @@ pub struct FlexItem<NodeId: Clone + Copy> {
     pub min_main_size: u16,
     pub max_main_size: Option<u16>,
     pub hypothetical_main_size: u16,
-    pub margin: Edges<u16>,
+    pub margin: Edges<i16>,
     pub resolved_box: ResolvedBox,
     pub frozen: bool,
     pub main_size: u16,
@@ -22,20 +22,17 @@ pub struct FlexItem<NodeId: Clone + Copy> {
 impl<NodeId: Copy> FlexItem<NodeId> {
     pub const fn outer_main_size(&self) -> u16 {
         self.main_size
-            .saturating_add(self.margin.left)
-            .saturating_add(self.margin.right)
+            .saturating_add_signed(self.margin.horizontal())
     }
 
     pub const fn outer_cross_size(&self) -> u16 {
         self.cross_size
-            .saturating_add(self.margin.top)
-            .saturating_add(self.margin.bottom)
+            .saturating_add_signed(self.margin.vertical())
     }
 
     pub const fn outer_hypothetical_main_size(&self) -> u16 {
         self.hypothetical_main_size
-            .saturating_add(self.margin.left)
-            .saturating_add(self.margin.right)
+            .saturating_add_signed(self.margin.horizontal())
     }
 }
 
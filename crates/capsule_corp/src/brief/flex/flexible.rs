@@ -167,7 +167,7 @@ mod tests {
             min_main_size: 0,
             max_main_size: None,
             hypothetical_main_size,
-            margin: Edges::ZERO,
+            margin: Edges::default(),
             resolved_box: ResolvedBox::ZERO,
             frozen: false,
             main_size: hypothetical_main_size,
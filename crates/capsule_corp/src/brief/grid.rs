@@ -0,0 +1,460 @@
+use crate::{
+    AvailableSpace, CapsuleDocument, CapsuleNode, ComputedStyle, Constraints, Dimension, Edges,
+    GridPlacement, Layout, Point, Size, TrackSize,
+    brief::{box_model::ResolvedBox, engine::compute_node_box},
+};
+
+#[derive(Debug, Clone, Copy)]
+struct GridItem<N> {
+    node_id: N,
+    column: usize,
+    row: usize,
+    column_span: usize,
+    row_span: usize,
+}
+
+/// A track sizing input: an item covering `span` tracks from `start` needs
+/// `size` cells of them.
+#[derive(Debug, Clone, Copy)]
+struct Contribution {
+    start: usize,
+    span: usize,
+    size: u16,
+}
+
+/// Perform grid layout on a container
+///
+/// Returns the content size
+pub fn layout<D: CapsuleDocument>(
+    document: &mut D,
+    node_id: D::NodeId,
+    style: &ComputedStyle,
+    constraints: Constraints,
+) -> Size {
+    let available_width = constraints.width.as_definite();
+    let available_height = constraints.height.as_definite();
+
+    let column_gap = style.column_gap.resolve(available_width.unwrap_or(0));
+    let row_gap = style.row_gap.resolve(available_height.unwrap_or(0));
+
+    let (items, column_count, row_count) = place_items(document, node_id, style);
+
+    let column_tracks = tracks(&style.grid_template_columns, column_count);
+    let row_tracks = tracks(&style.grid_template_rows, row_count);
+
+    let column_contributions: Vec<_> = items
+        .iter()
+        .map(|item| {
+            let unconstrained = Constraints::new(AvailableSpace::MaxContent, constraints.height);
+            let (size, margin) = measure(document, item.node_id, unconstrained);
+
+            Contribution {
+                start: item.column,
+                span: item.column_span,
+                size: size.width.saturating_add(margin.horizontal()),
+            }
+        })
+        .collect();
+
+    let columns = size_tracks(
+        &column_tracks,
+        available_width,
+        column_gap,
+        &column_contributions,
+    );
+
+    let row_contributions: Vec<_> = items
+        .iter()
+        .map(|item| {
+            let width = span_size(&columns, item.column, item.column_span, column_gap);
+            let (size, margin) = measure(
+                document,
+                item.node_id,
+                Constraints::new(AvailableSpace::Definite(width), constraints.height),
+            );
+
+            Contribution {
+                start: item.row,
+                span: item.row_span,
+                size: size.height.saturating_add(margin.vertical()),
+            }
+        })
+        .collect();
+
+    let rows = size_tracks(&row_tracks, available_height, row_gap, &row_contributions);
+
+    for item in &items {
+        let area = Size::new(
+            span_size(&columns, item.column, item.column_span, column_gap),
+            span_size(&rows, item.row, item.row_span, row_gap),
+        );
+        let origin = Point::new(
+            track_start(&columns, item.column, column_gap),
+            track_start(&rows, item.row, row_gap),
+        );
+
+        write_item(document, item.node_id, origin, area);
+    }
+
+    Size::new(
+        span_size(&columns, 0, columns.len(), column_gap),
+        span_size(&rows, 0, rows.len(), row_gap),
+    )
+}
+
+/// Assign every in-flow child a grid area. Items with both a definite row
+/// and column go first, the rest flow into the first free cells in row
+/// order, adding rows as needed.
+///
+/// Returns the items along with the number of columns and rows.
+fn place_items<D: CapsuleDocument>(
+    document: &D,
+    node_id: D::NodeId,
+    style: &ComputedStyle,
+) -> (Vec<GridItem<D::NodeId>>, usize, usize) {
+    let explicit_columns = u16::try_from(style.grid_template_columns.len()).unwrap_or(u16::MAX);
+    let explicit_rows = u16::try_from(style.grid_template_rows.len()).unwrap_or(u16::MAX);
+
+    let placements: Vec<_> = document
+        .children(node_id)
+        .filter_map(|child| {
+            let (column, row) = match document.get_node(child).computed_style() {
+                Some(style) if !style.in_flow() => return None,
+                Some(style) => (style.grid_column, style.grid_row),
+                None => (GridPlacement::default(), GridPlacement::default()),
+            };
+
+            let (column, column_span) = column.resolve(explicit_columns);
+            let (row, row_span) = row.resolve(explicit_rows);
+
+            Some((
+                child,
+                column.map(usize::from),
+                usize::from(column_span),
+                row.map(usize::from),
+                usize::from(row_span),
+            ))
+        })
+        .collect();
+
+    let column_count = placements
+        .iter()
+        .map(|(_, column, span, _, _)| column.unwrap_or(0) + span)
+        .fold(usize::from(explicit_columns).max(1), usize::max);
+
+    let mut occupied = Occupancy::new(column_count);
+    let mut items = Vec::with_capacity(placements.len());
+
+    for &(node_id, column, column_span, row, row_span) in &placements {
+        if let (Some(column), Some(row)) = (column, row) {
+            occupied.fill(column, row, column_span, row_span);
+            items.push(GridItem {
+                node_id,
+                column,
+                row,
+                column_span,
+                row_span,
+            });
+        }
+    }
+
+    let mut cursor = (0, 0);
+
+    for &(node_id, column, column_span, row, row_span) in &placements {
+        let (column, row) = match (column, row) {
+            (Some(_), Some(_)) => continue,
+            (Some(column), None) => {
+                if column < cursor.1 {
+                    cursor.0 += 1;
+                }
+
+                // Past the last occupied row everything is free
+                let row = (cursor.0..occupied.rows())
+                    .find(|&row| occupied.is_free(column, row, column_span, row_span))
+                    .unwrap_or_else(|| occupied.rows().max(cursor.0));
+
+                cursor = (row, column + column_span);
+                (column, row)
+            }
+            (None, Some(row)) => {
+                let column = (0..=column_count - column_span)
+                    .find(|&column| occupied.is_free(column, row, column_span, row_span))
+                    .unwrap_or(0);
+
+                (column, row)
+            }
+            (None, None) => {
+                let (mut row, mut column) = cursor;
+
+                loop {
+                    if column + column_span > column_count {
+                        row += 1;
+                        column = 0;
+                    } else if occupied.is_free(column, row, column_span, row_span) {
+                        break;
+                    } else {
+                        column += 1;
+                    }
+                }
+
+                cursor = (row, column + column_span);
+                (column, row)
+            }
+        };
+
+        occupied.fill(column, row, column_span, row_span);
+        items.push(GridItem {
+            node_id,
+            column,
+            row,
+            column_span,
+            row_span,
+        });
+    }
+
+    let row_count = occupied.rows().max(usize::from(explicit_rows));
+
+    (items, column_count, row_count)
+}
+
+/// Which cells of the grid already hold an item.
+struct Occupancy {
+    columns: usize,
+    cells: Vec<bool>,
+}
+
+impl Occupancy {
+    const fn new(columns: usize) -> Self {
+        Self {
+            columns,
+            cells: Vec::new(),
+        }
+    }
+
+    const fn rows(&self) -> usize {
+        self.cells.len() / self.columns
+    }
+
+    fn is_free(&self, column: usize, row: usize, column_span: usize, row_span: usize) -> bool {
+        (row..row + row_span).all(|row| {
+            (column..column + column_span).all(|column| {
+                !self
+                    .cells
+                    .get(row * self.columns + column)
+                    .copied()
+                    .unwrap_or(false)
+            })
+        })
+    }
+
+    fn fill(&mut self, column: usize, row: usize, column_span: usize, row_span: usize) {
+        let needed = (row + row_span) * self.columns;
+        if self.cells.len() < needed {
+            self.cells.resize(needed, false);
+        }
+
+        for row in row..row + row_span {
+            for column in column..column + column_span {
+                self.cells[row * self.columns + column] = true;
+            }
+        }
+    }
+}
+
+/// The explicit tracks, padded out with `auto` ones to `count`.
+fn tracks(template: &[TrackSize], count: usize) -> Vec<TrackSize> {
+    template
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat(TrackSize::Auto))
+        .take(count.max(template.len()))
+        .collect()
+}
+
+/// Size each track: lengths are fixed, `auto` tracks grow to fit their
+/// items and `fr` tracks share whatever is left of `available`. Without a
+/// definite `available`, `fr` tracks size like `auto` ones.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn size_tracks(
+    tracks: &[TrackSize],
+    available: Option<u16>,
+    gap: u16,
+    contributions: &[Contribution],
+) -> Vec<u16> {
+    let flexible = |track: &TrackSize| match track {
+        TrackSize::Length(_) => false,
+        TrackSize::Auto => true,
+        TrackSize::Fraction(_) => available.is_none(),
+    };
+
+    let mut sizes: Vec<u16> = tracks
+        .iter()
+        .map(|track| match track {
+            TrackSize::Length(length) => length.resolve(available.unwrap_or(0)),
+            TrackSize::Auto | TrackSize::Fraction(_) => 0,
+        })
+        .collect();
+
+    for contribution in contributions.iter().filter(|c| c.span == 1) {
+        if flexible(&tracks[contribution.start]) {
+            let size = &mut sizes[contribution.start];
+            *size = (*size).max(contribution.size);
+        }
+    }
+
+    // Items spanning several tracks grow the last flexible one they cover
+    for contribution in contributions.iter().filter(|c| c.span > 1) {
+        let covered = span_size(&sizes, contribution.start, contribution.span, gap);
+        let deficit = contribution.size.saturating_sub(covered);
+
+        let range = contribution.start..contribution.start + contribution.span;
+        if let Some(index) = range.rev().find(|&index| flexible(&tracks[index])) {
+            sizes[index] = sizes[index].saturating_add(deficit);
+        }
+    }
+
+    let Some(available) = available else {
+        return sizes;
+    };
+
+    let fractions: f32 = tracks
+        .iter()
+        .filter_map(|track| match track {
+            TrackSize::Fraction(fr) => Some(*fr),
+            _ => None,
+        })
+        .sum();
+
+    if fractions <= 0.0 {
+        return sizes;
+    }
+
+    let gaps =
+        gap.saturating_mul(u16::try_from(tracks.len().saturating_sub(1)).unwrap_or(u16::MAX));
+    let free = available
+        .saturating_sub(
+            sizes
+                .iter()
+                .fold(0u16, |sum, size| sum.saturating_add(*size)),
+        )
+        .saturating_sub(gaps);
+
+    // Less than `1fr` in total only hands out that share of the free space
+    let divisor = fractions.max(1.0);
+    let mut seen = 0.0;
+    let mut assigned = 0u16;
+
+    for (size, track) in sizes.iter_mut().zip(tracks) {
+        if let TrackSize::Fraction(fr) = track {
+            seen += fr;
+            let end = (f32::from(free) * seen / divisor).round() as u16;
+            *size = end.saturating_sub(assigned);
+            assigned = end;
+        }
+    }
+
+    sizes
+}
+
+/// Cells covered by `span` tracks from `start`, including the gaps between
+/// them.
+fn span_size(sizes: &[u16], start: usize, span: usize, gap: u16) -> u16 {
+    let tracks = &sizes[start..start + span];
+    let gaps = u16::try_from(span.saturating_sub(1)).unwrap_or(u16::MAX);
+
+    tracks
+        .iter()
+        .fold(0u16, |sum, size| sum.saturating_add(*size))
+        .saturating_add(gap.saturating_mul(gaps))
+}
+
+/// Offset of track `index` from the start of the grid.
+fn track_start(sizes: &[u16], index: usize, gap: u16) -> u16 {
+    if index == 0 {
+        return 0;
+    }
+
+    span_size(sizes, 0, index, gap).saturating_add(gap)
+}
+
+/// Border box size and margins of a node laid out within `constraints`.
+fn measure<D: CapsuleDocument>(
+    document: &mut D,
+    node_id: D::NodeId,
+    constraints: Constraints,
+) -> (Size, Edges<u16>) {
+    let margin = margin(
+        document,
+        node_id,
+        constraints.width.as_definite().unwrap_or(0),
+    );
+    let constraints = Constraints::new(
+        constraints.width.shrink(margin.horizontal()),
+        constraints.height.shrink(margin.vertical()),
+    );
+
+    let resolved_box = compute_node_box(document, node_id, constraints, true);
+
+    (resolved_box.border_box_size(), margin)
+}
+
+fn margin<D: CapsuleDocument>(document: &D, node_id: D::NodeId, width: u16) -> Edges<u16> {
+    document
+        .get_node(node_id)
+        .computed_style()
+        .map_or(Edges::ZERO, |style| style.margin.resolve(width).clamped())
+}
+
+/// Lay the item out in its grid area, stretching it to fill any dimension
+/// it doesn't size itself.
+fn write_item<D: CapsuleDocument>(document: &mut D, node_id: D::NodeId, origin: Point, area: Size) {
+    let margin = margin(document, node_id, area.width);
+    let inner = Size::new(
+        area.width.saturating_sub(margin.horizontal()),
+        area.height.saturating_sub(margin.vertical()),
+    );
+
+    let resolved_box = compute_node_box(document, node_id, Constraints::from_size(inner), true);
+
+    let (stretch_width, stretch_height) =
+        document
+            .get_node(node_id)
+            .computed_style()
+            .map_or((false, false), |style| {
+                (
+                    matches!(style.width, Dimension::Auto),
+                    matches!(style.height, Dimension::Auto),
+                )
+            });
+
+    let content_size = Size::new(
+        if stretch_width {
+            inner
+                .width
+                .saturating_sub(resolved_box.border_padding_horizontal())
+        } else {
+            resolved_box.content_size.width
+        },
+        if stretch_height {
+            inner
+                .height
+                .saturating_sub(resolved_box.border_padding_vertical())
+        } else {
+            resolved_box.content_size.height
+        },
+    );
+
+    document.get_node_mut(node_id).set_layout(Layout {
+        order: 0,
+        location: Point::new(
+            origin.x.saturating_add(margin.left),
+            origin.y.saturating_add(margin.top),
+        ),
+        scrollbar_size: Size::ZERO,
+        resolved_box: ResolvedBox {
+            content_size,
+            margin,
+            ..resolved_box
+        },
+    });
+}
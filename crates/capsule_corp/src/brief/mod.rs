@@ -1,10 +1,15 @@
 mod box_model;
 mod core;
+mod debug;
 mod engine;
 mod flex;
+mod grid;
+mod position;
 mod resolve;
 mod text;
 
 pub use box_model::*;
 pub use core::*;
+pub use debug::debug_tree;
 pub use engine::compute_layout;
+pub use text::truncate_text;
@@ -0,0 +1,208 @@
+use crate::{
+    AvailableSpace, CapsuleDocument, CapsuleNode, Constraints, Display, Layout, Point, Position,
+    Size,
+    brief::{box_model::ResolvedBox, engine::compute_node_box},
+};
+
+/// Nudge relatively positioned children of `node` away from where normal flow
+/// put them, by their insets against the container's `content_size`.
+///
+/// `left` wins over `right` and `top` over `bottom`.
+pub fn offset_relative_children<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    content_size: Size,
+) {
+    let children: Vec<_> = document.children(node).collect();
+
+    for child in children {
+        let Some(style) = document.get_node(child).computed_style() else {
+            continue;
+        };
+
+        if !matches!(style.position, Position::Relative) {
+            continue;
+        }
+
+        let inset = &style.inset;
+        let left = inset.left.resolve(content_size.width);
+        let right = inset.right.resolve(content_size.width);
+        let top = inset.top.resolve(content_size.height);
+        let bottom = inset.bottom.resolve(content_size.height);
+
+        let mut layout = document.get_node(child).layout();
+        let location = layout.location;
+
+        layout.location = Point::new(
+            shift(location.x, left, right),
+            shift(location.y, top, bottom),
+        );
+
+        document.get_node_mut(child).set_layout(layout);
+    }
+}
+
+const fn shift(position: u16, start: Option<u16>, end: Option<u16>) -> u16 {
+    match (start, end) {
+        (Some(start), _) => position.saturating_add(start),
+        (None, Some(end)) => position.saturating_sub(end),
+        (None, None) => position,
+    }
+}
+
+/// Lay out the absolutely positioned descendants that `node` is the
+/// containing block for, now that its own size is known.
+///
+/// Insets are measured from the containing block, usually the padding box,
+/// with `content_offset` the offset from there to `node`'s content box. A box
+/// with no inset on an axis stays where its parent's content starts.
+///
+/// Locations are unsigned and relative to the parent's content box, so a box
+/// can't be placed before its parent's content. Insets that would put it there
+/// clamp to the content edge.
+pub fn layout_absolute_descendants<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    containing_block: Size,
+    content_offset: Point,
+) {
+    let mut found = Vec::new();
+    collect_absolute(document, node, Point::ZERO, &mut found);
+
+    for (child, parent_offset) in found {
+        layout_absolute(
+            document,
+            child,
+            parent_offset,
+            containing_block,
+            content_offset,
+        );
+    }
+}
+
+/// Find absolutely positioned boxes below `node`, along with the offset of
+/// their parent's content box from `node`'s. Positioned boxes are containing
+/// blocks of their own, so the search doesn't go past them.
+fn collect_absolute<D: CapsuleDocument>(
+    document: &D,
+    node: D::NodeId,
+    offset: Point,
+    found: &mut Vec<(D::NodeId, Point)>,
+) {
+    for child in document.children(node) {
+        let Some(style) = document.get_node(child).computed_style() else {
+            continue;
+        };
+
+        if matches!(style.display, Display::None) {
+            continue;
+        }
+
+        match style.position {
+            Position::Absolute => found.push((child, offset)),
+            Position::Relative => {}
+            Position::Static => {
+                let layout = document.get_node(child).layout();
+                let resolved = layout.resolved_box;
+
+                let child_offset = Point::new(
+                    offset
+                        .x
+                        .saturating_add(layout.location.x)
+                        .saturating_add(resolved.border.left)
+                        .saturating_add(resolved.padding.left),
+                    offset
+                        .y
+                        .saturating_add(layout.location.y)
+                        .saturating_add(resolved.border.top)
+                        .saturating_add(resolved.padding.top),
+                );
+
+                collect_absolute(document, child, child_offset, found);
+            }
+        }
+    }
+}
+
+fn layout_absolute<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    parent_offset: Point,
+    containing_block: Size,
+    content_offset: Point,
+) {
+    let style = document
+        .get_node(node)
+        .computed_style()
+        .cloned()
+        .expect("element node must have computed style");
+
+    let inset = &style.inset;
+    let left = inset.left.resolve(containing_block.width);
+    let right = inset.right.resolve(containing_block.width);
+    let top = inset.top.resolve(containing_block.height);
+    let bottom = inset.bottom.resolve(containing_block.height);
+
+    let margin = style.margin.resolve(containing_block.width).clamped();
+
+    let constraints = Constraints::new(
+        AvailableSpace::Definite(
+            containing_block
+                .width
+                .saturating_sub(left.unwrap_or(0))
+                .saturating_sub(right.unwrap_or(0))
+                .saturating_sub(margin.horizontal()),
+        ),
+        AvailableSpace::Definite(
+            containing_block
+                .height
+                .saturating_sub(top.unwrap_or(0))
+                .saturating_sub(bottom.unwrap_or(0))
+                .saturating_sub(margin.vertical()),
+        ),
+    );
+
+    let resolved_box = compute_node_box(document, node, constraints, false);
+    let size = resolved_box.border_box_size();
+
+    // Where the box goes in the containing block
+    let x = match (left, right) {
+        (Some(left), _) => left.saturating_add(margin.left),
+        (None, Some(right)) => containing_block
+            .width
+            .saturating_sub(right)
+            .saturating_sub(margin.right)
+            .saturating_sub(size.width),
+        (None, None) => content_offset
+            .x
+            .saturating_add(parent_offset.x)
+            .saturating_add(margin.left),
+    };
+    let y = match (top, bottom) {
+        (Some(top), _) => top.saturating_add(margin.top),
+        (None, Some(bottom)) => containing_block
+            .height
+            .saturating_sub(bottom)
+            .saturating_sub(margin.bottom)
+            .saturating_sub(size.height),
+        (None, None) => content_offset
+            .y
+            .saturating_add(parent_offset.y)
+            .saturating_add(margin.top),
+    };
+
+    document.get_node_mut(node).set_layout(Layout {
+        order: 0,
+        location: Point::new(
+            x.saturating_sub(content_offset.x)
+                .saturating_sub(parent_offset.x),
+            y.saturating_sub(content_offset.y)
+                .saturating_sub(parent_offset.y),
+        ),
+        scrollbar_size: Size::ZERO,
+        resolved_box: ResolvedBox {
+            margin,
+            ..resolved_box
+        },
+    });
+}
@@ -1,7 +1,9 @@
+use std::borrow::Cow;
+
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
-use crate::{Size, WhiteSpace, brief::core::AvailableSpace};
+use crate::{Size, TextOverflow, WhiteSpace, brief::core::AvailableSpace};
 
 pub fn measure_text(text: &str, white_space: WhiteSpace, available_width: AvailableSpace) -> Size {
     match white_space {
@@ -14,6 +16,35 @@ pub fn measure_text(text: &str, white_space: WhiteSpace, available_width: Availa
     }
 }
 
+/// Cut a single line of text down to `width` cells, ending it with `…` for
+/// [`TextOverflow::Ellipsis`]. Text that already fits is returned as is.
+#[must_use]
+pub fn truncate_text(text: &str, width: u16, overflow: TextOverflow) -> Cow<'_, str> {
+    if text.width() <= usize::from(width) {
+        return Cow::Borrowed(text);
+    }
+
+    let budget = match overflow {
+        TextOverflow::Clip => usize::from(width),
+        TextOverflow::Ellipsis => usize::from(width.saturating_sub(1)),
+    };
+
+    let mut used = 0;
+    let mut truncated: String = text
+        .graphemes(true)
+        .take_while(|grapheme| {
+            used += grapheme.width();
+            used <= budget
+        })
+        .collect();
+
+    if matches!(overflow, TextOverflow::Ellipsis) && width > 0 {
+        truncated.push('…');
+    }
+
+    Cow::Owned(truncated)
+}
+
 #[inline]
 #[must_use]
 fn measure_no_wrap(text: &str) -> Size {
@@ -159,4 +190,26 @@ mod tests {
         );
         assert_eq!(size.width, 6);
     }
+
+    #[test]
+    fn truncate_fits() {
+        let text = truncate_text("hello", 5, TextOverflow::Ellipsis);
+        assert!(matches!(text, Cow::Borrowed("hello")));
+    }
+
+    #[test]
+    fn truncate_ellipsis() {
+        assert_eq!(
+            truncate_text("hello world", 5, TextOverflow::Ellipsis),
+            "hell…"
+        );
+        assert_eq!(truncate_text("hello world", 5, TextOverflow::Clip), "hello");
+    }
+
+    #[test]
+    fn truncate_wide_chars() {
+        // The ellipsis never splits a 2 cell character
+        assert_eq!(truncate_text("日本語", 4, TextOverflow::Ellipsis), "日…");
+        assert_eq!(truncate_text("日本語", 3, TextOverflow::Clip), "日");
+    }
 }
@@ -121,7 +121,12 @@ mod tests {
     }
 
     fn make_rule(selector: &str, source_order: u32) -> BulmaRule {
-        BulmaRule::new(parse_selector(selector), Arc::new(vec![]), source_order)
+        BulmaRule::new(
+            parse_selector(selector),
+            Arc::new(vec![]),
+            source_order,
+            Arc::default(),
+        )
     }
 
     #[test]
@@ -1,13 +1,19 @@
 use crate::{
-    AlignContent, AlignItems, AlignSelf, BorderStyle, Color, Dimension, Display, Edges,
-    FlexDirection, FlexWrap, FontStyle, FontWeight, JustifyContent, Length, Overflow, OverflowWrap,
-    TextAlign, TextDecoration, VerticalAlign, Visibility, WhiteSpace,
+    AlignContent, AlignItems, AlignSelf, AspectRatio, BorderStyle, BoxShadow, Color, Cursor,
+    Dimension, Display, Edges, FlexDirection, FlexWrap, FontStyle, FontWeight, GridPlacement,
+    JustifyContent, Length, Margin, Overflow, OverflowWrap, Position, TextAlign, TextDecoration,
+    TextOverflow, TrackSize, Transition, VerticalAlign, Visibility, WhiteSpace, ZIndex,
 };
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ComputedStyle {
     pub display: Display,
 
+    pub position: Position,
+    /// Offsets from the containing block for positioned boxes, `top`,
+    /// `right`, `bottom` and `left`
+    pub inset: Edges<Dimension>,
+
     pub flex_direction: FlexDirection,
     pub flex_wrap: FlexWrap,
     pub justify_content: JustifyContent,
@@ -19,6 +25,12 @@ pub struct ComputedStyle {
     pub flex_basis: Dimension,
     pub align_self: AlignSelf,
 
+    pub grid_template_columns: Vec<TrackSize>,
+    pub grid_template_rows: Vec<TrackSize>,
+
+    pub grid_column: GridPlacement,
+    pub grid_row: GridPlacement,
+
     pub row_gap: Length,
     pub column_gap: Length,
 
@@ -28,15 +40,30 @@ pub struct ComputedStyle {
     pub max_width: Dimension,
     pub min_height: Dimension,
     pub max_height: Dimension,
+    pub aspect_ratio: AspectRatio,
 
-    pub margin: Edges<Length>,
+    pub margin: Edges<Margin>,
     pub padding: Edges<Length>,
 
     pub border_style: Edges<BorderStyle>,
     pub border_color: Edges<Color>,
 
+    /// Drawn over the border box, never takes up layout space
+    pub outline_style: BorderStyle,
+    pub outline_color: Color,
+
+    /// Painted behind the border box, never takes up layout space
+    pub box_shadow: Option<BoxShadow>,
+    /// Between 0.0 and 1.0. Not inherited, but painting multiplies it down
+    /// the tree so a faded parent fades its children too.
+    pub opacity: f32,
+
+    pub transitions: Vec<Transition>,
+
     pub color: Color,
     pub background_color: Color,
+    /// Glyph repeated across the background, `None` uses the renderer's fill character
+    pub background_char: Option<char>,
 
     pub font_weight: FontWeight,
     pub font_style: FontStyle,
@@ -45,12 +72,15 @@ pub struct ComputedStyle {
     pub vertical_align: VerticalAlign,
     pub white_space: WhiteSpace,
     pub overflow_wrap: OverflowWrap,
+    pub text_overflow: TextOverflow,
 
     pub overflow_x: Overflow,
     pub overflow_y: Overflow,
 
     pub visibility: Visibility,
-    pub z_index: i16,
+    pub z_index: ZIndex,
+
+    pub cursor: Cursor,
 }
 
 impl Default for ComputedStyle {
@@ -58,6 +88,9 @@ impl Default for ComputedStyle {
         Self {
             display: Display::default(),
 
+            position: Position::default(),
+            inset: Edges::all(Dimension::Auto),
+
             flex_direction: FlexDirection::default(),
             flex_wrap: FlexWrap::default(),
             justify_content: JustifyContent::default(),
@@ -69,6 +102,12 @@ impl Default for ComputedStyle {
             flex_basis: Dimension::Auto,
             align_self: AlignSelf::default(),
 
+            grid_template_columns: Vec::new(),
+            grid_template_rows: Vec::new(),
+
+            grid_column: GridPlacement::default(),
+            grid_row: GridPlacement::default(),
+
             row_gap: Length::ZERO,
             column_gap: Length::ZERO,
 
@@ -78,6 +117,7 @@ impl Default for ComputedStyle {
             max_width: Dimension::None,
             min_height: Dimension::Auto,
             max_height: Dimension::None,
+            aspect_ratio: AspectRatio::Auto,
 
             margin: Edges::default(),
             padding: Edges::default(),
@@ -85,8 +125,17 @@ impl Default for ComputedStyle {
             border_style: Edges::default(),
             border_color: Edges::all(Color::Reset),
 
+            outline_style: BorderStyle::None,
+            outline_color: Color::Reset,
+
+            box_shadow: None,
+            opacity: 1.0,
+
+            transitions: Vec::new(),
+
             color: Color::Reset,
             background_color: Color::Reset,
+            background_char: None,
 
             font_weight: FontWeight::default(),
             font_style: FontStyle::default(),
@@ -95,12 +144,15 @@ impl Default for ComputedStyle {
             vertical_align: VerticalAlign::default(),
             white_space: WhiteSpace::default(),
             overflow_wrap: OverflowWrap::default(),
+            text_overflow: TextOverflow::default(),
 
             overflow_x: Overflow::default(),
             overflow_y: Overflow::default(),
 
             visibility: Visibility::default(),
-            z_index: 0,
+            z_index: ZIndex::Auto,
+
+            cursor: Cursor::default(),
         }
     }
 }
@@ -122,10 +174,18 @@ impl ComputedStyle {
             white_space: parent.white_space,
             overflow_wrap: parent.overflow_wrap,
             visibility: parent.visibility,
+            cursor: parent.cursor,
             ..Self::default()
         }
     }
 
+    /// Whether the box takes up space among its siblings, rather than being
+    /// hidden or positioned out of flow.
+    #[must_use]
+    pub const fn in_flow(&self) -> bool {
+        !matches!(self.display, Display::None) && !matches!(self.position, Position::Absolute)
+    }
+
     #[must_use]
     pub const fn is_flex_container(&self) -> bool {
         matches!(self.display, Display::Flex)
@@ -1,23 +1,26 @@
 use std::sync::Arc;
 
-use cssparser::{Parser, ParserInput};
+use cssparser::{Parser, ParserInput, ToCss};
 use ginyu_force::Pose;
+use rustc_hash::FxHashMap;
 use selectors::{
     SelectorList,
     context::{MatchingContext, SelectorCaches},
     matching::matches_selector,
+    parser::Selector,
 };
 use smallvec::SmallVec;
 
 use crate::{
-    AlignContent, AlignItems, AlignSelf, BorderStyle, CapsuleElement, Color, ComputedStyle,
-    ConcreteCapsuleElement, CustomPropertiesMap, CustomPropertiesResolver, Dimension, Display,
-    ElementState, FlexDirection, FlexWrap, FontStyle, FontWeight, JustifyContent, Length, Overflow,
-    OverflowWrap, Property, Selectors, Stylesheet, TextAlign, TextDecoration, Value, VerticalAlign,
-    Visibility, WhiteSpace,
+    AlignContent, AlignItems, AlignSelf, AspectRatio, BorderStyle, CapsuleElement, Color,
+    ComputedStyle, ConcreteCapsuleElement, Cursor, CustomPropertiesMap, CustomPropertiesResolver,
+    Dimension, Display, ElementState, FlexDirection, FlexWrap, FontStyle, FontWeight,
+    GridPlacement, JustifyContent, Length, Margin, MediaQueryList, Overflow, OverflowWrap,
+    Position, Property, PropertyRegistry, Selectors, Size, Specificity, Stylesheet, TextAlign,
+    TextDecoration, TextOverflow, Value, VerticalAlign, Visibility, WhiteSpace, ZIndex,
     bulma::{
         cascade::CascadeData, invalidation::InvalidationMap, make_context, restyle::RestyleHint,
-        rule::BulmaRule,
+        rule::BulmaRule, sharing::StyleSharingCache,
     },
     parser::{Declaration, Rule, parse_inline_style, parse_property_value},
 };
@@ -26,6 +29,11 @@ use crate::{
 pub struct Bulma {
     cascade_data: CascadeData,
     invalidation_map: InvalidationMap,
+    media_queries: Vec<MediaQueryList>,
+    viewport: Size,
+    sharing_cache: StyleSharingCache,
+    /// Custom properties registered with `@property`
+    registry: PropertyRegistry,
 
     num_rebuilds: usize,
     source_order: u32,
@@ -39,6 +47,10 @@ impl Bulma {
         Self {
             cascade_data: CascadeData::default(),
             invalidation_map: InvalidationMap::default(),
+            media_queries: Vec::new(),
+            viewport: Size::ZERO,
+            sharing_cache: StyleSharingCache::default(),
+            registry: PropertyRegistry::default(),
             num_rebuilds: 0,
             source_order: Self::AUTHOR_SOURCE_ORDER_START,
         }
@@ -48,10 +60,12 @@ impl Bulma {
         let mut source_order = self.source_order & !Self::AUTHOR_SOURCE_ORDER_START;
 
         for rule in &stylesheet.rules {
-            self.add_rule(rule, None, &mut source_order);
+            self.add_top_level_rule(rule, &mut source_order);
         }
 
+        self.register_properties(stylesheet);
         self.source_order = source_order | (self.source_order & Self::AUTHOR_SOURCE_ORDER_START);
+        self.sharing_cache.clear();
 
         self.cascade_data.shrink_to_fit();
         self.invalidation_map.shrink_to_fit();
@@ -61,19 +75,40 @@ impl Bulma {
         let mut source_order = self.source_order;
 
         for rule in &stylesheet.rules {
-            self.add_rule(rule, None, &mut source_order);
+            self.add_top_level_rule(rule, &mut source_order);
         }
 
+        self.register_properties(stylesheet);
         self.source_order = source_order;
+        self.sharing_cache.clear();
         self.cascade_data.shrink_to_fit();
         self.invalidation_map.shrink_to_fit();
         self.num_rebuilds += 1;
     }
 
+    fn register_properties(&mut self, stylesheet: &Stylesheet) {
+        for registration in &stylesheet.properties {
+            self.registry
+                .insert(registration.name, registration.clone());
+        }
+    }
+
+    fn add_top_level_rule(&mut self, rule: &Rule, source_order: &mut u32) {
+        for list in &rule.media {
+            if !self.media_queries.contains(list) {
+                self.media_queries.push(list.clone());
+            }
+        }
+
+        let media = Arc::from(rule.media.as_slice());
+        self.add_rule(rule, None, &media, source_order);
+    }
+
     fn add_rule(
         &mut self,
         rule: &Rule,
         parent_selectors: Option<&SelectorList<Selectors>>,
+        media: &Arc<[MediaQueryList]>,
         source_order: &mut u32,
     ) {
         let declations = Arc::new(rule.declarations.clone());
@@ -86,23 +121,47 @@ impl Bulma {
 
             self.invalidation_map.register_selector(&final_selector);
 
-            let bulma_rule = BulmaRule::new(final_selector, declations.clone(), self.source_order);
+            let bulma_rule = BulmaRule::new(
+                final_selector,
+                declations.clone(),
+                *source_order,
+                media.clone(),
+            );
 
             self.cascade_data.insert(bulma_rule);
             *source_order += 1;
         }
 
         for nested in &rule.nested_rules {
-            self.add_rule(nested, Some(&rule.selectors), source_order);
+            self.add_rule(nested, Some(&rule.selectors), media, source_order);
         }
     }
 
     pub fn clear(&mut self) {
         self.cascade_data.clear();
         self.invalidation_map.clear();
+        self.media_queries.clear();
+        self.sharing_cache.clear();
+        self.registry.clear();
         self.source_order = Self::AUTHOR_SOURCE_ORDER_START;
     }
 
+    /// Size of the terminal that `@media` rules are matched against.
+    #[must_use]
+    pub const fn viewport(&self) -> Size {
+        self.viewport
+    }
+
+    /// Update the viewport, returning whether any `@media` rule starts or
+    /// stops applying, in which case the document needs a full restyle.
+    pub fn set_viewport(&mut self, viewport: Size) -> bool {
+        let old = std::mem::replace(&mut self.viewport, viewport);
+
+        self.media_queries
+            .iter()
+            .any(|list| list.matches(old) != list.matches(viewport))
+    }
+
     #[inline]
     #[must_use]
     pub fn restyle_hint_for_state_change(
@@ -153,27 +212,29 @@ impl Bulma {
         let mut matched = SmallVec::new();
         let wrapped = ConcreteCapsuleElement::new(element.clone());
         let cascade_data = &self.cascade_data;
+        let viewport = self.viewport;
         let mut context = make_context(caches);
 
         if let Some(id) = element.id()
             && let Some(rules) = cascade_data.rules_by_id(id)
         {
-            collect_if_matching(&wrapped, rules, &mut context, &mut matched);
+            collect_if_matching(&wrapped, rules, viewport, &mut context, &mut matched);
         }
 
         element.each_class(|class| {
             if let Some(rules) = cascade_data.rules_by_class(class) {
-                collect_if_matching(&wrapped, rules, &mut context, &mut matched);
+                collect_if_matching(&wrapped, rules, viewport, &mut context, &mut matched);
             }
         });
 
         if let Some(rules) = cascade_data.rules_by_tag(element.tag_name()) {
-            collect_if_matching(&wrapped, rules, &mut context, &mut matched);
+            collect_if_matching(&wrapped, rules, viewport, &mut context, &mut matched);
         }
 
         collect_if_matching(
             &wrapped,
             cascade_data.universal_rules(),
+            viewport,
             &mut context,
             &mut matched,
         );
@@ -183,37 +244,156 @@ impl Bulma {
         matched
     }
 
+    /// Compute the style for `element`, sharing the result with earlier
+    /// elements that matched the same rules under an identical parent.
     pub fn compute_style<E: CapsuleElement>(
         &mut self,
         element: &E,
         parent_style: Option<&ComputedStyle>,
         parent_custom_properties: Option<&CustomPropertiesMap>,
         caches: &mut SelectorCaches,
-    ) -> (ComputedStyle, CustomPropertiesMap) {
+    ) -> (Arc<ComputedStyle>, CustomPropertiesMap) {
         let matched = self.collect_matching_rules(element, caches);
+        let inline_style = element.style_attribute();
+
+        if let Some(shared) = self.sharing_cache.get(
+            &matched,
+            inline_style,
+            parent_style,
+            parent_custom_properties,
+        ) {
+            return shared;
+        }
 
-        let mut style =
-            parent_style.map_or_else(ComputedStyle::default, ComputedStyle::inherit_from);
+        let (style, custom_properties) = cascade(
+            &matched,
+            inline_style,
+            parent_style,
+            parent_custom_properties,
+            &self.registry,
+        );
+        let style = Arc::new(style);
+
+        self.sharing_cache.insert(
+            &matched,
+            inline_style,
+            parent_style,
+            parent_custom_properties,
+            style.clone(),
+            custom_properties.clone(),
+        );
 
-        let mut resolver = CustomPropertiesResolver::new(parent_custom_properties);
+        (style, custom_properties)
+    }
 
+    /// Every rule that matched `element`, weakest first, with the
+    /// declarations that won marked. Meant for working out why a style
+    /// isn't applying.
+    pub fn explain<E: CapsuleElement>(
+        &mut self,
+        element: &E,
+        caches: &mut SelectorCaches,
+    ) -> Vec<RuleExplanation> {
+        let matched = self.collect_matching_rules(element, caches);
         let inline_declarations = element
             .style_attribute()
             .map(parse_inline_style)
             .unwrap_or_default();
 
-        for applicable in &matched {
-            for declaration in applicable.declarations.iter() {
-                if let (Property::Custom(name), Value::Custom(value)) =
-                    (&declaration.property, &declaration.value)
-                    && !declaration.important
-                {
-                    resolver.add(*name, value.clone());
+        let mut explanations: Vec<_> = matched
+            .iter()
+            .map(|applicable| RuleExplanation {
+                selector: Some(applicable.selector.to_css_string()),
+                specificity: Specificity::from_packed(applicable.specificity),
+                source_order: applicable.source_order,
+                declarations: explain_declarations(&applicable.declarations),
+            })
+            .collect();
+
+        if !inline_declarations.is_empty() {
+            explanations.push(RuleExplanation {
+                selector: None,
+                specificity: Specificity::default(),
+                source_order: 0,
+                declarations: explain_declarations(&inline_declarations),
+            });
+        }
+
+        // Same order as the cascade: normal declarations from the rules then
+        // the style attribute, then the important ones the same way. The last
+        // one seen for a property is the one that sticks.
+        let mut winners = FxHashMap::default();
+
+        for important in [false, true] {
+            for (rule, explanation) in explanations.iter().enumerate() {
+                for (index, declaration) in explanation.declarations.iter().enumerate() {
+                    if declaration.declaration.important == important {
+                        winners.insert(&declaration.declaration.property, (rule, index));
+                    }
                 }
             }
         }
 
-        for declaration in &inline_declarations {
+        let winners: Vec<_> = winners.into_values().collect();
+        for (rule, index) in winners {
+            explanations[rule].declarations[index].winning = true;
+        }
+
+        explanations
+    }
+
+    #[must_use]
+    pub const fn num_selectors(&self) -> usize {
+        self.cascade_data.num_selectors
+    }
+
+    #[must_use]
+    pub const fn num_declarations(&self) -> usize {
+        self.cascade_data.num_declarations
+    }
+
+    #[must_use]
+    pub const fn num_rebuilds(&self) -> usize {
+        self.num_rebuilds
+    }
+
+    /// Styles handed out from the sharing cache instead of being computed.
+    #[must_use]
+    pub const fn num_shared_styles(&self) -> usize {
+        self.sharing_cache.hits()
+    }
+
+    /// Styles that had to be computed because nothing could be shared.
+    #[must_use]
+    pub const fn num_computed_styles(&self) -> usize {
+        self.sharing_cache.misses()
+    }
+}
+
+impl Default for Bulma {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run the cascade over the declarations an element matched, in order, with
+/// its inline style on top.
+fn cascade(
+    matched: &[ApplicableDeclaration],
+    inline_style: Option<&str>,
+    parent_style: Option<&ComputedStyle>,
+    parent_custom_properties: Option<&CustomPropertiesMap>,
+    registry: &PropertyRegistry,
+) -> (ComputedStyle, CustomPropertiesMap) {
+    let mut style = parent_style.map_or_else(ComputedStyle::default, ComputedStyle::inherit_from);
+
+    let mut resolver =
+        CustomPropertiesResolver::new(parent_custom_properties).with_registry(registry);
+
+    let inline_declarations = inline_style.map(parse_inline_style).unwrap_or_default();
+
+    for applicable in matched {
+        for declaration in applicable.declarations.iter() {
             if let (Property::Custom(name), Value::Custom(value)) =
                 (&declaration.property, &declaration.value)
                 && !declaration.important
@@ -221,19 +401,19 @@ impl Bulma {
                 resolver.add(*name, value.clone());
             }
         }
+    }
 
-        for applicable in &matched {
-            for declaration in applicable.declarations.iter() {
-                if let (Property::Custom(name), Value::Custom(value)) =
-                    (&declaration.property, &declaration.value)
-                    && declaration.important
-                {
-                    resolver.add(*name, value.clone());
-                }
-            }
+    for declaration in &inline_declarations {
+        if let (Property::Custom(name), Value::Custom(value)) =
+            (&declaration.property, &declaration.value)
+            && !declaration.important
+        {
+            resolver.add(*name, value.clone());
         }
+    }
 
-        for declaration in &inline_declarations {
+    for applicable in matched {
+        for declaration in applicable.declarations.iter() {
             if let (Property::Custom(name), Value::Custom(value)) =
                 (&declaration.property, &declaration.value)
                 && declaration.important
@@ -241,71 +421,72 @@ impl Bulma {
                 resolver.add(*name, value.clone());
             }
         }
+    }
 
-        let custom_properties = resolver.build();
-
-        for applicable in &matched {
-            for declaration in applicable.declarations.iter() {
-                if !declaration.property.is_custom() && !declaration.important {
-                    apply_declaration(&mut style, declaration, parent_style, &custom_properties);
-                }
-            }
+    for declaration in &inline_declarations {
+        if let (Property::Custom(name), Value::Custom(value)) =
+            (&declaration.property, &declaration.value)
+            && declaration.important
+        {
+            resolver.add(*name, value.clone());
         }
+    }
 
-        for declaration in &inline_declarations {
+    let custom_properties = resolver.build();
+
+    for applicable in matched {
+        for declaration in applicable.declarations.iter() {
             if !declaration.property.is_custom() && !declaration.important {
                 apply_declaration(&mut style, declaration, parent_style, &custom_properties);
             }
         }
+    }
 
-        for applicable in &matched {
-            for declaration in applicable.declarations.iter() {
-                if !declaration.property.is_custom() && declaration.important {
-                    apply_declaration(&mut style, declaration, parent_style, &custom_properties);
-                }
-            }
+    for declaration in &inline_declarations {
+        if !declaration.property.is_custom() && !declaration.important {
+            apply_declaration(&mut style, declaration, parent_style, &custom_properties);
         }
+    }
 
-        for declaration in &inline_declarations {
+    for applicable in matched {
+        for declaration in applicable.declarations.iter() {
             if !declaration.property.is_custom() && declaration.important {
                 apply_declaration(&mut style, declaration, parent_style, &custom_properties);
             }
         }
-
-        (style, custom_properties)
     }
 
-    #[must_use]
-    pub const fn num_selectors(&self) -> usize {
-        self.cascade_data.num_selectors
-    }
-
-    #[must_use]
-    pub const fn num_declarations(&self) -> usize {
-        self.cascade_data.num_declarations
+    for declaration in &inline_declarations {
+        if !declaration.property.is_custom() && declaration.important {
+            apply_declaration(&mut style, declaration, parent_style, &custom_properties);
+        }
     }
 
-    #[must_use]
-    pub const fn num_rebuilds(&self) -> usize {
-        self.num_rebuilds
-    }
+    (style, custom_properties)
 }
 
-impl Default for Bulma {
-    fn default() -> Self {
-        Self::new()
-    }
+fn explain_declarations(declarations: &[Declaration]) -> Vec<DeclarationExplanation> {
+    declarations
+        .iter()
+        .map(|declaration| DeclarationExplanation {
+            declaration: declaration.clone(),
+            winning: false,
+        })
+        .collect()
 }
 
 fn collect_if_matching<E: CapsuleElement>(
     element: &ConcreteCapsuleElement<E>,
     rules: &[BulmaRule],
+    viewport: Size,
     context: &mut MatchingContext<'_, Selectors>,
     matched: &mut SmallVec<[ApplicableDeclaration; 8]>,
 ) {
     for rule in rules {
-        if matches_selector(&rule.selector, 0, None, element, context) {
+        if rule.applies_to(viewport) && matches_selector(&rule.selector, 0, None, element, context)
+        {
             matched.push(ApplicableDeclaration {
+                selector: rule.selector.clone(),
                 declarations: rule.declarations.clone(),
                 specificity: rule.specificity(),
                 source_order: rule.source_order,
@@ -365,6 +546,11 @@ fn apply_declaration(
 fn apply_inherited(style: &mut ComputedStyle, property: Property, parent: &ComputedStyle) {
     match property {
         Property::Display => style.display = parent.display,
+        Property::Position => style.position = parent.position,
+        Property::Top => style.inset.top = parent.inset.top.clone(),
+        Property::Right => style.inset.right = parent.inset.right.clone(),
+        Property::Bottom => style.inset.bottom = parent.inset.bottom.clone(),
+        Property::Left => style.inset.left = parent.inset.left.clone(),
         Property::FlexDirection => style.flex_direction = parent.flex_direction,
         Property::FlexWrap => style.flex_wrap = parent.flex_wrap,
         Property::JustifyContent => style.justify_content = parent.justify_content,
@@ -382,6 +568,7 @@ fn apply_inherited(style: &mut ComputedStyle, property: Property, parent: &Compu
         Property::MaxWidth => style.max_width = parent.max_width.clone(),
         Property::MinHeight => style.min_height = parent.min_height.clone(),
         Property::MaxHeight => style.max_height = parent.max_height.clone(),
+        Property::AspectRatio => style.aspect_ratio = parent.aspect_ratio,
         Property::MarginTop => style.margin.top = parent.margin.top.clone(),
         Property::MarginRight => style.margin.right = parent.margin.right.clone(),
         Property::MarginBottom => style.margin.bottom = parent.margin.bottom.clone(),
@@ -398,8 +585,14 @@ fn apply_inherited(style: &mut ComputedStyle, property: Property, parent: &Compu
         Property::BorderRightColor => style.border_color.right = parent.border_color.right,
         Property::BorderBottomColor => style.border_color.bottom = parent.border_color.bottom,
         Property::BorderLeftColor => style.border_color.left = parent.border_color.left,
+        Property::OutlineStyle => style.outline_style = parent.outline_style,
+        Property::OutlineColor => style.outline_color = parent.outline_color,
+        Property::BoxShadow => style.box_shadow = parent.box_shadow,
+        Property::Opacity => style.opacity = parent.opacity,
+        Property::Transition => style.transitions.clone_from(&parent.transitions),
         Property::Color => style.color = parent.color,
         Property::BackgroundColor => style.background_color = parent.background_color,
+        Property::BackgroundChar => style.background_char = parent.background_char,
         Property::FontWeight => style.font_weight = parent.font_weight,
         Property::FontStyle => style.font_style = parent.font_style,
         Property::TextDecoration => style.text_decoration = parent.text_decoration,
@@ -407,21 +600,36 @@ fn apply_inherited(style: &mut ComputedStyle, property: Property, parent: &Compu
         Property::VerticalAlign => style.vertical_align = parent.vertical_align,
         Property::WhiteSpace => style.white_space = parent.white_space,
         Property::OverflowWrap => style.overflow_wrap = parent.overflow_wrap,
+        Property::TextOverflow => style.text_overflow = parent.text_overflow,
         Property::OverflowX => style.overflow_x = parent.overflow_x,
         Property::OverflowY => style.overflow_y = parent.overflow_y,
         Property::Visibility => style.visibility = parent.visibility,
         Property::ZIndex => style.z_index = parent.z_index,
-        Property::GridTemplateColumns
-        | Property::GridTemplateRows
-        | Property::GridColumn
-        | Property::GridRow
-        | Property::Custom(_) => {}
+        Property::Cursor => style.cursor = parent.cursor,
+        Property::GridTemplateColumns => {
+            style
+                .grid_template_columns
+                .clone_from(&parent.grid_template_columns);
+        }
+        Property::GridTemplateRows => {
+            style
+                .grid_template_rows
+                .clone_from(&parent.grid_template_rows);
+        }
+        Property::GridColumn => style.grid_column = parent.grid_column,
+        Property::GridRow => style.grid_row = parent.grid_row,
+        Property::Custom(_) => {}
     }
 }
 
 fn apply_initial(style: &mut ComputedStyle, property: Property) {
     match property {
         Property::Display => style.display = Display::default(),
+        Property::Position => style.position = Position::default(),
+        Property::Top => style.inset.top = Dimension::Auto,
+        Property::Right => style.inset.right = Dimension::Auto,
+        Property::Bottom => style.inset.bottom = Dimension::Auto,
+        Property::Left => style.inset.left = Dimension::Auto,
         Property::FlexDirection => style.flex_direction = FlexDirection::default(),
         Property::FlexWrap => style.flex_wrap = FlexWrap::default(),
         Property::JustifyContent => style.justify_content = JustifyContent::default(),
@@ -439,10 +647,11 @@ fn apply_initial(style: &mut ComputedStyle, property: Property) {
         Property::MaxWidth => style.max_width = Dimension::None,
         Property::MinHeight => style.min_height = Dimension::Auto,
         Property::MaxHeight => style.max_height = Dimension::None,
-        Property::MarginTop => style.margin.top = Length::ZERO,
-        Property::MarginRight => style.margin.right = Length::ZERO,
-        Property::MarginBottom => style.margin.bottom = Length::ZERO,
-        Property::MarginLeft => style.margin.left = Length::ZERO,
+        Property::AspectRatio => style.aspect_ratio = AspectRatio::Auto,
+        Property::MarginTop => style.margin.top = Margin::ZERO,
+        Property::MarginRight => style.margin.right = Margin::ZERO,
+        Property::MarginBottom => style.margin.bottom = Margin::ZERO,
+        Property::MarginLeft => style.margin.left = Margin::ZERO,
         Property::PaddingTop => style.padding.top = Length::ZERO,
         Property::PaddingRight => style.padding.right = Length::ZERO,
         Property::PaddingBottom => style.padding.bottom = Length::ZERO,
@@ -455,8 +664,14 @@ fn apply_initial(style: &mut ComputedStyle, property: Property) {
         Property::BorderRightColor => style.border_color.right = Color::Reset,
         Property::BorderBottomColor => style.border_color.bottom = Color::Reset,
         Property::BorderLeftColor => style.border_color.left = Color::Reset,
+        Property::OutlineStyle => style.outline_style = BorderStyle::default(),
+        Property::OutlineColor => style.outline_color = Color::Reset,
+        Property::BoxShadow => style.box_shadow = None,
+        Property::Opacity => style.opacity = 1.0,
+        Property::Transition => style.transitions.clear(),
         Property::Color => style.color = Color::Reset,
         Property::BackgroundColor => style.background_color = Color::Reset,
+        Property::BackgroundChar => style.background_char = None,
         Property::FontWeight => style.font_weight = FontWeight::default(),
         Property::FontStyle => style.font_style = FontStyle::default(),
         Property::TextDecoration => style.text_decoration = TextDecoration::default(),
@@ -464,16 +679,17 @@ fn apply_initial(style: &mut ComputedStyle, property: Property) {
         Property::VerticalAlign => style.vertical_align = VerticalAlign::default(),
         Property::WhiteSpace => style.white_space = WhiteSpace::default(),
         Property::OverflowWrap => style.overflow_wrap = OverflowWrap::default(),
+        Property::TextOverflow => style.text_overflow = TextOverflow::default(),
         Property::OverflowX => style.overflow_x = Overflow::default(),
         Property::OverflowY => style.overflow_y = Overflow::default(),
         Property::Visibility => style.visibility = Visibility::default(),
-        Property::ZIndex => style.z_index = 0,
+        Property::ZIndex => style.z_index = ZIndex::Auto,
+        Property::Cursor => style.cursor = Cursor::default(),
 
-        // TODO: Grid
-        Property::GridTemplateColumns
-        | Property::GridTemplateRows
-        | Property::GridColumn
-        | Property::GridRow => {}
+        Property::GridTemplateColumns => style.grid_template_columns = Vec::new(),
+        Property::GridTemplateRows => style.grid_template_rows = Vec::new(),
+        Property::GridColumn => style.grid_column = GridPlacement::default(),
+        Property::GridRow => style.grid_row = GridPlacement::default(),
         Property::Custom(_) => unreachable!(),
     }
 }
@@ -481,6 +697,11 @@ fn apply_initial(style: &mut ComputedStyle, property: Property) {
 fn apply_value(style: &mut ComputedStyle, property: Property, value: &Value) {
     match (property, value) {
         (Property::Display, Value::Display(v)) => style.display = *v,
+        (Property::Position, Value::Position(v)) => style.position = *v,
+        (Property::Top, Value::Dimension(v)) => style.inset.top = v.clone(),
+        (Property::Right, Value::Dimension(v)) => style.inset.right = v.clone(),
+        (Property::Bottom, Value::Dimension(v)) => style.inset.bottom = v.clone(),
+        (Property::Left, Value::Dimension(v)) => style.inset.left = v.clone(),
         (Property::FlexDirection, Value::FlexDirection(v)) => style.flex_direction = *v,
         (Property::FlexWrap, Value::FlexWrap(v)) => style.flex_wrap = *v,
         (Property::JustifyContent, Value::JustifyContent(v)) => style.justify_content = *v,
@@ -498,10 +719,11 @@ fn apply_value(style: &mut ComputedStyle, property: Property, value: &Value) {
         (Property::MaxWidth, Value::Dimension(v)) => style.max_width = v.clone(),
         (Property::MinHeight, Value::Dimension(v)) => style.min_height = v.clone(),
         (Property::MaxHeight, Value::Dimension(v)) => style.max_height = v.clone(),
-        (Property::MarginTop, Value::Length(v)) => style.margin.top = v.clone(),
-        (Property::MarginRight, Value::Length(v)) => style.margin.right = v.clone(),
-        (Property::MarginBottom, Value::Length(v)) => style.margin.bottom = v.clone(),
-        (Property::MarginLeft, Value::Length(v)) => style.margin.left = v.clone(),
+        (Property::AspectRatio, Value::AspectRatio(v)) => style.aspect_ratio = *v,
+        (Property::MarginTop, Value::Margin(v)) => style.margin.top = v.clone(),
+        (Property::MarginRight, Value::Margin(v)) => style.margin.right = v.clone(),
+        (Property::MarginBottom, Value::Margin(v)) => style.margin.bottom = v.clone(),
+        (Property::MarginLeft, Value::Margin(v)) => style.margin.left = v.clone(),
         (Property::PaddingTop, Value::Length(v)) => style.padding.top = v.clone(),
         (Property::PaddingRight, Value::Length(v)) => style.padding.right = v.clone(),
         (Property::PaddingBottom, Value::Length(v)) => style.padding.bottom = v.clone(),
@@ -514,8 +736,14 @@ fn apply_value(style: &mut ComputedStyle, property: Property, value: &Value) {
         (Property::BorderRightColor, Value::Color(v)) => style.border_color.right = *v,
         (Property::BorderBottomColor, Value::Color(v)) => style.border_color.bottom = *v,
         (Property::BorderLeftColor, Value::Color(v)) => style.border_color.left = *v,
+        (Property::OutlineStyle, Value::BorderStyle(v)) => style.outline_style = *v,
+        (Property::OutlineColor, Value::Color(v)) => style.outline_color = *v,
+        (Property::BoxShadow, Value::BoxShadow(v)) => style.box_shadow = *v,
+        (Property::Opacity, Value::Number(v)) => style.opacity = *v,
+        (Property::Transition, Value::Transitions(v)) => style.transitions.clone_from(v),
         (Property::Color, Value::Color(v)) => style.color = *v,
         (Property::BackgroundColor, Value::Color(v)) => style.background_color = *v,
+        (Property::BackgroundChar, Value::Char(v)) => style.background_char = *v,
         (Property::FontWeight, Value::FontWeight(v)) => style.font_weight = *v,
         (Property::FontStyle, Value::FontStyle(v)) => style.font_style = *v,
         (Property::TextDecoration, Value::TextDecoration(v)) => style.text_decoration = *v,
@@ -523,17 +751,20 @@ fn apply_value(style: &mut ComputedStyle, property: Property, value: &Value) {
         (Property::VerticalAlign, Value::VerticalAlign(v)) => style.vertical_align = *v,
         (Property::WhiteSpace, Value::WhiteSpace(v)) => style.white_space = *v,
         (Property::OverflowWrap, Value::OverflowWrap(v)) => style.overflow_wrap = *v,
+        (Property::TextOverflow, Value::TextOverflow(v)) => style.text_overflow = *v,
         (Property::OverflowX, Value::Overflow(v)) => style.overflow_x = *v,
         (Property::OverflowY, Value::Overflow(v)) => style.overflow_y = *v,
         (Property::Visibility, Value::Visibility(v)) => style.visibility = *v,
-        (Property::ZIndex, Value::Integer(v)) => style.z_index = *v,
-        (
-            Property::GridTemplateColumns
-            | Property::GridTemplateRows
-            | Property::GridColumn
-            | Property::GridRow,
-            _,
-        ) => {}
+        (Property::ZIndex, Value::ZIndex(v)) => style.z_index = *v,
+        (Property::Cursor, Value::Cursor(v)) => style.cursor = *v,
+        (Property::GridTemplateColumns, Value::GridTemplate(v)) => {
+            style.grid_template_columns.clone_from(v);
+        }
+        (Property::GridTemplateRows, Value::GridTemplate(v)) => {
+            style.grid_template_rows.clone_from(v);
+        }
+        (Property::GridColumn, Value::GridPlacement(v)) => style.grid_column = *v,
+        (Property::GridRow, Value::GridPlacement(v)) => style.grid_row = *v,
 
         (Property::Custom(_), _) => unreachable!(),
 
@@ -555,6 +786,7 @@ fn parse_substituted_value(property: Property, css: &str) -> Option<Value> {
 
 #[derive(Debug, Clone)]
 pub struct ApplicableDeclaration {
+    pub selector: Selector<Selectors>,
     pub declarations: Arc<Vec<Declaration>>,
     pub specificity: u32,
     pub source_order: u32,
@@ -568,11 +800,30 @@ impl ApplicableDeclaration {
     }
 }
 
+/// One rule that matched an element, see [`Bulma::explain`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleExplanation {
+    /// The selector as written, or `None` for the element's `style` attribute
+    pub selector: Option<String>,
+    /// Zero for the `style` attribute, which beats every rule regardless
+    pub specificity: Specificity,
+    pub source_order: u32,
+    pub declarations: Vec<DeclarationExplanation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclarationExplanation {
+    pub declaration: Declaration,
+    /// Whether this is the declaration the element's style ended up with
+    pub winning: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{CapsuleNode, Layout, Stylesheet};
     use selectors::context::SelectorCaches;
+    use std::ptr::NonNull;
 
     #[derive(Debug, Clone, PartialEq)]
     struct TestElement {
@@ -624,7 +875,12 @@ mod tests {
             None
         }
 
-        fn set_style(&mut self, _style: ComputedStyle, _custom_properties: CustomPropertiesMap) {}
+        fn set_style(
+            &mut self,
+            _style: Arc<ComputedStyle>,
+            _custom_properties: CustomPropertiesMap,
+        ) {
+        }
 
         fn clear_needs_layout(&mut self) {}
 
@@ -691,6 +947,14 @@ mod tests {
         fn has_children(&self) -> bool {
             false
         }
+
+        fn first_child(&self) -> Option<Self> {
+            None
+        }
+
+        fn identity(&self) -> NonNull<()> {
+            NonNull::from(self).cast()
+        }
     }
 
     #[test]
@@ -770,6 +1034,99 @@ mod tests {
         assert_eq!(matched.len(), 1);
     }
 
+    #[test]
+    fn media_rules_follow_viewport() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(
+            ".btn { color: red }
+             @media (min-width: 80) { .btn { color: blue } }",
+        )
+        .expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let element = TestElement::new("div").with_class("btn");
+        let mut caches = SelectorCaches::default();
+
+        assert!(!bulma.set_viewport(Size::new(79, 24)));
+        let (style, _) = bulma.compute_style(&element, None, None, &mut caches);
+        assert_eq!(style.color, Color::RED);
+
+        assert!(bulma.set_viewport(Size::new(80, 24)));
+        let (style, _) = bulma.compute_style(&element, None, None, &mut caches);
+        assert_eq!(style.color, Color::BLUE);
+
+        assert!(!bulma.set_viewport(Size::new(120, 40)));
+        let (style, _) = bulma.compute_style(&element, None, None, &mut caches);
+        assert_eq!(style.color, Color::BLUE);
+    }
+
+    #[test]
+    fn collect_matching_rules_negation() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(".btn:not(.active) { color: red }").expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let mut caches = SelectorCaches::default();
+
+        let idle = TestElement::new("div").with_class("btn");
+        assert_eq!(bulma.collect_matching_rules(&idle, &mut caches).len(), 1);
+
+        let active = TestElement::new("div")
+            .with_class("btn")
+            .with_class("active");
+        assert!(
+            bulma
+                .collect_matching_rules(&active, &mut caches)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn identical_elements_share_styles() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(".item { color: red }").expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let mut caches = SelectorCaches::default();
+        let element = TestElement::new("li").with_class("item");
+
+        let (first, _) = bulma.compute_style(&element, None, None, &mut caches);
+        let (second, _) = bulma.compute_style(&element, None, None, &mut caches);
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(bulma.num_computed_styles(), 1);
+        assert_eq!(bulma.num_shared_styles(), 1);
+    }
+
+    #[test]
+    fn sharing_respects_parent_and_inline_style() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(".item { font-weight: bold }").expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let mut caches = SelectorCaches::default();
+        let element = TestElement::new("li").with_class("item");
+
+        let parent = ComputedStyle {
+            color: Color::BLUE,
+            ..Default::default()
+        };
+
+        let (plain, _) = bulma.compute_style(&element, None, None, &mut caches);
+        let (inherited, _) = bulma.compute_style(&element, Some(&parent), None, &mut caches);
+        assert_eq!(plain.color, ComputedStyle::default().color);
+        assert_eq!(inherited.color, Color::BLUE);
+
+        let inline = TestElement::new("li")
+            .with_class("item")
+            .with_style("color: red");
+        let (styled, _) = bulma.compute_style(&inline, None, None, &mut caches);
+        assert_eq!(styled.color, Color::RED);
+
+        assert_eq!(bulma.num_shared_styles(), 0);
+        assert_eq!(bulma.num_computed_styles(), 3);
+    }
+
     #[test]
     fn collect_matching_rules_no_match() {
         let mut bulma = {
@@ -869,6 +1226,117 @@ mod tests {
         assert_eq!(child_style.color, Color::CYAN);
     }
 
+    #[test]
+    fn compute_style_cursor_inherits_and_overrides() {
+        let mut bulma = {
+            let mut b = Bulma::new();
+            let stylesheet = Stylesheet::parse(
+                r"
+                .parent { cursor: pointer }
+                .text { cursor: text }
+            ",
+            )
+            .expect("failed");
+            b.add_stylesheet(&stylesheet);
+            b
+        };
+
+        let mut caches = SelectorCaches::default();
+        let (default_style, _) =
+            bulma.compute_style(&TestElement::new("div"), None, None, &mut caches);
+        assert_eq!(default_style.cursor, Cursor::Default);
+
+        let parent_element = TestElement::new("div").with_class("parent");
+        let mut caches = SelectorCaches::default();
+        let (parent_style, parent_cp) =
+            bulma.compute_style(&parent_element, None, None, &mut caches);
+
+        let mut caches = SelectorCaches::default();
+        let (child_style, _) = bulma.compute_style(
+            &TestElement::new("span"),
+            Some(&parent_style),
+            Some(&parent_cp),
+            &mut caches,
+        );
+        assert_eq!(child_style.cursor, Cursor::Pointer);
+
+        let mut caches = SelectorCaches::default();
+        let (child_style, _) = bulma.compute_style(
+            &TestElement::new("span").with_class("text"),
+            Some(&parent_style),
+            Some(&parent_cp),
+            &mut caches,
+        );
+        assert_eq!(child_style.cursor, Cursor::Text);
+    }
+
+    #[test]
+    fn compute_style_all_initial_resets_inherited() {
+        let mut bulma = {
+            let mut b = Bulma::new();
+            let stylesheet = Stylesheet::parse(
+                r"
+                .parent { color: cyan; font-weight: bold; text-align: center }
+                .isolated { all: initial }
+            ",
+            )
+            .expect("failed");
+            b.add_stylesheet(&stylesheet);
+            b
+        };
+
+        let parent_element = TestElement::new("div").with_class("parent");
+        let mut caches = SelectorCaches::default();
+        let (parent_style, parent_cp) =
+            bulma.compute_style(&parent_element, None, None, &mut caches);
+
+        let child_element = TestElement::new("div").with_class("isolated");
+        let mut caches = SelectorCaches::default();
+        let (child_style, _) = bulma.compute_style(
+            &child_element,
+            Some(&parent_style),
+            Some(&parent_cp),
+            &mut caches,
+        );
+
+        assert_eq!(child_style.color, Color::Reset);
+        assert_eq!(child_style.font_weight, FontWeight::default());
+        assert_eq!(child_style.text_align, TextAlign::default());
+    }
+
+    #[test]
+    fn compute_style_all_inherit_copies_parent() {
+        let mut bulma = {
+            let mut b = Bulma::new();
+            let stylesheet = Stylesheet::parse(
+                r"
+                .parent { display: flex; color: cyan }
+                .child { all: inherit }
+            ",
+            )
+            .expect("failed");
+            b.add_stylesheet(&stylesheet);
+            b
+        };
+
+        let parent_element = TestElement::new("div").with_class("parent");
+        let mut caches = SelectorCaches::default();
+        let (parent_style, parent_cp) =
+            bulma.compute_style(&parent_element, None, None, &mut caches);
+
+        let child_element = TestElement::new("div").with_class("child");
+        let mut caches = SelectorCaches::default();
+        let (child_style, _) = bulma.compute_style(
+            &child_element,
+            Some(&parent_style),
+            Some(&parent_cp),
+            &mut caches,
+        );
+
+        assert_eq!(child_style.display, Display::Flex);
+        assert_eq!(child_style.color, Color::CYAN);
+    }
+
     #[test]
     fn compute_style_does_not_inherit_display() {
         let mut bulma = {
@@ -966,6 +1434,59 @@ mod tests {
         assert_eq!(style.color, Color::BLUE);
     }
 
+    #[test]
+    fn explain_marks_the_winning_rule() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(
+            r"
+            .a { color: red; display: flex }
+            #id.a { color: blue }
+            .unrelated { color: green }
+        ",
+        )
+        .expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let element = TestElement::new("div").with_id("id").with_class("a");
+        let mut caches = SelectorCaches::default();
+
+        let explanations = bulma.explain(&element, &mut caches);
+        assert_eq!(explanations.len(), 2);
+
+        let (class, id) = (&explanations[0], &explanations[1]);
+        assert_eq!(class.selector.as_deref(), Some(".a"));
+        assert_eq!(id.selector.as_deref(), Some("#id.a"));
+        assert!(id.specificity > class.specificity);
+        assert!(class.source_order < id.source_order);
+
+        // The id rule takes color, display is only set by the class rule
+        assert!(!class.declarations[0].winning);
+        assert!(class.declarations[1].winning);
+        assert!(id.declarations[0].winning);
+    }
+
+    #[test]
+    fn explain_includes_inline_and_important() {
+        let mut bulma = Bulma::new();
+        let stylesheet =
+            Stylesheet::parse(".a { color: red !important; display: flex }").expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let element = TestElement::new("div")
+            .with_class("a")
+            .with_style("color: blue; display: block");
+        let mut caches = SelectorCaches::default();
+
+        let explanations = bulma.explain(&element, &mut caches);
+        let (rule, inline) = (&explanations[0], &explanations[1]);
+
+        assert_eq!(inline.selector, None);
+        assert!(rule.declarations[0].winning);
+        assert!(!rule.declarations[1].winning);
+        assert!(!inline.declarations[0].winning);
+        assert!(inline.declarations[1].winning);
+    }
+
     #[test]
     fn restyle_hint_for_hover_change() {
         let bulma = {
@@ -1124,10 +1645,10 @@ mod tests {
         let mut caches = SelectorCaches::default();
 
         let (style, _) = bulma.compute_style(&element, None, None, &mut caches);
-        assert_eq!(style.margin.top, Length::Cells(10));
-        assert_eq!(style.margin.right, Length::Cells(10));
-        assert_eq!(style.margin.bottom, Length::Cells(10));
-        assert_eq!(style.margin.left, Length::Cells(10));
+        assert_eq!(style.margin.top, Margin::from(Length::Cells(10)));
+        assert_eq!(style.margin.right, Margin::from(Length::Cells(10)));
+        assert_eq!(style.margin.bottom, Margin::from(Length::Cells(10)));
+        assert_eq!(style.margin.left, Margin::from(Length::Cells(10)));
     }
 
     #[test]
@@ -1282,6 +1803,26 @@ mod tests {
         assert_eq!(style.color, Color::RED);
     }
 
+    #[test]
+    fn registered_property_initial_value() {
+        let mut bulma = Bulma::new();
+        let stylesheet = Stylesheet::parse(
+            r#"
+            @property --x { syntax: "<color>"; inherits: false; initial-value: red }
+            div { color: var(--x) }
+            "#,
+        )
+        .expect("failed");
+        bulma.add_stylesheet(&stylesheet);
+
+        let element = TestElement::new("div");
+        let mut caches = SelectorCaches::default();
+        let (style, custom_properties) = bulma.compute_style(&element, None, None, &mut caches);
+
+        assert_eq!(style.color, Color::RED);
+        assert_eq!(custom_properties.get(Pose::from("x")), Some("red"));
+    }
+
     #[test]
     fn author_custom_property_overrides_ua() {
         let mut bulma = Bulma::new();
@@ -1298,6 +1839,24 @@ mod tests {
         assert_eq!(root_cp.get(Pose::from("color")), Some("blue"));
     }
 
+    #[test]
+    fn merged_stylesheets_match_both() {
+        let mut stylesheet =
+            Stylesheet::parse(".a { color: red; font-weight: bold }").expect("failed");
+        stylesheet.merge(Stylesheet::parse(".b { color: blue }").expect("failed"));
+
+        let mut bulma = Bulma::new();
+        bulma.add_stylesheet(&stylesheet);
+
+        let element = TestElement::new("div").with_class("a").with_class("b");
+        let mut caches = SelectorCaches::default();
+        let (style, _) = bulma.compute_style(&element, None, None, &mut caches);
+
+        assert_eq!(style.font_weight, FontWeight::Bold);
+        // Same specificity, so the later sheet wins
+        assert_eq!(style.color, Color::BLUE);
+    }
+
     #[test]
     fn clear_removes_ua_and_author() {
         let mut bulma = Bulma::new();
@@ -1,7 +1,9 @@
+use ginyu_force::{Pose, pose};
 use selectors::context::SelectorCaches;
 
 use crate::{
-    Bulma, CapsuleDocument, ComputedStyle, CustomPropertiesMap, bulma::restyle::RestyleHint,
+    Bulma, CapsuleDocument, ComputedStyle, CustomPropertiesMap, Display,
+    bulma::restyle::RestyleHint,
 };
 
 pub fn compute_styles<D: CapsuleDocument>(document: &mut D) {
@@ -31,6 +33,73 @@ pub fn restyle_subtree<D: CapsuleDocument>(document: &mut D, node: D::NodeId, hi
     document.set_stylist(stylist);
 }
 
+/// Restyles whatever adding or removing `class` on `node` can affect, going by
+/// the selectors that mention it. Returns the hint that was used, which is
+/// empty when no selector cares about the class.
+pub fn restyle_for_class_change<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    class: Pose,
+) -> RestyleHint {
+    let hint = hint_from_stylist(document, |stylist| {
+        stylist.restyle_hint_for_class_change(class)
+    });
+    restyle_subtree(document, node, hint);
+
+    hint
+}
+
+/// Like [`restyle_for_class_change`], for an attribute being set or removed.
+/// The `style` attribute always restyles the element itself.
+pub fn restyle_for_attribute_change<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    attribute: Pose,
+) -> RestyleHint {
+    let mut hint = hint_from_stylist(document, |stylist| {
+        stylist.restyle_hint_for_attribute_change(attribute)
+    });
+
+    if attribute == pose!("style") {
+        hint |= RestyleHint::RESTYLE_STYLE_ATTRIBUTE;
+    }
+
+    restyle_subtree(document, node, hint);
+
+    hint
+}
+
+/// Like [`restyle_for_class_change`], for the id going from `old` to `new`.
+pub fn restyle_for_id_change<D: CapsuleDocument>(
+    document: &mut D,
+    node: D::NodeId,
+    old: Option<Pose>,
+    new: Option<Pose>,
+) -> RestyleHint {
+    let hint = hint_from_stylist(document, |stylist| {
+        [old, new]
+            .into_iter()
+            .flatten()
+            .fold(RestyleHint::empty(), |hint, id| {
+                hint | stylist.restyle_hint_for_id_change(id)
+            })
+    });
+    restyle_subtree(document, node, hint);
+
+    hint
+}
+
+fn hint_from_stylist<D: CapsuleDocument>(
+    document: &mut D,
+    hint: impl FnOnce(&Bulma) -> RestyleHint,
+) -> RestyleHint {
+    let stylist = document.take_stylist();
+    let hint = hint(&stylist);
+    document.set_stylist(stylist);
+
+    hint
+}
+
 fn restyle_subtree_inner<D: CapsuleDocument>(
     document: &mut D,
     stylist: &mut Bulma,
@@ -46,6 +115,8 @@ fn restyle_subtree_inner<D: CapsuleDocument>(
             )
         });
 
+    let mut changed = false;
+
     if hint.affects_self() {
         let Some(element) = document.get_element(node) else {
             return;
@@ -53,10 +124,21 @@ fn restyle_subtree_inner<D: CapsuleDocument>(
         let (style, custom_properties) =
             stylist.compute_style(&element, parent_style, parent_custom_properties, caches);
 
+        changed = document.computed_style(node) != Some(&*style)
+            || document.custom_properties(node) != Some(&custom_properties);
+
         document.set_style(node, style, custom_properties);
     }
 
-    if hint.affects_descendants() {
+    // Nothing under a `display: none` subtree is rendered, so there is no point
+    // styling it. `visibility: hidden` still takes up space so we keep going.
+    let skip_descendants = document
+        .computed_style(node)
+        .is_some_and(|style| style.display == Display::None);
+
+    // Children inherit from the new style, so they need another look when it
+    // changed even if none of their own selectors did
+    if (hint.affects_descendants() || changed) && !skip_descendants {
         let style = document.computed_style(node).cloned();
         let custom_properties = document.custom_properties(node).cloned();
 
@@ -107,7 +189,7 @@ fn compute_styles_recursive<D: CapsuleDocument>(
             stylist,
             caches,
             child,
-            Some(&style),
+            Some(style.as_ref()),
             Some(&custom_properties),
         );
     }
@@ -131,13 +213,17 @@ fn restyle_subtree_recursive<D: CapsuleDocument>(
 
     document.set_style(node, style.clone(), custom_properties.clone());
 
+    if style.display == Display::None {
+        return;
+    }
+
     for child in children {
         restyle_subtree_recursive(
             document,
             stylist,
             caches,
             child,
-            Some(&style),
+            Some(style.as_ref()),
             Some(&custom_properties),
         );
     }
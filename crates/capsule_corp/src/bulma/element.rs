@@ -94,9 +94,6 @@ pub enum PseudoClass {
     Active,
     Disabled,
     Checked,
-    FirstChild,
-    LastChild,
-    NthChild(i32),
     Root,
 }
 
@@ -111,9 +108,6 @@ impl ToCss for PseudoClass {
             Self::Active => write!(dest, ":active"),
             Self::Disabled => write!(dest, ":disabled"),
             Self::Checked => write!(dest, ":checked"),
-            Self::FirstChild => write!(dest, ":first-child"),
-            Self::LastChild => write!(dest, ":last-child"),
-            Self::NthChild(n) => write!(dest, ":nth-child({n})"),
             Self::Root => write!(dest, ":root"),
         }
     }
@@ -184,8 +178,6 @@ impl<'i> Parser<'i> for SelectorParser {
             "active" => Ok(PseudoClass::Active),
             "disabled" => Ok(PseudoClass::Disabled),
             "checked" => Ok(PseudoClass::Checked),
-            "first-child" => Ok(PseudoClass::FirstChild),
-            "last-child" => Ok(PseudoClass::LastChild),
             "root" => Ok(PseudoClass::Root),
             _ => Err(ParseError {
                 kind: ParseErrorKind::Custom(
@@ -195,34 +187,13 @@ impl<'i> Parser<'i> for SelectorParser {
             }),
         }
     }
-
-    fn parse_non_ts_functional_pseudo_class<'t>(
-        &self,
-        name: CowRcStr<'i>,
-        parser: &mut cssparser::Parser<'i, 't>,
-        _after_part: bool,
-    ) -> Result<<Self::Impl as SelectorImpl>::NonTSPseudoClass, ParseError<'i, Self::Error>> {
-        let location = parser.current_source_location();
-        match name.as_ref() {
-            "nth-child" => {
-                let n = parser.expect_integer()?;
-                Ok(PseudoClass::NthChild(n))
-            }
-            _ => Err(cssparser::ParseError {
-                kind: cssparser::ParseErrorKind::Custom(
-                    SelectorParseErrorKind::UnsupportedPseudoClassOrElement(name),
-                ),
-                location,
-            }),
-        }
-    }
 }
 
 impl<E: CapsuleElement> Element for ConcreteCapsuleElement<E> {
     type Impl = Selectors;
 
     fn opaque(&self) -> OpaqueElement {
-        OpaqueElement::new(self)
+        OpaqueElement::from_non_null_ptr(self.identity())
     }
 
     fn parent_element(&self) -> Option<Self> {
@@ -250,7 +221,7 @@ impl<E: CapsuleElement> Element for ConcreteCapsuleElement<E> {
     }
 
     fn first_element_child(&self) -> Option<Self> {
-        None
+        self.first_child().map(ConcreteCapsuleElement)
     }
 
     fn is_html_element_in_html_document(&self) -> bool {
@@ -297,7 +268,6 @@ impl<E: CapsuleElement> Element for ConcreteCapsuleElement<E> {
     }
 
     // TODO: Use the `context` for perf improvements
-    #[allow(clippy::cast_sign_loss)]
     fn match_non_ts_pseudo_class(
         &self,
         pseudo_class: &PseudoClass,
@@ -312,9 +282,6 @@ impl<E: CapsuleElement> Element for ConcreteCapsuleElement<E> {
             Active => state.contains(ElementState::ACTIVE),
             Disabled => state.contains(ElementState::DISABLED),
             Checked => state.contains(ElementState::CHECKED),
-            FirstChild => self.is_first_child(),
-            LastChild => self.is_last_child(),
-            NthChild(n) => self.sibling_index() == (*n as usize),
             Root => self.is_root(),
         }
     }
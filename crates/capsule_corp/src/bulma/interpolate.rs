@@ -0,0 +1,114 @@
+use crate::{Color, ComputedStyle, Property};
+
+/// Properties that can be eased from one value to another by a transition.
+/// Everything else switches straight to its new value.
+pub const ANIMATABLE: &[Property] = &[
+    Property::Color,
+    Property::BackgroundColor,
+    Property::BorderTopColor,
+    Property::BorderRightColor,
+    Property::BorderBottomColor,
+    Property::BorderLeftColor,
+    Property::OutlineColor,
+    Property::Opacity,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Animated {
+    Color(Color),
+    Number(f32),
+}
+
+impl ComputedStyle {
+    /// Whether `self` and `other` disagree on `property`. Properties that
+    /// aren't [`ANIMATABLE`] never do.
+    #[must_use]
+    pub fn differs(&self, other: &Self, property: Property) -> bool {
+        self.animated(property) != other.animated(property)
+    }
+
+    /// Sets `property` to its value `t` of the way from `from` to `to`.
+    pub fn interpolate(&mut self, property: Property, from: &Self, to: &Self, t: f32) {
+        let value = match (from.animated(property), to.animated(property)) {
+            (Some(Animated::Color(from)), Some(Animated::Color(to))) => {
+                Animated::Color(from.lerp(to, t))
+            }
+            (Some(Animated::Number(from)), Some(Animated::Number(to))) => {
+                Animated::Number((to - from).mul_add(t, from))
+            }
+            _ => return,
+        };
+
+        self.set_animated(property, value);
+    }
+
+    const fn animated(&self, property: Property) -> Option<Animated> {
+        let value = match property {
+            Property::Color => Animated::Color(self.color),
+            Property::BackgroundColor => Animated::Color(self.background_color),
+            Property::BorderTopColor => Animated::Color(self.border_color.top),
+            Property::BorderRightColor => Animated::Color(self.border_color.right),
+            Property::BorderBottomColor => Animated::Color(self.border_color.bottom),
+            Property::BorderLeftColor => Animated::Color(self.border_color.left),
+            Property::OutlineColor => Animated::Color(self.outline_color),
+            Property::Opacity => Animated::Number(self.opacity),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+
+    const fn set_animated(&mut self, property: Property, value: Animated) {
+        match (property, value) {
+            (Property::Color, Animated::Color(v)) => self.color = v,
+            (Property::BackgroundColor, Animated::Color(v)) => self.background_color = v,
+            (Property::BorderTopColor, Animated::Color(v)) => self.border_color.top = v,
+            (Property::BorderRightColor, Animated::Color(v)) => self.border_color.right = v,
+            (Property::BorderBottomColor, Animated::Color(v)) => self.border_color.bottom = v,
+            (Property::BorderLeftColor, Animated::Color(v)) => self.border_color.left = v,
+            (Property::OutlineColor, Animated::Color(v)) => self.outline_color = v,
+            (Property::Opacity, Animated::Number(v)) => self.opacity = v,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_colors_and_numbers() {
+        let from = ComputedStyle {
+            background_color: Color::Rgb(0, 0, 0),
+            opacity: 0.0,
+            ..Default::default()
+        };
+        let to = ComputedStyle {
+            background_color: Color::Rgb(200, 100, 0),
+            opacity: 1.0,
+            ..Default::default()
+        };
+
+        let mut style = to.clone();
+        style.interpolate(Property::BackgroundColor, &from, &to, 0.5);
+        style.interpolate(Property::Opacity, &from, &to, 0.25);
+
+        assert_eq!(style.background_color, Color::Rgb(100, 50, 0));
+        assert!((style.opacity - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn only_animatable_properties_differ() {
+        let from = ComputedStyle::default();
+        let to = ComputedStyle {
+            color: Color::RED,
+            flex_grow: 1.0,
+            ..Default::default()
+        };
+
+        assert!(from.differs(&to, Property::Color));
+        assert!(!from.differs(&to, Property::BackgroundColor));
+        assert!(!from.differs(&to, Property::FlexGrow));
+    }
+}
@@ -11,8 +11,6 @@ pub struct InvalidationMap {
     attribute: FxHashMap<Pose, SmallVec<[Dependency; 4]>>,
     class: FxHashMap<Pose, SmallVec<[Dependency; 4]>>,
     id: FxHashMap<Pose, SmallVec<[Dependency; 4]>>,
-
-    
 }
 
 impl InvalidationMap {
@@ -36,7 +34,16 @@ impl InvalidationMap {
     }
 
     pub fn register_selector(&mut self, selector: &Selector<Selectors>) {
-        let mut location = DependencyLocation::Subject;
+        self.register_selector_at(selector, DependencyLocation::Subject);
+    }
+
+    /// Register `selector` as if its subject sat at `location`, for selectors
+    /// nested in `:not()`, `:is()` and `:where()`.
+    fn register_selector_at(
+        &mut self,
+        selector: &Selector<Selectors>,
+        mut location: DependencyLocation,
+    ) {
         let mut components = selector.iter();
 
         loop {
@@ -85,6 +92,17 @@ impl InvalidationMap {
                     .or_default()
                     .push(dependency);
             }
+            AttributeOther(attribute) => {
+                self.attribute
+                    .entry(attribute.local_name.as_pose())
+                    .or_default()
+                    .push(dependency);
+            }
+            Negation(list) | Is(list) | Where(list) => {
+                for selector in list.slice() {
+                    self.register_selector_at(selector, location);
+                }
+            }
             NonTSPseudoClass(pseudo) => {
                 let state = pseudo_class_to_state(pseudo);
                 if !state.is_empty() {
@@ -180,7 +198,7 @@ const fn pseudo_class_to_state(pseudo: &PseudoClass) -> ElementState {
         PseudoClass::Active => ElementState::ACTIVE,
         PseudoClass::Disabled => ElementState::DISABLED,
         PseudoClass::Checked => ElementState::CHECKED,
-        _ => ElementState::empty(),
+        PseudoClass::Root => ElementState::empty(),
     }
 }
 
@@ -260,6 +278,30 @@ mod tests {
         assert!(map.has_attribute_dependency(Pose::from("disabled")));
     }
 
+    #[test]
+    fn register_attribute_value_selectors() {
+        let mut map = InvalidationMap::new();
+        map.register_selector(&parse_selector("[data-state=\"open\"]"));
+        map.register_selector(&parse_selector("[data-kind=\"a\" i]"));
+        map.register_selector(&parse_selector("button:not([disabled])"));
+
+        assert!(map.has_attribute_dependency(Pose::from("data-state")));
+        assert!(map.has_attribute_dependency(Pose::from("data-kind")));
+        assert!(map.has_attribute_dependency(Pose::from("disabled")));
+    }
+
+    #[test]
+    fn attribute_change_restyle_hint() {
+        let mut map = InvalidationMap::new();
+        map.register_selector(&parse_selector("[data-state=\"open\"] .panel"));
+
+        let hint = map.restyle_hint_for_attribute_change(Pose::from("data-state"));
+        assert!(hint.contains(RestyleHint::RESTYLE_DESCENDANTS));
+
+        let hint = map.restyle_hint_for_attribute_change(Pose::from("data-other"));
+        assert!(hint.is_empty());
+    }
+
     #[test]
     fn state_change_restyle_hint_subject() {
         let mut map = InvalidationMap::new();
@@ -290,6 +332,22 @@ mod tests {
         assert!(hint.contains(RestyleHint::RESTYLE_SELF));
     }
 
+    #[test]
+    fn class_change_inside_negation() {
+        let mut map = InvalidationMap::new();
+        map.register_selector(&parse_selector(".btn:not(.active)"));
+
+        let hint = map.restyle_hint_for_class_change(Pose::from("active"));
+        assert!(hint.contains(RestyleHint::RESTYLE_SELF));
+
+        let mut map = InvalidationMap::new();
+        map.register_selector(&parse_selector(".list :not(.active) > .item"));
+
+        // The negated compound sits on the item's parent
+        let hint = map.restyle_hint_for_class_change(Pose::from("active"));
+        assert!(hint.contains(RestyleHint::RESTYLE_DESCENDANTS));
+    }
+
     #[test]
     fn class_change_no_hint_when_unrelated() {
         let mut map = InvalidationMap::new();
@@ -3,21 +3,26 @@ mod computed;
 mod core;
 mod document;
 mod element;
+mod interpolate;
 mod invalidation;
 mod query;
 mod restyle;
 mod rule;
+mod sharing;
+mod specificity;
 
 pub use computed::*;
 pub use core::*;
 pub use document::*;
 pub use element::*;
+pub use interpolate::ANIMATABLE;
 pub use query::*;
 pub use restyle::RestyleHint;
 use selectors::context::{
     MatchingContext, MatchingForInvalidation, MatchingMode, NeedsSelectorFlags, QuirksMode,
     SelectorCaches,
 };
+pub use specificity::Specificity;
 
 fn make_context(caches: &mut SelectorCaches) -> MatchingContext<'_, Selectors> {
     MatchingContext::new(
@@ -2,13 +2,14 @@ use std::sync::Arc;
 
 use selectors::parser::Selector;
 
-use crate::{Selectors, parser::Declaration};
+use crate::{MediaQueryList, Selectors, Size, parser::Declaration};
 
 #[derive(Debug, Clone)]
 pub struct BulmaRule {
     pub selector: Selector<Selectors>,
     pub declarations: Arc<Vec<Declaration>>,
     pub source_order: u32,
+    pub media: Arc<[MediaQueryList]>,
 }
 
 impl BulmaRule {
@@ -16,11 +17,13 @@ impl BulmaRule {
         selector: Selector<Selectors>,
         declarations: Arc<Vec<Declaration>>,
         source_order: u32,
+        media: Arc<[MediaQueryList]>,
     ) -> Self {
         Self {
             selector,
             declarations,
             source_order,
+            media,
         }
     }
 
@@ -28,4 +31,10 @@ impl BulmaRule {
     pub fn specificity(&self) -> u32 {
         self.selector.specificity()
     }
+
+    /// Whether the `@media` blocks around the rule match `viewport`.
+    #[inline]
+    pub fn applies_to(&self, viewport: Size) -> bool {
+        self.media.iter().all(|list| list.matches(viewport))
+    }
 }
@@ -0,0 +1,126 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use crate::{
+    ComputedStyle, CustomPropertiesMap, bulma::core::ApplicableDeclaration, parser::Declaration,
+};
+
+/// Recently computed styles, handed back to elements that would compute the
+/// exact same thing, like a long run of `<li class="even">`.
+///
+/// Two elements get the same style when they match the same declarations in
+/// the same order, have the same inline style, and their parents computed to
+/// the same values.
+#[derive(Debug, Default)]
+pub struct StyleSharingCache {
+    entries: VecDeque<Entry>,
+    hits: usize,
+    misses: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    // Holding on to the declarations keeps their addresses from being reused,
+    // so comparing by pointer stays sound
+    declarations: Vec<Arc<Vec<Declaration>>>,
+    inline_style: Option<Box<str>>,
+    parent_style: Option<ComputedStyle>,
+    parent_custom_properties: Option<CustomPropertiesMap>,
+
+    style: Arc<ComputedStyle>,
+    custom_properties: CustomPropertiesMap,
+}
+
+impl Entry {
+    fn matches(
+        &self,
+        matched: &[ApplicableDeclaration],
+        inline_style: Option<&str>,
+        parent_style: Option<&ComputedStyle>,
+        parent_custom_properties: Option<&CustomPropertiesMap>,
+    ) -> bool {
+        self.declarations.len() == matched.len()
+            && self
+                .declarations
+                .iter()
+                .zip(matched)
+                .all(|(a, b)| Arc::ptr_eq(a, &b.declarations))
+            && self.inline_style.as_deref() == inline_style
+            && self.parent_style.as_ref() == parent_style
+            && self.parent_custom_properties.as_ref() == parent_custom_properties
+    }
+}
+
+impl StyleSharingCache {
+    /// Candidates kept around. Matching runs of siblings is the common case,
+    /// so only a handful are ever needed.
+    const CAPACITY: usize = 32;
+
+    pub fn get(
+        &mut self,
+        matched: &[ApplicableDeclaration],
+        inline_style: Option<&str>,
+        parent_style: Option<&ComputedStyle>,
+        parent_custom_properties: Option<&CustomPropertiesMap>,
+    ) -> Option<(Arc<ComputedStyle>, CustomPropertiesMap)> {
+        let found = self.entries.iter().position(|entry| {
+            entry.matches(
+                matched,
+                inline_style,
+                parent_style,
+                parent_custom_properties,
+            )
+        });
+
+        let Some(index) = found else {
+            self.misses += 1;
+            return None;
+        };
+
+        self.hits += 1;
+
+        // Keep the most recently used entries at the front
+        let entry = self.entries.remove(index)?;
+        let shared = (entry.style.clone(), entry.custom_properties.clone());
+        self.entries.push_front(entry);
+
+        Some(shared)
+    }
+
+    pub fn insert(
+        &mut self,
+        matched: &[ApplicableDeclaration],
+        inline_style: Option<&str>,
+        parent_style: Option<&ComputedStyle>,
+        parent_custom_properties: Option<&CustomPropertiesMap>,
+        style: Arc<ComputedStyle>,
+        custom_properties: CustomPropertiesMap,
+    ) {
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_back();
+        }
+
+        self.entries.push_front(Entry {
+            declarations: matched
+                .iter()
+                .map(|applicable| applicable.declarations.clone())
+                .collect(),
+            inline_style: inline_style.map(Into::into),
+            parent_style: parent_style.cloned(),
+            parent_custom_properties: parent_custom_properties.cloned(),
+            style,
+            custom_properties,
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub const fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub const fn misses(&self) -> usize {
+        self.misses
+    }
+}
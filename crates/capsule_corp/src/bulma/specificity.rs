@@ -0,0 +1,81 @@
+use crate::Selector;
+
+/// How specific a selector is, compared id count first, then classes
+/// (including attributes and pseudo-classes), then elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Specificity {
+    pub ids: u32,
+    pub classes: u32,
+    pub elements: u32,
+}
+
+impl Specificity {
+    // How `selectors` packs the counts, ten bits each
+    const BITS: u32 = 10;
+    const MASK: u32 = (1 << Self::BITS) - 1;
+
+    #[must_use]
+    pub const fn new(ids: u32, classes: u32, elements: u32) -> Self {
+        Self {
+            ids,
+            classes,
+            elements,
+        }
+    }
+
+    #[must_use]
+    pub fn of(selector: &Selector) -> Self {
+        Self::from_packed(selector.specificity())
+    }
+
+    /// Unpack the `u32` the cascade sorts by.
+    #[must_use]
+    pub const fn from_packed(packed: u32) -> Self {
+        Self {
+            ids: packed >> (Self::BITS * 2),
+            classes: (packed >> Self::BITS) & Self::MASK,
+            elements: packed & Self::MASK,
+        }
+    }
+
+    #[must_use]
+    pub const fn packed(self) -> u32 {
+        (self.ids << (Self::BITS * 2)) | (self.classes << Self::BITS) | self.elements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_selector;
+
+    fn specificity(selector: &str) -> Specificity {
+        let list = parse_selector(selector).expect("failed");
+        Specificity::of(&list.slice()[0])
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(specificity(".a"), Specificity::new(0, 1, 0));
+        assert_eq!(specificity("#id"), Specificity::new(1, 0, 0));
+        assert_eq!(specificity("div.a.b"), Specificity::new(0, 2, 1));
+        assert_eq!(specificity("div > span:hover"), Specificity::new(0, 1, 2));
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(specificity("#id") > specificity("div.a.b"));
+        assert!(specificity("div.a.b") > specificity(".a"));
+        assert!(specificity(".a") > specificity("div"));
+    }
+
+    #[test]
+    fn packing_round_trips() {
+        let list = parse_selector("#a .b.c span").expect("failed");
+        let selector = &list.slice()[0];
+        let specificity = Specificity::of(selector);
+
+        assert_eq!(specificity, Specificity::new(1, 2, 1));
+        assert_eq!(specificity.packed(), selector.specificity());
+    }
+}
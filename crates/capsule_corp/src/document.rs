@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::sync::Arc;
 
 use ginyu_force::{Pose, pose};
 
@@ -24,7 +26,7 @@ pub trait CapsuleDocument {
     fn set_style(
         &mut self,
         node: Self::NodeId,
-        style: ComputedStyle,
+        style: Arc<ComputedStyle>,
         custom_properties: CustomPropertiesMap,
     );
     fn take_stylist(&mut self) -> Bulma;
@@ -45,27 +47,18 @@ pub trait CapsuleElement: Sized + Clone + Debug + PartialEq {
     fn prev_sibling(&self) -> Option<Self>;
     fn next_sibling(&self) -> Option<Self>;
     fn has_children(&self) -> bool;
-    fn is_first_child(&self) -> bool {
-        self.prev_sibling().is_none()
-    }
-    fn is_last_child(&self) -> bool {
-        self.next_sibling().is_none()
-    }
-    fn sibling_index(&self) -> usize {
-        let mut index = 1;
-        let mut current = self.clone();
-        while let Some(prev) = current.prev_sibling() {
-            index += 1;
-            current = prev;
-        }
-        index
-    }
+    /// First child that's an element, skipping text and other nodes.
+    fn first_child(&self) -> Option<Self>;
+    /// Address that stays the same for as long as the element lives, whichever
+    /// handle it's reached through. Selector matching caches sibling indices
+    /// by it.
+    fn identity(&self) -> NonNull<()>;
 }
 
 pub trait CapsuleNode {
     fn computed_style(&self) -> Option<&ComputedStyle>;
     fn custom_properties(&self) -> Option<&CustomPropertiesMap>;
-    fn set_style(&mut self, style: ComputedStyle, custom_properties: CustomPropertiesMap);
+    fn set_style(&mut self, style: Arc<ComputedStyle>, custom_properties: CustomPropertiesMap);
 
     fn layout(&self) -> Layout;
     fn set_layout(&mut self, layout: Layout);
@@ -74,6 +67,12 @@ pub trait CapsuleNode {
     fn clear_needs_layout(&mut self);
 
     fn text_content(&self) -> Option<&str>;
+
+    /// Whether this text is laid out exactly as given, as if under
+    /// `white-space: pre`, whatever its parent asks for.
+    fn is_preformatted(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
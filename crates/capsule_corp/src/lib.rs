@@ -12,11 +12,12 @@ use cssparser::ParserInput;
 pub use document::*;
 pub use ginyu_force::Pose;
 
-pub use parser::{ParseErrorKind, ParseResult, Stylesheet, parse_stylesheet};
+pub use parser::{ParseDiagnostic, ParseErrorKind, ParseResult, Stylesheet, parse_stylesheet};
 pub use property::*;
 pub use values::*;
 
 pub type SelectorList = selectors::SelectorList<Selectors>;
+pub type Selector = selectors::parser::Selector<Selectors>;
 
 pub fn parse_selector(selector: &str) -> Result<SelectorList, String> {
     let mut input = ParserInput::new(selector);
@@ -47,6 +47,9 @@ macro_rules! keyword_enum {
         }
 
         impl $name {
+            /// Every variant except `Custom`.
+            pub const ALL: &'static [Self] = &[$(Self::$variant,)*];
+
             pub fn from_name(name: &str) -> Option<Self> {
                 match name {
                     $($str => Some(Self::$variant),)*
@@ -21,16 +21,9 @@ pub fn parse_color<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
 fn parse_color_function<'i>(name: &str, input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
     let location = input.current_source_location();
 
-    match name {
-        "rgb" => {
-            let r = parse_u8(input)?;
-            input.expect_comma()?;
-            let g = parse_u8(input)?;
-            input.expect_comma()?;
-            let b = parse_u8(input)?;
-
-            Ok(Color::Rgb(r, g, b))
-        }
+    match name.to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => parse_rgb(input),
+        "hsl" | "hsla" => parse_hsl(input),
         "ansi" => {
             let n = parse_u8(input)?;
             Ok(Color::Ansi(n))
@@ -39,6 +32,119 @@ fn parse_color_function<'i>(name: &str, input: &mut Parser<'i, '_>) -> ParseResu
     }
 }
 
+/// `rgb(r, g, b[, a])` or `rgb(r g b[ / a])`, where channels are numbers or
+/// percentages. Out of range channels are clamped and alpha is ignored, the
+/// terminal has no way to blend.
+fn parse_rgb<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
+    let r = parse_channel(input)?;
+    let commas = input.try_parse(Parser::expect_comma).is_ok();
+    let g = parse_channel(input)?;
+    parse_separator(input, commas)?;
+    let b = parse_channel(input)?;
+    parse_alpha(input, commas)?;
+
+    Ok(Color::Rgb(r, g, b))
+}
+
+/// `hsl(h, s%, l%[, a])` or `hsl(h s% l%[ / a])`, converted to RGB.
+fn parse_hsl<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Color> {
+    let hue = parse_hue(input)?;
+    let commas = input.try_parse(Parser::expect_comma).is_ok();
+    let saturation = parse_percentage(input)?;
+    parse_separator(input, commas)?;
+    let lightness = parse_percentage(input)?;
+    parse_alpha(input, commas)?;
+
+    let (r, g, b) = hsl_to_rgb(hue, saturation, lightness);
+
+    Ok(Color::Rgb(r, g, b))
+}
+
+fn parse_separator<'i>(input: &mut Parser<'i, '_>, commas: bool) -> ParseResult<'i, ()> {
+    if commas {
+        input.expect_comma()?;
+    }
+
+    Ok(())
+}
+
+fn parse_alpha<'i>(input: &mut Parser<'i, '_>, commas: bool) -> ParseResult<'i, ()> {
+    let has_alpha = if commas {
+        input.try_parse(Parser::expect_comma).is_ok()
+    } else {
+        input.try_parse(|i| i.expect_delim('/')).is_ok()
+    };
+
+    if has_alpha && input.try_parse(Parser::expect_number).is_err() {
+        input.expect_percentage()?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_channel<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, u8> {
+    let location = input.current_source_location();
+
+    let value = match input.next()? {
+        Token::Number { value, .. } => *value,
+        Token::Percentage { unit_value, .. } => unit_value * 255.0,
+        other => return expected("number or percentage", other, location),
+    };
+
+    Ok(value.round().clamp(0.0, 255.0) as u8)
+}
+
+/// A hue in degrees, bare or with a `deg`, `rad`, `grad` or `turn` unit.
+fn parse_hue<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, f32> {
+    let location = input.current_source_location();
+
+    let degrees = match input.next()? {
+        Token::Number { value, .. } => *value,
+        Token::Dimension { value, unit, .. } => match unit.to_ascii_lowercase().as_str() {
+            "deg" => *value,
+            "rad" => value.to_degrees(),
+            "grad" => value * 0.9,
+            "turn" => value * 360.0,
+            _ => return error(ParseErrorKind::InvalidColor, location),
+        },
+        other => return expected("hue", other, location),
+    };
+
+    Ok(degrees.rem_euclid(360.0))
+}
+
+/// A percentage as a fraction, clamped to `0..=1`.
+fn parse_percentage<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, f32> {
+    let location = input.current_source_location();
+
+    match input.next()? {
+        Token::Percentage { unit_value, .. } => Ok(unit_value.clamp(0.0, 1.0)),
+        other => expected("percentage", other, location),
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn hsl_to_rgb(hue: f32, saturation: f32, lightness: f32) -> (u8, u8, u8) {
+    let chroma = (1.0 - 2.0f32.mul_add(lightness, -1.0).abs()) * saturation;
+    let sector = hue / 60.0;
+    let second = chroma * (1.0 - (sector.rem_euclid(2.0) - 1.0).abs());
+
+    let (red, green, blue) = match sector as u8 {
+        0 => (chroma, second, 0.0),
+        1 => (second, chroma, 0.0),
+        2 => (0.0, chroma, second),
+        3 => (0.0, second, chroma),
+        4 => (second, 0.0, chroma),
+        _ => (chroma, 0.0, second),
+    };
+
+    let lift = lightness - chroma / 2.0;
+    let channel = |value: f32| ((value + lift) * 255.0).round().clamp(0.0, 255.0) as u8;
+
+    (channel(red), channel(green), channel(blue))
+}
+
 #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 fn parse_u8<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, u8> {
     let location = input.current_source_location();
@@ -165,6 +271,92 @@ mod tests {
         assert_eq!(parse("rgb(0, 0, 0)").expect("failed"), Color::Rgb(0, 0, 0));
     }
 
+    #[test]
+    fn rgb_space_separated() {
+        assert_eq!(
+            parse("rgb(255 100 50)").expect("failed"),
+            Color::Rgb(255, 100, 50)
+        );
+        assert_eq!(
+            parse("rgb(100% 0% 50%)").expect("failed"),
+            Color::Rgb(255, 0, 128)
+        );
+    }
+
+    #[test]
+    fn rgba_ignores_alpha() {
+        assert_eq!(
+            parse("rgba(255, 0, 0, 0.5)").expect("failed"),
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(
+            parse("rgb(0 255 0 / 50%)").expect("failed"),
+            Color::Rgb(0, 255, 0)
+        );
+    }
+
+    #[test]
+    fn rgb_clamps_out_of_range() {
+        assert_eq!(
+            parse("rgb(300, -20, 127.6)").expect("failed"),
+            Color::Rgb(255, 0, 128)
+        );
+        assert_eq!(
+            parse("rgb(150% 0 0)").expect("failed"),
+            Color::Rgb(255, 0, 0)
+        );
+    }
+
+    #[test]
+    fn rgb_mixed_separators() {
+        assert!(parse("rgb(255, 0 0)").is_err());
+        assert!(parse("rgb(255 0 0, 1)").is_err());
+    }
+
+    #[test]
+    fn hsl() {
+        assert_eq!(
+            parse("hsl(120, 100%, 50%)").expect("failed"),
+            Color::Rgb(0, 255, 0)
+        );
+        assert_eq!(
+            parse("hsl(0 100% 50%)").expect("failed"),
+            Color::Rgb(255, 0, 0)
+        );
+        assert_eq!(
+            parse("hsl(240deg 100% 25%)").expect("failed"),
+            Color::Rgb(0, 0, 128)
+        );
+        assert_eq!(
+            parse("hsl(0, 0%, 100%)").expect("failed"),
+            Color::Rgb(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn hsla_and_hue_units() {
+        assert_eq!(
+            parse("hsla(0.5turn, 100%, 50%, 0.3)").expect("failed"),
+            Color::Rgb(0, 255, 255)
+        );
+        assert_eq!(
+            parse("hsl(-60 100% 50% / 1)").expect("failed"),
+            Color::Rgb(255, 0, 255)
+        );
+    }
+
+    #[test]
+    fn hsl_clamps_out_of_range() {
+        assert_eq!(
+            parse("hsl(120, 150%, 50%)").expect("failed"),
+            Color::Rgb(0, 255, 0)
+        );
+        assert_eq!(
+            parse("hsl(120, 100%, -10%)").expect("failed"),
+            Color::Rgb(0, 0, 0)
+        );
+    }
+
     #[test]
     fn hex_short() {
         assert_eq!(parse("#f00").expect("failed"), Color::Rgb(255, 0, 0));
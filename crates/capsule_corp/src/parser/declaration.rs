@@ -2,11 +2,13 @@ use cssparser::{ParseError, Parser, ParserInput, Token, parse_important};
 use ginyu_force::Pose;
 
 use crate::{
-    Color, CustomValue, Dimension, GlobalKeyword, Length, ParseErrorKind, ParseResult, Property,
-    PropertyName, Shorthand, UnresolvedValue, Value,
+    BorderStyle, Color, CustomValue, Dimension, GlobalKeyword, Length, ParseErrorKind, ParseResult,
+    Property, PropertyName, Shorthand, UnresolvedValue, Value,
     parser::{
-        error::build_err, parse_border_style, parse_color, parse_dimension, parse_length,
-        parse_number, parse_overflow, parse_value_with_vars, value::parse_property_value,
+        error::{build_err, expected},
+        parse_border_style, parse_color, parse_dimension, parse_length, parse_margin, parse_number,
+        parse_overflow, parse_value_with_vars,
+        value::parse_property_value,
     },
 };
 
@@ -223,6 +225,7 @@ fn parse_shorthand<'i>(
                 Property::MarginBottom,
                 Property::MarginLeft,
             ],
+            parse_margin,
         ),
         Padding => parse_box_shorthand(
             input,
@@ -232,6 +235,7 @@ fn parse_shorthand<'i>(
                 Property::PaddingBottom,
                 Property::PaddingLeft,
             ],
+            parse_length,
         ),
         Gap => parse_gap_shorthand(input),
         Overflow => parse_overflow_shorthand(input),
@@ -256,6 +260,11 @@ fn parse_shorthand<'i>(
             parse_border_side_shorthand(input, Property::BorderLeftStyle, Property::BorderLeftColor)
         }
         Background => parse_background_shorthand(input),
+        Outline => {
+            parse_border_side_shorthand(input, Property::OutlineStyle, Property::OutlineColor)
+        }
+        // `all` only accepts the global keywords, which are handled before we get here
+        All => Err(input.new_error_for_next_token()),
     }
 }
 
@@ -263,6 +272,7 @@ fn shorthand_properties(shorthand: Shorthand) -> Vec<Property> {
     use Shorthand::*;
     match shorthand {
         Background => vec![Property::BackgroundColor],
+        Outline => vec![Property::OutlineStyle, Property::OutlineColor],
         Margin => vec![
             Property::MarginTop,
             Property::MarginRight,
@@ -308,6 +318,7 @@ fn shorthand_properties(shorthand: Shorthand) -> Vec<Property> {
             Property::BorderBottomColor,
             Property::BorderLeftColor,
         ],
+        All => Property::ALL.to_vec(),
     }
 }
 
@@ -343,16 +354,17 @@ fn expand_unresolved(
         .collect()
 }
 
-fn parse_box_shorthand<'i>(
+fn parse_box_shorthand<'i, T: Clone + Into<Value>>(
     input: &mut Parser<'i, '_>,
     properties: [Property; 4],
+    parse_side: fn(&mut Parser<'i, '_>) -> ParseResult<'i, T>,
 ) -> ParseResult<'i, Vec<Declaration>> {
     let mut values = Vec::with_capacity(4);
 
-    values.push(parse_length(input)?);
+    values.push(parse_side(input)?);
 
     for _ in 0..3 {
-        if let Ok(value) = input.try_parse(parse_length) {
+        if let Ok(value) = input.try_parse(parse_side) {
             values.push(value);
         } else {
             break;
@@ -388,13 +400,15 @@ fn parse_box_shorthand<'i>(
     };
 
     Ok(vec![
-        Declaration::new(properties[0], Value::Length(top)),
-        Declaration::new(properties[1], Value::Length(right)),
-        Declaration::new(properties[2], Value::Length(bottom)),
-        Declaration::new(properties[3], Value::Length(left)),
+        Declaration::new(properties[0], top.into()),
+        Declaration::new(properties[1], right.into()),
+        Declaration::new(properties[2], bottom.into()),
+        Declaration::new(properties[3], left.into()),
     ])
 }
 
+/// `gap: <row> [<column>]`. Either may be a percentage, which flex layout
+/// resolves against the container's height for rows and width for columns.
 fn parse_gap_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<Declaration>> {
     let row = parse_length(input)?;
     let column = input
@@ -403,7 +417,7 @@ fn parse_gap_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<De
 
     Ok(vec![
         Declaration::new(Property::RowGap, Value::Length(row)),
-        Declaration::new(Property::RowGap, Value::Length(column)),
+        Declaration::new(Property::ColumnGap, Value::Length(column)),
     ])
 }
 
@@ -448,10 +462,9 @@ fn parse_flex_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<D
     ])
 }
 
-/// Parse <style> <color>?
+/// Parse `<width>? <style>? <color>?` on all four sides.
 fn parse_border_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<Declaration>> {
-    let style = parse_border_style(input)?;
-    let color = input.try_parse(parse_color).unwrap_or(Color::Reset);
+    let (style, color) = parse_border_parts(input)?;
 
     Ok(vec![
         Declaration::new(Property::BorderTopStyle, Value::BorderStyle(style)),
@@ -465,6 +478,56 @@ fn parse_border_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec
     ])
 }
 
+/// Parse the width, style and color of a border in any order. Whatever is
+/// left out resets. Borders are always a cell wide, so a width is accepted
+/// and ignored.
+fn parse_border_parts<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, (BorderStyle, Color)> {
+    let mut style = None;
+    let mut color = None;
+    let mut width = false;
+
+    loop {
+        if style.is_none()
+            && let Ok(value) = input.try_parse(parse_border_style)
+        {
+            style = Some(value);
+        } else if color.is_none()
+            && let Ok(value) = input.try_parse(parse_color)
+        {
+            color = Some(value);
+        } else if !width && input.try_parse(parse_border_width).is_ok() {
+            width = true;
+        } else {
+            break;
+        }
+    }
+
+    if style.is_none() && color.is_none() && !width {
+        let location = input.current_source_location();
+        let token = input.next()?;
+        return expected("border style, color or width", token, location);
+    }
+
+    Ok((style.unwrap_or_default(), color.unwrap_or_default()))
+}
+
+fn parse_border_width<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, ()> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+
+    match token {
+        Token::Number { .. } | Token::Dimension { .. } => Ok(()),
+        Token::Ident(ident)
+            if ["thin", "medium", "thick"]
+                .iter()
+                .any(|width| ident.eq_ignore_ascii_case(width)) =>
+        {
+            Ok(())
+        }
+        other => expected("border width", other, location),
+    }
+}
+
 fn parse_border_style_shorthand<'i>(
     input: &mut Parser<'i, '_>,
 ) -> ParseResult<'i, Vec<Declaration>> {
@@ -529,17 +592,13 @@ fn parse_border_color_shorthand<'i>(
     ])
 }
 
-/// Parse border-<side> shorthand: <style> <color>?
+/// Parse border-<side> shorthand: <width>? <style>? <color>?
 fn parse_border_side_shorthand<'i>(
     input: &mut Parser<'i, '_>,
     style_prop: Property,
     color_prop: Property,
 ) -> ParseResult<'i, Vec<Declaration>> {
-    use crate::parser::{parse_border_style, parse_color};
-    use crate::values::Color;
-
-    let style = parse_border_style(input)?;
-    let color = input.try_parse(parse_color).unwrap_or(Color::Reset);
+    let (style, color) = parse_border_parts(input)?;
 
     Ok(vec![
         Declaration::new(style_prop, Value::BorderStyle(style)),
@@ -561,7 +620,7 @@ fn parse_background_shorthand<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i,
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Color, Display, Property, Value};
+    use crate::{BorderStyle, Color, Display, Margin, Property, Value};
     use cssparser::ParserInput;
 
     fn parse(name: &str, value: &str) -> Result<Vec<Declaration>, String> {
@@ -592,6 +651,19 @@ mod tests {
         assert!(decls.iter().all(|d| d.value == Value::Initial));
     }
 
+    #[test]
+    fn all_expands_global_keyword() {
+        let decls = parse("all", "unset").expect("failed");
+        assert_eq!(decls.len(), Property::ALL.len());
+        assert!(decls.iter().all(|d| d.value == Value::Unset));
+        assert!(decls.iter().all(|d| !d.property.is_custom()));
+    }
+
+    #[test]
+    fn all_rejects_other_values() {
+        assert!(parse("all", "red").is_err());
+    }
+
     #[test]
     fn var_function_deferred() {
         let decls = parse("color", "var(--primary)").expect("failed");
@@ -617,6 +689,58 @@ mod tests {
         assert!(unresolved.references[0].fallback.is_some());
     }
 
+    fn sides(decls: &[Declaration]) -> Vec<i32> {
+        decls
+            .iter()
+            .map(|d| match &d.value {
+                Value::Length(Length::Cells(n)) => i32::from(*n),
+                Value::Margin(margin) => i32::from(margin.resolve(0)),
+                other => panic!("expected cells, got {other:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn box_shorthand_arities() {
+        // top right bottom left
+        let decls = parse("margin", "1 2 3 4").expect("failed");
+        assert_eq!(decls[0].property, Property::MarginTop);
+        assert_eq!(decls[3].property, Property::MarginLeft);
+        assert_eq!(sides(&decls), [1, 2, 3, 4]);
+
+        // left mirrors right
+        let decls = parse("margin", "1 2 3").expect("failed");
+        assert_eq!(sides(&decls), [1, 2, 3, 2]);
+
+        // vertical horizontal
+        let decls = parse("padding", "1 2").expect("failed");
+        assert_eq!(decls[0].property, Property::PaddingTop);
+        assert_eq!(decls[1].property, Property::PaddingRight);
+        assert_eq!(decls[2].property, Property::PaddingBottom);
+        assert_eq!(decls[3].property, Property::PaddingLeft);
+        assert_eq!(sides(&decls), [1, 2, 1, 2]);
+
+        let decls = parse("padding", "5").expect("failed");
+        assert_eq!(sides(&decls), [5, 5, 5, 5]);
+    }
+
+    #[test]
+    fn gap_sets_rows_and_columns() {
+        let decls = parse("gap", "1 10%").expect("failed");
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0].property, Property::RowGap);
+        assert_eq!(decls[0].value, Value::Length(Length::Cells(1)));
+        assert_eq!(decls[1].property, Property::ColumnGap);
+        assert_eq!(decls[1].value, Value::Length(Length::Percent(10.0)));
+
+        let decls = parse("gap", "25%").expect("failed");
+        assert!(
+            decls
+                .iter()
+                .all(|d| d.value == Value::Length(Length::Percent(25.0)))
+        );
+    }
+
     #[test]
     fn important_flag() {
         let decls = parse("color", "red !important").expect("failed");
@@ -685,6 +809,64 @@ mod tests {
         assert!(decls.iter().any(|d| d.property == Property::MarginLeft));
     }
 
+    #[test]
+    fn border_shorthand_expands() {
+        let decls = parse_inline_style("border: solid red");
+        assert_eq!(decls.len(), 8);
+
+        for side in [
+            Property::BorderTopColor,
+            Property::BorderRightColor,
+            Property::BorderBottomColor,
+            Property::BorderLeftColor,
+        ] {
+            let decl = decls
+                .iter()
+                .find(|d| d.property == side)
+                .expect("missing side");
+            assert_eq!(decl.value, Value::Color(Color::RED));
+        }
+
+        assert!(
+            decls[..4]
+                .iter()
+                .all(|d| d.value == Value::BorderStyle(BorderStyle::Solid))
+        );
+    }
+
+    #[test]
+    fn border_shorthand_any_order() {
+        let decls = parse_inline_style("border: 1 red rounded");
+        assert_eq!(decls.len(), 8);
+        assert_eq!(decls[0].value, Value::BorderStyle(BorderStyle::Rounded));
+        assert_eq!(decls[4].value, Value::Color(Color::RED));
+
+        // Left out parts reset
+        let decls = parse_inline_style("border: thick blue");
+        assert_eq!(decls[0].value, Value::BorderStyle(BorderStyle::None));
+        assert_eq!(decls[4].value, Value::Color(Color::BLUE));
+    }
+
+    #[test]
+    fn border_side_shorthand() {
+        let decls = parse_inline_style("border-top: solid");
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0].property, Property::BorderTopStyle);
+        assert_eq!(decls[0].value, Value::BorderStyle(BorderStyle::Solid));
+        assert_eq!(decls[1].property, Property::BorderTopColor);
+        assert_eq!(decls[1].value, Value::Color(Color::Reset));
+    }
+
+    #[test]
+    fn outline_shorthand_expands() {
+        let decls = parse_inline_style("outline: rounded cyan");
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0].property, Property::OutlineStyle);
+        assert_eq!(decls[0].value, Value::BorderStyle(BorderStyle::Rounded));
+        assert_eq!(decls[1].property, Property::OutlineColor);
+        assert_eq!(decls[1].value, Value::Color(Color::CYAN));
+    }
+
     #[test]
     fn style_important_flag() {
         let decls = parse_inline_style("color: red !important");
@@ -715,6 +897,21 @@ mod tests {
         assert!(decls.iter().any(|d| d.property == Property::Display));
     }
 
+    #[test]
+    fn negative_margin_kept() {
+        let decls = parse_inline_style("margin-top: -2; padding-top: -2; color: red");
+        assert_eq!(decls.len(), 2);
+        assert_eq!(decls[0].property, Property::MarginTop);
+        assert_eq!(
+            decls[0].value,
+            Value::Margin(Margin::negative(Length::Cells(2)))
+        );
+        assert_eq!(decls[1].property, Property::Color);
+
+        let decls = parse_inline_style("margin: -1 2");
+        assert_eq!(sides(&decls), [-1, 2, -1, 2]);
+    }
+
     #[test]
     fn custom_property() {
         let decls = parse_inline_style("--primary: blue");
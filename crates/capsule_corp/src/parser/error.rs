@@ -1,4 +1,4 @@
-use cssparser::{ParseError, SourceLocation, ToCss, Token};
+use cssparser::{BasicParseErrorKind, ParseError, SourceLocation, ToCss, Token};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -47,6 +47,50 @@ pub enum ParseErrorKind {
 
     #[error("failed to parse selector: {0}")]
     BadSelector(String),
+
+    #[error("unknown at-rule '@{0}'")]
+    UnknownAtRule(String),
+
+    #[error("invalid rule")]
+    InvalidRule,
+
+    #[error("@import has to come before every other rule")]
+    MisplacedImport,
+}
+
+/// Something wrong with a stylesheet that parsing skipped over, and where.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{line}:{column}: {kind}")]
+pub struct ParseDiagnostic {
+    pub kind: ParseErrorKind,
+    /// 1-based, like an editor shows it
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<ParseError<'_, ParseErrorKind>> for ParseDiagnostic {
+    fn from(error: ParseError<'_, ParseErrorKind>) -> Self {
+        let kind = match error.kind {
+            cssparser::ParseErrorKind::Custom(kind) => kind,
+            cssparser::ParseErrorKind::Basic(basic) => match basic {
+                BasicParseErrorKind::UnexpectedToken(token) => {
+                    ParseErrorKind::UnexpectedToken(token.to_css_string())
+                }
+                BasicParseErrorKind::EndOfInput => ParseErrorKind::UnexpectedEof,
+                BasicParseErrorKind::AtRuleInvalid(name) => {
+                    ParseErrorKind::UnknownAtRule(name.to_string())
+                }
+                BasicParseErrorKind::AtRuleBodyInvalid
+                | BasicParseErrorKind::QualifiedRuleInvalid => ParseErrorKind::InvalidRule,
+            },
+        };
+
+        Self {
+            kind,
+            line: error.location.line + 1,
+            column: error.location.column,
+        }
+    }
 }
 
 pub type ParseResult<'i, T> = Result<T, ParseError<'i, ParseErrorKind>>;
@@ -0,0 +1,133 @@
+use cssparser::{Parser, Token};
+
+use crate::{
+    GridLine, GridPlacement, ParseErrorKind, ParseResult, TrackSize,
+    parser::{
+        error::{error, expected},
+        length::parse_length,
+        parse_integer,
+    },
+};
+
+/// `none`, or a list of tracks where each is a length, `<n>fr`, `auto` or
+/// `repeat(<count>, <track>+)`.
+pub fn parse_grid_template<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<TrackSize>> {
+    if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        return Ok(Vec::new());
+    }
+
+    let mut tracks = parse_tracks(input)?;
+
+    while let Ok(more) = input.try_parse(parse_tracks) {
+        tracks.extend(more);
+    }
+
+    Ok(tracks)
+}
+
+/// A single track, or the tracks a `repeat()` expands to.
+fn parse_tracks<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<TrackSize>> {
+    if input
+        .try_parse(|i| i.expect_function_matching("repeat"))
+        .is_ok()
+    {
+        return input.parse_nested_block(parse_repeat);
+    }
+
+    parse_track_size(input).map(|track| vec![track])
+}
+
+fn parse_repeat<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<TrackSize>> {
+    let count = parse_positive(input)?;
+    input.expect_comma()?;
+
+    let mut pattern = vec![parse_track_size(input)?];
+    while let Ok(track) = input.try_parse(parse_track_size) {
+        pattern.push(track);
+    }
+
+    Ok(pattern
+        .iter()
+        .cycle()
+        .take(pattern.len() * usize::from(count))
+        .cloned()
+        .collect())
+}
+
+fn parse_track_size<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, TrackSize> {
+    if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+        return Ok(TrackSize::Auto);
+    }
+
+    let fraction = input.try_parse(|i| {
+        let location = i.current_source_location();
+
+        match i.next()? {
+            Token::Dimension { value, unit, .. }
+                if unit.eq_ignore_ascii_case("fr") && *value >= 0.0 =>
+            {
+                Ok(*value)
+            }
+            other => expected("non-negative fraction", other, location),
+        }
+    });
+
+    if let Ok(fraction) = fraction {
+        return Ok(TrackSize::Fraction(fraction));
+    }
+
+    parse_length(input).map(TrackSize::Length)
+}
+
+/// `<line> [/ <line>]`, where a line is `auto`, a non-zero line number or
+/// `span <n>`. A lone line number covers just the track after it.
+pub fn parse_grid_placement<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, GridPlacement> {
+    let start = parse_grid_line(input)?;
+    let end = if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+        parse_grid_line(input)?
+    } else {
+        GridLine::Auto
+    };
+
+    Ok(GridPlacement::new(start, end))
+}
+
+fn parse_grid_line<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, GridLine> {
+    if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+        return Ok(GridLine::Auto);
+    }
+
+    if input.try_parse(|i| i.expect_ident_matching("span")).is_ok() {
+        return parse_positive(input).map(GridLine::Span);
+    }
+
+    let location = input.current_source_location();
+
+    match parse_integer(input)? {
+        0 => error(
+            ParseErrorKind::Expected {
+                what: "non-zero line number".into(),
+                got: "0".into(),
+            },
+            location,
+        ),
+        line => Ok(GridLine::Line(line)),
+    }
+}
+
+fn parse_positive<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, u16> {
+    let location = input.current_source_location();
+    let n = parse_integer(input)?;
+
+    match u16::try_from(n) {
+        Ok(n) if n > 0 => Ok(n),
+        _ => error(
+            ParseErrorKind::OutOfRange {
+                value: i64::from(n),
+                min: 1,
+                max: i64::from(i16::MAX),
+            },
+            location,
+        ),
+    }
+}
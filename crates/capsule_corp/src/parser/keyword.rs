@@ -1,9 +1,9 @@
 use cssparser::{Parser, Token};
 
 use crate::{
-    AlignContent, AlignItems, AlignSelf, BorderStyle, Display, FlexDirection, FlexWrap, FontStyle,
-    FontWeight, JustifyContent, Overflow, OverflowWrap, ParseErrorKind, ParseResult, TextAlign,
-    TextDecoration, VerticalAlign, Visibility, WhiteSpace,
+    AlignContent, AlignItems, AlignSelf, BorderStyle, Cursor, Display, FlexDirection, FlexWrap,
+    FontStyle, FontWeight, JustifyContent, Overflow, OverflowWrap, ParseErrorKind, ParseResult,
+    Position, TextAlign, TextDecoration, TextOverflow, VerticalAlign, Visibility, WhiteSpace,
     parser::error::{build_err, expected},
 };
 
@@ -48,6 +48,7 @@ macro_rules! keyword_parsers {
 
 keyword_parsers! {
     parse_display => Display, "display";
+    parse_position => Position, "position";
     parse_flex_direction => FlexDirection, "flex-direction";
     parse_flex_wrap => FlexWrap, "flex-wrap";
     parse_justify_content => JustifyContent, "justify-content";
@@ -62,11 +63,14 @@ keyword_parsers! {
     parse_text_decoration => TextDecoration, "text-decoration";
     parse_white_space => WhiteSpace, "white-space";
     parse_overflow_wrap => OverflowWrap, "overflow-wrap";
+    parse_text_overflow => TextOverflow, "text-overflow";
 
     parse_overflow => Overflow, "overflow";
     parse_visibility => Visibility, "visibility";
 
     parse_border_style => BorderStyle, "border-style";
+
+    parse_cursor => Cursor, "cursor";
 }
 
 #[cfg(test)]
@@ -1,12 +1,11 @@
-use cssparser::{Parser, Token};
+use cssparser::{Parser, SourceLocation, Token};
 
 use crate::{
-    CalcExpr, Dimension, Length, ParseResult,
-    parser::error::{expected, integer_required, unexpected_token},
+    CalcExpr, Dimension, Length, Margin, ParseErrorKind, ParseResult,
+    parser::error::{error, expected, integer_required, unexpected_token},
 };
 
 /// Parse a length: integer, integer + 'c', or percentage.
-#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
 pub fn parse_length<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Length> {
     if input
         .try_parse(|i| i.expect_function_matching("calc"))
@@ -25,7 +24,7 @@ pub fn parse_length<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Length> {
     match token {
         Token::Number {
             int_value: Some(n), ..
-        } => Ok(Length::Cells(*n as u16)),
+        } => parse_cells(*n, location),
 
         Token::Number { .. } => integer_required(location),
 
@@ -35,7 +34,7 @@ pub fn parse_length<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Length> {
             int_value: Some(n),
             unit,
             ..
-        } if unit.eq_ignore_ascii_case("c") => Ok(Length::Cells(*n as u16)),
+        } if unit.eq_ignore_ascii_case("c") => parse_cells(*n, location),
 
         Token::Dimension { unit, .. } if unit.eq_ignore_ascii_case("c") => {
             integer_required(location)
@@ -45,6 +44,65 @@ pub fn parse_length<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Length> {
     }
 }
 
+/// Cells are unsigned, layout has no notion of negative offsets. Reject anything
+/// that doesn't fit rather than letting `-2` wrap around to `65534`.
+fn parse_cells<'i>(n: i32, location: SourceLocation) -> ParseResult<'i, Length> {
+    u16::try_from(n).map(Length::Cells).or_else(|_| {
+        error(
+            ParseErrorKind::OutOfRange {
+                value: i64::from(n),
+                min: 0,
+                max: i64::from(u16::MAX),
+            },
+            location,
+        )
+    })
+}
+
+/// Parse a margin side: a length, which unlike anywhere else may have a
+/// minus sign in front of its cells or percentage.
+pub fn parse_margin<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Margin> {
+    let start = input.state();
+    let location = input.current_source_location();
+
+    let negative = match input.next()? {
+        Token::Number {
+            int_value: Some(n), ..
+        } if *n < 0 => Some(negative_cells(*n, location)?),
+        Token::Dimension {
+            int_value: Some(n),
+            unit,
+            ..
+        } if *n < 0 && unit.eq_ignore_ascii_case("c") => Some(negative_cells(*n, location)?),
+        Token::Percentage { unit_value, .. } if *unit_value < 0.0 => {
+            Some(Length::Percent(-*unit_value * 100.0))
+        }
+        _ => None,
+    };
+
+    if let Some(length) = negative {
+        return Ok(Margin::negative(length));
+    }
+
+    input.reset(&start);
+    parse_length(input).map(Margin::from)
+}
+
+fn negative_cells<'i>(n: i32, location: SourceLocation) -> ParseResult<'i, Length> {
+    u16::try_from(n.unsigned_abs())
+        .map(Length::Cells)
+        .or_else(|_| {
+            error(
+                ParseErrorKind::OutOfRange {
+                    value: i64::from(n),
+                    min: -i64::from(u16::MAX),
+                    max: i64::from(u16::MAX),
+                },
+                location,
+            )
+        })
+}
+
 /// Parse a dimension: length, 'auto', or 'none'
 pub fn parse_dimension<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Dimension> {
     if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
@@ -53,6 +111,18 @@ pub fn parse_dimension<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Dimens
     if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
         return Ok(Dimension::None);
     }
+    if input
+        .try_parse(|i| i.expect_ident_matching("min-content"))
+        .is_ok()
+    {
+        return Ok(Dimension::MinContent);
+    }
+    if input
+        .try_parse(|i| i.expect_ident_matching("max-content"))
+        .is_ok()
+    {
+        return Ok(Dimension::MaxContent);
+    }
     parse_length(input).map(Dimension::Length)
 }
 
@@ -227,6 +297,40 @@ mod tests {
         assert_eq!(error_kind(result), Some(ParseErrorKind::IntegerRequired));
     }
 
+    #[test]
+    fn length_negative_rejected() {
+        let result = parse("-2", parse_length);
+        assert_eq!(
+            error_kind(result),
+            Some(ParseErrorKind::OutOfRange {
+                value: -2,
+                min: 0,
+                max: 65535,
+            })
+        );
+
+        let result = parse("-2c", parse_length);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn margin_keeps_negative() {
+        let m = parse("-2", parse_margin).expect("failed");
+        assert_eq!(m, Margin::negative(Length::Cells(2)));
+
+        let m = parse("-2c", parse_margin).expect("failed");
+        assert_eq!(m, Margin::negative(Length::Cells(2)));
+
+        let m = parse("-10%", parse_margin).expect("failed");
+        assert_eq!(m, Margin::negative(Length::Percent(10.0)));
+
+        let m = parse("40000", parse_margin).expect("failed");
+        assert_eq!(m, Margin::from(Length::Cells(40000)));
+
+        let result = parse("-70000", parse_margin);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn dimension_auto() {
         let d = parse("auto", parse_dimension).expect("failed");
@@ -239,6 +343,15 @@ mod tests {
         assert_eq!(d, Dimension::None);
     }
 
+    #[test]
+    fn dimension_intrinsic() {
+        let d = parse("min-content", parse_dimension).expect("failed");
+        assert_eq!(d, Dimension::MinContent);
+
+        let d = parse("max-content", parse_dimension).expect("failed");
+        assert_eq!(d, Dimension::MaxContent);
+    }
+
     #[test]
     fn dimension_length() {
         let d = parse("50%", parse_dimension).expect("failed");
@@ -280,4 +393,34 @@ mod tests {
         };
         assert_eq!(expr.resolve(100), 40);
     }
+
+    #[test]
+    fn calc_add() {
+        let l = parse("calc(2 + 3)", parse_length).expect("failed");
+        assert_eq!(
+            l,
+            Length::Calc(Box::new(CalcExpr::Add(
+                Box::new(CalcExpr::Cells(2)),
+                Box::new(CalcExpr::Cells(3)),
+            )))
+        );
+    }
+
+    #[test]
+    fn calc_in_dimension() {
+        let d = parse("calc(100% - 2)", parse_dimension).expect("failed");
+        let Dimension::Length(Length::Calc(expr)) = d else {
+            panic!("expected calc")
+        };
+        assert_eq!(expr.resolve(50), 48);
+    }
+
+    #[test]
+    fn calc_rejects_bad_operands() {
+        assert!(parse("calc(red + 1)", parse_length).is_err());
+        assert!(parse("calc(1 + )", parse_length).is_err());
+        assert!(parse("calc(1 2)", parse_length).is_err());
+        // Only plain numbers scale a length
+        assert!(parse("calc(10 * 50%)", parse_length).is_err());
+    }
 }
@@ -0,0 +1,139 @@
+use cssparser::{Parser, Token};
+
+use crate::{
+    MediaFeature, MediaQuery, MediaQueryList, ParseErrorKind, ParseResult,
+    parser::{
+        error::{error, expected},
+        parse_integer,
+    },
+};
+
+/// The prelude of an `@media` rule: comma separated queries, each an optional
+/// `all` or `screen` followed by `(feature: value)` conditions joined with
+/// `and`.
+pub fn parse_media_query_list<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, MediaQueryList> {
+    let queries = input.parse_comma_separated(parse_media_query)?;
+
+    Ok(MediaQueryList { queries })
+}
+
+fn parse_media_query<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, MediaQuery> {
+    let mut features = Vec::new();
+
+    let has_type = input
+        .try_parse(|i| {
+            let location = i.current_source_location();
+
+            match i.next()? {
+                Token::Ident(ident)
+                    if ident.eq_ignore_ascii_case("all")
+                        || ident.eq_ignore_ascii_case("screen") =>
+                {
+                    Ok(())
+                }
+                other => expected("'all' or 'screen'", other, location),
+            }
+        })
+        .is_ok();
+
+    if !has_type {
+        features.push(parse_media_feature(input)?);
+    }
+
+    while input.try_parse(|i| i.expect_ident_matching("and")).is_ok() {
+        features.push(parse_media_feature(input)?);
+    }
+
+    Ok(MediaQuery { features })
+}
+
+fn parse_media_feature<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, MediaFeature> {
+    input.expect_parenthesis_block()?;
+
+    input.parse_nested_block(|input| {
+        let location = input.current_source_location();
+        let name = input.expect_ident_cloned()?;
+        input.expect_colon()?;
+        let value = parse_cells(input)?;
+
+        match name.to_ascii_lowercase().as_str() {
+            "width" => Ok(MediaFeature::Width(value)),
+            "min-width" => Ok(MediaFeature::MinWidth(value)),
+            "max-width" => Ok(MediaFeature::MaxWidth(value)),
+            "height" => Ok(MediaFeature::Height(value)),
+            "min-height" => Ok(MediaFeature::MinHeight(value)),
+            "max-height" => Ok(MediaFeature::MaxHeight(value)),
+            _ => error(
+                ParseErrorKind::Expected {
+                    what: "media feature".into(),
+                    got: name.to_string(),
+                },
+                location,
+            ),
+        }
+    })
+}
+
+fn parse_cells<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, u16> {
+    let location = input.current_source_location();
+    let n = parse_integer(input)?;
+
+    u16::try_from(n).or_else(|_| {
+        error(
+            ParseErrorKind::OutOfRange {
+                value: i64::from(n),
+                min: 0,
+                max: i64::from(i16::MAX),
+            },
+            location,
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cssparser::ParserInput;
+
+    use super::*;
+
+    fn parse(s: &str) -> Result<MediaQueryList, String> {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        parse_media_query_list(&mut parser).map_err(|e| format!("{e:?}"))
+    }
+
+    #[test]
+    fn single_feature() {
+        let list = parse("(min-width: 80)").expect("failed");
+        assert_eq!(list.queries.len(), 1);
+        assert_eq!(list.queries[0].features, [MediaFeature::MinWidth(80)]);
+    }
+
+    #[test]
+    fn features_joined_with_and() {
+        let list = parse("screen and (min-width: 40) and (max-height: 30)").expect("failed");
+        assert_eq!(
+            list.queries[0].features,
+            [MediaFeature::MinWidth(40), MediaFeature::MaxHeight(30)]
+        );
+    }
+
+    #[test]
+    fn comma_separated_queries() {
+        let list = parse("(max-width: 40), (min-height: 50)").expect("failed");
+        assert_eq!(list.queries.len(), 2);
+    }
+
+    #[test]
+    fn bare_media_type() {
+        let list = parse("all").expect("failed");
+        assert!(list.queries[0].features.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_features() {
+        assert!(parse("(orientation: landscape)").is_err());
+        assert!(parse("(min-width: -1)").is_err());
+        assert!(parse("print").is_err());
+    }
+}
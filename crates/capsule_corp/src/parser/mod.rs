@@ -1,33 +1,45 @@
 mod color;
 mod declaration;
 mod error;
+mod grid;
 mod keyword;
 mod length;
+mod media;
+mod registration;
 mod rule;
 mod selector;
+mod serialize;
 mod stylesheet;
+mod transition;
 mod unresolved;
 mod value;
 
 use color::parse_color;
 use cssparser::{Parser, Token};
 pub use declaration::{Declaration, parse_inline_style};
+use grid::{parse_grid_placement, parse_grid_template};
 use keyword::{
-    parse_align_items, parse_align_self, parse_border_style, parse_display, parse_flex_direction,
-    parse_flex_wrap, parse_font_style, parse_font_weight, parse_justify_content, parse_overflow,
-    parse_overflow_wrap, parse_text_align, parse_text_decoration, parse_vertical_align,
-    parse_visibility, parse_white_space,
+    parse_align_items, parse_align_self, parse_border_style, parse_cursor, parse_display,
+    parse_flex_direction, parse_flex_wrap, parse_font_style, parse_font_weight,
+    parse_justify_content, parse_overflow, parse_overflow_wrap, parse_position, parse_text_align,
+    parse_text_decoration, parse_text_overflow, parse_vertical_align, parse_visibility,
+    parse_white_space,
 };
-use length::{parse_dimension, parse_length};
+use length::{parse_dimension, parse_length, parse_margin};
+pub use registration::matches_syntax;
 pub use rule::Rule;
 pub use selector::parse_selector;
+use transition::parse_transitions;
 pub use unresolved::parse_value_with_vars;
 pub use value::parse_property_value;
 
-pub use error::{ParseErrorKind, ParseResult};
+pub use error::{ParseDiagnostic, ParseErrorKind, ParseResult};
 pub use stylesheet::*;
 
-use crate::parser::error::expected;
+use crate::{
+    AspectRatio, BoxShadow, Color, ZIndex,
+    parser::error::{error, expected},
+};
 
 fn parse_number<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, f32> {
     let location = input.current_source_location();
@@ -39,6 +51,85 @@ fn parse_number<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, f32> {
     }
 }
 
+/// Parse a single quoted character, or `none`
+fn parse_char<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Option<char>> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+
+    match token {
+        Token::Ident(ident) if ident.eq_ignore_ascii_case("none") => Ok(None),
+        Token::QuotedString(s) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Some(c)),
+                _ => expected("single character", token, location),
+            }
+        }
+        other => expected("single character or 'none'", other, location),
+    }
+}
+
+/// `auto`, or a ratio as `<width> [/ <height>]`. A zero on either side is
+/// degenerate and behaves like `auto`.
+fn parse_aspect_ratio<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, AspectRatio> {
+    if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+        return Ok(AspectRatio::Auto);
+    }
+
+    let location = input.current_source_location();
+    let width = parse_number(input)?;
+    let height = if input.try_parse(|i| i.expect_delim('/')).is_ok() {
+        parse_number(input)?
+    } else {
+        1.0
+    };
+
+    if width < 0.0 || height < 0.0 {
+        return error(
+            ParseErrorKind::Expected {
+                what: "non-negative ratio".into(),
+                got: format!("{width} / {height}"),
+            },
+            location,
+        );
+    }
+
+    if width == 0.0 || height == 0.0 {
+        return Ok(AspectRatio::Auto);
+    }
+
+    Ok(AspectRatio::Ratio(width / height))
+}
+
+/// `none`, or `<offset-x> <offset-y>` in cells with an optional color on
+/// either side. The color defaults to black.
+fn parse_box_shadow<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Option<BoxShadow>> {
+    if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        return Ok(None);
+    }
+
+    let leading = input.try_parse(parse_color).ok();
+    let offset_x = parse_integer(input)?;
+    let offset_y = parse_integer(input)?;
+    let color = leading
+        .or_else(|| input.try_parse(parse_color).ok())
+        .unwrap_or(Color::BLACK);
+
+    Ok(Some(BoxShadow::new(offset_x, offset_y, color)))
+}
+
+/// A number, clamped to `0.0..=1.0` like browsers do.
+fn parse_opacity<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, f32> {
+    parse_number(input).map(|opacity| opacity.clamp(0.0, 1.0))
+}
+
+fn parse_z_index<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, ZIndex> {
+    if input.try_parse(|i| i.expect_ident_matching("auto")).is_ok() {
+        return Ok(ZIndex::Auto);
+    }
+    parse_integer(input).map(ZIndex::Integer)
+}
+
 #[allow(clippy::cast_possible_truncation)]
 fn parse_integer<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, i16> {
     let location = input.current_source_location();
@@ -0,0 +1,202 @@
+use cssparser::{Delimiter, Parser, ParserInput, Token};
+use ginyu_force::Pose;
+
+use crate::{
+    ParseErrorKind, ParseResult, PropertyRegistration, PropertySyntax,
+    parser::{
+        color::parse_color,
+        error::{error, expected},
+        length::parse_length,
+        parse_integer, parse_number,
+    },
+};
+
+/// Parse the `--name` prelude of an `@property` rule.
+pub fn parse_property_name<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Pose> {
+    let location = input.current_source_location();
+    let token = input.next()?;
+
+    match token {
+        Token::Ident(ident) if ident.starts_with("--") => Ok(Pose::from(&ident[2..])),
+        other => expected("custom property name", other, location),
+    }
+}
+
+/// Parse the descriptors of an `@property` rule. `syntax` and `inherits`
+/// are required, and so is `initial-value` unless the syntax is `"*"`.
+pub fn parse_property_registration<'i>(
+    name: Pose,
+    input: &mut Parser<'i, '_>,
+) -> ParseResult<'i, PropertyRegistration> {
+    let location = input.current_source_location();
+
+    let mut syntax = None;
+    let mut inherits = None;
+    let mut initial_value = None;
+
+    while !input.is_exhausted() {
+        let descriptor = input.expect_ident_cloned()?;
+        input.expect_colon()?;
+
+        input.parse_until_after(Delimiter::Semicolon, |input| {
+            match &*descriptor {
+                "syntax" => syntax = Some(parse_syntax(input)?),
+                "inherits" => inherits = Some(parse_inherits(input)?),
+                "initial-value" => {
+                    let start = input.position();
+                    while input.next().is_ok() {}
+                    initial_value = Some(input.slice_from(start).trim().to_owned());
+                }
+                _ => {
+                    return error(
+                        ParseErrorKind::UnknownProperty(descriptor.to_string()),
+                        location,
+                    );
+                }
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let (Some(syntax), Some(inherits)) = (syntax, inherits) else {
+        return error(ParseErrorKind::UnexpectedEof, location);
+    };
+
+    if syntax != PropertySyntax::Universal {
+        match &initial_value {
+            Some(value) if matches_syntax(syntax, value) => {}
+            _ => return error(ParseErrorKind::InvalidVariable, location),
+        }
+    }
+
+    Ok(PropertyRegistration {
+        name,
+        syntax,
+        inherits,
+        initial_value,
+    })
+}
+
+fn parse_syntax<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, PropertySyntax> {
+    let location = input.current_source_location();
+    let syntax = input.expect_string_cloned()?;
+
+    match syntax.trim() {
+        "*" => Ok(PropertySyntax::Universal),
+        "<color>" => Ok(PropertySyntax::Color),
+        "<length>" => Ok(PropertySyntax::Length),
+        "<number>" => Ok(PropertySyntax::Number),
+        "<integer>" => Ok(PropertySyntax::Integer),
+        other => error(
+            ParseErrorKind::UnknownKeyword {
+                keyword: other.to_owned(),
+                property: "syntax",
+            },
+            location,
+        ),
+    }
+}
+
+fn parse_inherits<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, bool> {
+    let location = input.current_source_location();
+    let ident = input.expect_ident_cloned()?;
+
+    match &*ident {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        other => error(
+            ParseErrorKind::UnknownKeyword {
+                keyword: other.to_owned(),
+                property: "inherits",
+            },
+            location,
+        ),
+    }
+}
+
+/// Whether `value` is a valid value for a property registered with `syntax`.
+pub fn matches_syntax(syntax: PropertySyntax, value: &str) -> bool {
+    let mut input = ParserInput::new(value);
+    let mut parser = Parser::new(&mut input);
+
+    let parsed = parser.parse_entirely(|input| match syntax {
+        PropertySyntax::Universal => {
+            while input.next().is_ok() {}
+            Ok(())
+        }
+        PropertySyntax::Color => parse_color(input).map(drop),
+        PropertySyntax::Length => parse_length(input).map(drop),
+        PropertySyntax::Number => parse_number(input).map(drop),
+        PropertySyntax::Integer => parse_integer(input).map(drop),
+    });
+
+    parsed.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(name: &str, css: &str) -> Result<PropertyRegistration, String> {
+        let mut input = ParserInput::new(css);
+        let mut parser = Parser::new(&mut input);
+        parse_property_registration(Pose::from(name), &mut parser).map_err(|e| format!("{e:?}"))
+    }
+
+    #[test]
+    fn registration() {
+        let registration = parse(
+            "accent",
+            r#"syntax: "<color>"; inherits: false; initial-value: red"#,
+        )
+        .expect("failed");
+
+        assert_eq!(registration.name, Pose::from("accent"));
+        assert_eq!(registration.syntax, PropertySyntax::Color);
+        assert!(!registration.inherits);
+        assert_eq!(registration.initial_value.as_deref(), Some("red"));
+    }
+
+    #[test]
+    fn universal_needs_no_initial_value() {
+        let registration = parse("anything", r#"syntax: "*"; inherits: true"#).expect("failed");
+
+        assert_eq!(registration.syntax, PropertySyntax::Universal);
+        assert_eq!(registration.initial_value, None);
+    }
+
+    #[test]
+    fn invalid_registrations() {
+        // Missing inherits
+        assert!(parse("x", r#"syntax: "<color>"; initial-value: red"#).is_err());
+        // Initial value doesn't match the syntax
+        assert!(
+            parse(
+                "x",
+                r#"syntax: "<integer>"; inherits: true; initial-value: red"#
+            )
+            .is_err()
+        );
+        // Typed syntax without an initial value
+        assert!(parse("x", r#"syntax: "<length>"; inherits: true"#).is_err());
+        assert!(
+            parse(
+                "x",
+                r#"syntax: "<angle>"; inherits: true; initial-value: 1"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn syntax_matching() {
+        assert!(matches_syntax(PropertySyntax::Color, "#ff0000"));
+        assert!(!matches_syntax(PropertySyntax::Color, "12"));
+        assert!(matches_syntax(PropertySyntax::Length, "50%"));
+        assert!(matches_syntax(PropertySyntax::Integer, "3"));
+        assert!(!matches_syntax(PropertySyntax::Integer, "3 4"));
+        assert!(matches_syntax(PropertySyntax::Number, "0.5"));
+        assert!(matches_syntax(PropertySyntax::Universal, "anything at all"));
+    }
+}
@@ -1,22 +1,31 @@
 use cssparser::{
-    AtRuleParser, CowRcStr, DeclarationParser, Parser, ParserState, QualifiedRuleParser,
-    RuleBodyItemParser, RuleBodyParser,
+    AtRuleParser, BasicParseErrorKind, CowRcStr, DeclarationParser, Parser, ParserState,
+    QualifiedRuleParser, RuleBodyItemParser, RuleBodyParser, StyleSheetParser,
 };
 use selectors::SelectorList;
 
+use ginyu_force::Pose;
+
 use crate::{
-    ParseErrorKind, ParseResult, Selectors,
+    MediaQueryList, ParseDiagnostic, ParseErrorKind, ParseResult, PropertyRegistration, Selectors,
     parser::{
         declaration::{Declaration, parse_declaration},
+        error::error,
+        media::parse_media_query_list,
+        registration::{parse_property_name, parse_property_registration},
         selector::{parse_selector, parse_selector_for_nesting},
     },
 };
 
 #[derive(Clone, Debug)]
+#[allow(clippy::use_self)]
 pub struct Rule {
     pub selectors: SelectorList<Selectors>,
     pub declarations: Vec<Declaration>,
     pub nested_rules: Vec<Rule>,
+    /// Queries of the `@media` blocks the rule sits in, every one of which
+    /// has to match for it to apply.
+    pub media: Vec<MediaQueryList>,
 }
 
 impl Rule {
@@ -25,16 +34,37 @@ impl Rule {
             selectors,
             declarations,
             nested_rules: Vec::new(),
+            media: Vec::new(),
         }
     }
 }
 
+/// A top-level item in a stylesheet. `@media` blocks flatten into the rules
+/// they contain.
+pub enum TopLevelItem {
+    Rule(Rule),
+    Media(Vec<Rule>),
+    Property(PropertyRegistration),
+    Import(String),
+}
+
+pub enum AtRulePrelude {
+    Media(MediaQueryList),
+    Property(Pose),
+    Import(String),
+}
+
 enum RuleBodyItem {
     Declarations(Vec<Declaration>),
     NestedRule(Rule),
 }
 
-struct RuleParser;
+/// Parses the body of a style rule, keeping hold of what went wrong in
+/// nested rules.
+#[derive(Default)]
+struct RuleParser {
+    errors: Vec<ParseDiagnostic>,
+}
 
 impl<'i> DeclarationParser<'i> for RuleParser {
     type Declaration = RuleBodyItem;
@@ -72,7 +102,7 @@ impl<'i> QualifiedRuleParser<'i> for RuleParser {
         _start: &ParserState,
         input: &mut Parser<'i, 't>,
     ) -> ParseResult<'i, Self::QualifiedRule> {
-        let rule = parse_rule_body(prelude, input);
+        let rule = parse_rule_body(prelude, input, &mut self.errors);
 
         Ok(RuleBodyItem::NestedRule(rule))
     }
@@ -88,17 +118,109 @@ impl RuleBodyItemParser<'_, RuleBodyItem, ParseErrorKind> for RuleParser {
     }
 }
 
-pub struct TopLevelRuleParser;
+/// Parses the rules of a stylesheet. Anything invalid is skipped, and what
+/// went wrong ends up in `errors`.
+#[derive(Default)]
+pub struct TopLevelRuleParser {
+    pub errors: Vec<ParseDiagnostic>,
+    /// `@import`s are only allowed before any other rule
+    rules_seen: bool,
+}
 
-impl AtRuleParser<'_> for TopLevelRuleParser {
-    type Prelude = ();
-    type AtRule = Rule;
+impl<'i> AtRuleParser<'i> for TopLevelRuleParser {
+    type Prelude = AtRulePrelude;
+    type AtRule = TopLevelItem;
     type Error = ParseErrorKind;
+
+    fn parse_prelude<'t>(
+        &mut self,
+        name: CowRcStr<'i>,
+        input: &mut Parser<'i, 't>,
+    ) -> ParseResult<'i, Self::Prelude> {
+        if name.eq_ignore_ascii_case("import") {
+            if self.rules_seen {
+                return error(
+                    ParseErrorKind::MisplacedImport,
+                    input.current_source_location(),
+                );
+            }
+
+            let url = input.expect_url_or_string()?;
+            return Ok(AtRulePrelude::Import(url.to_string()));
+        }
+
+        if name.eq_ignore_ascii_case("media") {
+            return parse_media_query_list(input).map(AtRulePrelude::Media);
+        }
+
+        if name.eq_ignore_ascii_case("property") {
+            return parse_property_name(input).map(AtRulePrelude::Property);
+        }
+
+        Err(input.new_error(BasicParseErrorKind::AtRuleInvalid(name)))
+    }
+
+    fn parse_block<'t>(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &ParserState,
+        input: &mut Parser<'i, 't>,
+    ) -> ParseResult<'i, Self::AtRule> {
+        self.rules_seen = true;
+
+        let prelude = match prelude {
+            AtRulePrelude::Media(prelude) => prelude,
+            AtRulePrelude::Property(name) => {
+                return parse_property_registration(name, input).map(TopLevelItem::Property);
+            }
+            AtRulePrelude::Import(_) => {
+                return Err(input.new_error(BasicParseErrorKind::AtRuleBodyInvalid));
+            }
+        };
+
+        let mut rules = Vec::new();
+        let mut errors = Vec::new();
+        // Nothing can be imported from inside a block
+        let mut parser = Self {
+            rules_seen: true,
+            ..Self::default()
+        };
+
+        for item in StyleSheetParser::new(input, &mut parser) {
+            match item {
+                Ok(TopLevelItem::Rule(rule)) => rules.push(rule),
+                Ok(TopLevelItem::Media(nested)) => rules.extend(nested),
+                // Registrations are global, so they're ignored inside `@media`
+                Ok(TopLevelItem::Property(_) | TopLevelItem::Import(_)) => {}
+                Err((err, _slice)) => errors.push(ParseDiagnostic::from(err)),
+            }
+        }
+
+        self.errors.extend(parser.errors);
+        self.errors.extend(errors);
+
+        for rule in &mut rules {
+            rule.media.push(prelude.clone());
+        }
+
+        Ok(TopLevelItem::Media(rules))
+    }
+
+    fn rule_without_block(
+        &mut self,
+        prelude: Self::Prelude,
+        _start: &ParserState,
+    ) -> Result<Self::AtRule, ()> {
+        match prelude {
+            AtRulePrelude::Import(url) => Ok(TopLevelItem::Import(url)),
+            AtRulePrelude::Media(_) | AtRulePrelude::Property(_) => Err(()),
+        }
+    }
 }
 
 impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
     type Prelude = SelectorList<Selectors>;
-    type QualifiedRule = Rule;
+    type QualifiedRule = TopLevelItem;
     type Error = ParseErrorKind;
 
     fn parse_prelude<'t>(&mut self, input: &mut Parser<'i, 't>) -> ParseResult<'i, Self::Prelude> {
@@ -111,15 +233,27 @@ impl<'i> QualifiedRuleParser<'i> for TopLevelRuleParser {
         _start: &ParserState,
         input: &mut Parser<'i, 't>,
     ) -> ParseResult<'i, Self::QualifiedRule> {
-        Ok(parse_rule_body(prelude, input))
+        self.rules_seen = true;
+
+        Ok(TopLevelItem::Rule(parse_rule_body(
+            prelude,
+            input,
+            &mut self.errors,
+        )))
     }
 }
 
-fn parse_rule_body(selectors: SelectorList<Selectors>, input: &mut Parser<'_, '_>) -> Rule {
+/// Parse declarations and nested rules, skipping past anything invalid to
+/// the next `;` or `}`.
+fn parse_rule_body(
+    selectors: SelectorList<Selectors>,
+    input: &mut Parser<'_, '_>,
+    errors: &mut Vec<ParseDiagnostic>,
+) -> Rule {
     let mut declarations = Vec::new();
     let mut nested_rules = Vec::new();
 
-    let mut parser = RuleParser;
+    let mut parser = RuleParser::default();
     let items = RuleBodyParser::new(input, &mut parser);
 
     for result in items {
@@ -130,16 +264,17 @@ fn parse_rule_body(selectors: SelectorList<Selectors>, input: &mut Parser<'_, '_
             Ok(RuleBodyItem::NestedRule(rule)) => {
                 nested_rules.push(rule);
             }
-            Err((_err, _slice)) => {
-                // eprintln!("skipping invalid rule body item: {:?}", err);
-            }
+            Err((err, _slice)) => errors.push(ParseDiagnostic::from(err)),
         }
     }
 
+    errors.append(&mut parser.errors);
+
     Rule {
         selectors,
         declarations,
         nested_rules,
+        media: Vec::new(),
     }
 }
 
@@ -153,11 +288,14 @@ mod tests {
     fn parse(s: &str) -> Result<Rule, String> {
         let mut input = ParserInput::new(s);
         let mut parser = Parser::new(&mut input);
-        let mut rule_parser = TopLevelRuleParser;
+        let mut rule_parser = TopLevelRuleParser::default();
 
         let mut iter = StyleSheetParser::new(&mut parser, &mut rule_parser);
         match iter.next() {
-            Some(Ok(rule)) => Ok(rule),
+            Some(Ok(TopLevelItem::Rule(rule))) => Ok(rule),
+            Some(Ok(
+                TopLevelItem::Media(_) | TopLevelItem::Property(_) | TopLevelItem::Import(_),
+            )) => Err("expected a style rule".to_string()),
             Some(Err((e, _))) => Err(format!("{e:?}")),
             None => Err("no rule found".to_string()),
         }
@@ -70,6 +70,10 @@ mod tests {
         assert!(parse(".foo:hover").is_ok());
         assert!(parse(":first-child").is_ok());
         assert!(parse(":nth-child(2)").is_ok());
+        assert!(parse(":nth-child(2n + 1)").is_ok());
+        assert!(parse("li:nth-of-type(odd)").is_ok());
+        assert!(parse(".btn:not(.active)").is_ok());
+        assert!(parse(":not(.a, #b)").is_ok());
     }
 
     #[test]
@@ -0,0 +1,470 @@
+use std::fmt::{self, Write};
+
+use cssparser::{ToCss, serialize_string};
+
+use crate::{
+    AspectRatio, BoxShadow, CalcExpr, Color, CustomValue, Dimension, GridLine, GridPlacement,
+    Length, Margin, MediaFeature, MediaQuery, MediaQueryList, PropertyRegistration, PropertySyntax,
+    Stylesheet, TrackSize, Transition, TransitionProperty, Value, ZIndex,
+    parser::{Declaration, Rule},
+};
+
+const INDENT: &str = "    ";
+
+impl Stylesheet {
+    /// Write the stylesheet back out as CSS. Shorthands come out as the
+    /// longhands they expanded to, and rules inside `@media` blocks each get
+    /// a block of their own.
+    #[must_use]
+    pub fn to_css(&self) -> String {
+        let mut css = String::new();
+
+        for url in &self.imports {
+            css.push_str("@import ");
+            // Writing to a `String` can't fail
+            let _ = serialize_string(url, &mut css);
+            css.push_str(";\n");
+        }
+
+        for registration in &self.properties {
+            let _ = registration.to_css(&mut css);
+        }
+
+        for rule in &self.rules {
+            let _ = write_top_level_rule(rule, &mut css);
+        }
+
+        css
+    }
+}
+
+fn write_top_level_rule<W: Write>(rule: &Rule, dest: &mut W) -> fmt::Result {
+    for (depth, media) in rule.media.iter().enumerate() {
+        indent(dest, depth)?;
+        dest.write_str("@media ")?;
+        media.to_css(dest)?;
+        dest.write_str(" {\n")?;
+    }
+
+    write_rule(rule, dest, rule.media.len())?;
+
+    for depth in (0..rule.media.len()).rev() {
+        indent(dest, depth)?;
+        dest.write_str("}\n")?;
+    }
+
+    Ok(())
+}
+
+fn write_rule<W: Write>(rule: &Rule, dest: &mut W, depth: usize) -> fmt::Result {
+    indent(dest, depth)?;
+    rule.selectors.to_css(dest)?;
+    dest.write_str(" {\n")?;
+
+    for declaration in &rule.declarations {
+        indent(dest, depth + 1)?;
+        declaration.to_css(dest)?;
+        dest.write_str(";\n")?;
+    }
+
+    for nested in &rule.nested_rules {
+        write_rule(nested, dest, depth + 1)?;
+    }
+
+    indent(dest, depth)?;
+    dest.write_str("}\n")
+}
+
+fn indent<W: Write>(dest: &mut W, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        dest.write_str(INDENT)?;
+    }
+
+    Ok(())
+}
+
+impl ToCss for PropertyRegistration {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        let syntax = match self.syntax {
+            PropertySyntax::Universal => "*",
+            PropertySyntax::Color => "<color>",
+            PropertySyntax::Length => "<length>",
+            PropertySyntax::Number => "<number>",
+            PropertySyntax::Integer => "<integer>",
+        };
+
+        write!(dest, "@property --{} {{\n{INDENT}syntax: ", self.name)?;
+        serialize_string(syntax, dest)?;
+        write!(dest, ";\n{INDENT}inherits: {};\n", self.inherits)?;
+
+        if let Some(initial_value) = &self.initial_value {
+            writeln!(dest, "{INDENT}initial-value: {initial_value};")?;
+        }
+
+        dest.write_str("}\n")
+    }
+}
+
+impl ToCss for MediaQueryList {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        for (i, query) in self.queries.iter().enumerate() {
+            if i > 0 {
+                dest.write_str(", ")?;
+            }
+
+            query.to_css(dest)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToCss for MediaQuery {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        if self.features.is_empty() {
+            return dest.write_str("all");
+        }
+
+        for (i, feature) in self.features.iter().enumerate() {
+            if i > 0 {
+                dest.write_str(" and ")?;
+            }
+
+            feature.to_css(dest)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToCss for MediaFeature {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        let (name, value) = match *self {
+            Self::Width(value) => ("width", value),
+            Self::MinWidth(value) => ("min-width", value),
+            Self::MaxWidth(value) => ("max-width", value),
+            Self::Height(value) => ("height", value),
+            Self::MinHeight(value) => ("min-height", value),
+            Self::MaxHeight(value) => ("max-height", value),
+        };
+
+        write!(dest, "({name}: {value})")
+    }
+}
+
+impl ToCss for Declaration {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        write!(dest, "{}: ", self.property.to_name())?;
+        self.value.to_css(dest)?;
+
+        if self.important {
+            dest.write_str(" !important")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToCss for Value {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Display(v) => dest.write_str(v.to_name()),
+            Self::Position(v) => dest.write_str(v.to_name()),
+            Self::FlexDirection(v) => dest.write_str(v.to_name()),
+            Self::FlexWrap(v) => dest.write_str(v.to_name()),
+            Self::JustifyContent(v) => dest.write_str(v.to_name()),
+            Self::AlignItems(v) => dest.write_str(v.to_name()),
+            Self::AlignSelf(v) => dest.write_str(v.to_name()),
+            Self::AlignContent(v) => dest.write_str(v.to_name()),
+            Self::FontWeight(v) => dest.write_str(v.to_name()),
+            Self::FontStyle(v) => dest.write_str(v.to_name()),
+            Self::TextDecoration(v) => dest.write_str(v.to_name()),
+            Self::TextAlign(v) => dest.write_str(v.to_name()),
+            Self::VerticalAlign(v) => dest.write_str(v.to_name()),
+            Self::WhiteSpace(v) => dest.write_str(v.to_name()),
+            Self::OverflowWrap(v) => dest.write_str(v.to_name()),
+            Self::TextOverflow(v) => dest.write_str(v.to_name()),
+            Self::Overflow(v) => dest.write_str(v.to_name()),
+            Self::Visibility(v) => dest.write_str(v.to_name()),
+            Self::BorderStyle(v) => dest.write_str(v.to_name()),
+            Self::Cursor(v) => dest.write_str(v.to_name()),
+            Self::ZIndex(ZIndex::Auto) | Self::AspectRatio(AspectRatio::Auto) => {
+                dest.write_str("auto")
+            }
+            Self::ZIndex(ZIndex::Integer(n)) | Self::Integer(n) => write!(dest, "{n}"),
+            Self::AspectRatio(AspectRatio::Ratio(ratio)) => write!(dest, "{ratio}"),
+            Self::GridTemplate(tracks) if tracks.is_empty() => dest.write_str("none"),
+            Self::GridTemplate(tracks) => write_separated(tracks, " ", dest),
+            Self::GridPlacement(placement) => placement.to_css(dest),
+            Self::BoxShadow(None) | Self::Char(None) => dest.write_str("none"),
+            Self::BoxShadow(Some(shadow)) => shadow.to_css(dest),
+            Self::Transitions(transitions) if transitions.is_empty() => dest.write_str("none"),
+            Self::Transitions(transitions) => write_separated(transitions, ", ", dest),
+            Self::Length(length) => length.to_css(dest),
+            Self::Margin(margin) => margin.to_css(dest),
+            Self::Dimension(dimension) => dimension.to_css(dest),
+            Self::Color(color) => color.to_css(dest),
+            Self::Number(n) => write!(dest, "{n}"),
+            Self::Char(Some(c)) => serialize_string(c.encode_utf8(&mut [0; 4]), dest),
+            Self::Inherit => dest.write_str("inherit"),
+            Self::Initial => dest.write_str("initial"),
+            Self::Unset => dest.write_str("unset"),
+            Self::Unresolved(unresolved) => dest.write_str(&unresolved.css),
+            Self::Custom(custom) => custom.to_css(dest),
+        }
+    }
+}
+
+fn write_separated<T: ToCss, W: Write>(items: &[T], separator: &str, dest: &mut W) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            dest.write_str(separator)?;
+        }
+
+        item.to_css(dest)?;
+    }
+
+    Ok(())
+}
+
+impl ToCss for CustomValue {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Resolved(css) => dest.write_str(css),
+            Self::Unresolved(unresolved) => dest.write_str(&unresolved.css),
+            Self::Inherit => dest.write_str("inherit"),
+            Self::Initial => dest.write_str("initial"),
+        }
+    }
+}
+
+impl ToCss for Color {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Reset => dest.write_str("reset"),
+            Self::Basic(color) => dest.write_str(color.name()),
+            Self::Bright(color) => write!(dest, "bright-{}", color.name()),
+            Self::Ansi(n) => write!(dest, "ansi({n})"),
+            Self::Rgb(r, g, b) => write!(dest, "#{r:02x}{g:02x}{b:02x}"),
+        }
+    }
+}
+
+impl ToCss for Length {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Cells(n) => write!(dest, "{n}"),
+            Self::Percent(p) => write!(dest, "{p}%"),
+            Self::Calc(expr) => {
+                dest.write_str("calc(")?;
+                expr.to_css(dest)?;
+                dest.write_char(')')
+            }
+        }
+    }
+}
+
+impl ToCss for Margin {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        if self.negative {
+            dest.write_char('-')?;
+        }
+
+        self.length.to_css(dest)
+    }
+}
+
+impl ToCss for CalcExpr {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Cells(n) => write!(dest, "{n}"),
+            Self::Percent(p) => write!(dest, "{p}%"),
+            Self::Add(a, b) => {
+                a.to_css(dest)?;
+                dest.write_str(" + ")?;
+                write_calc_operand(b, dest)
+            }
+            Self::Sub(a, b) => {
+                a.to_css(dest)?;
+                dest.write_str(" - ")?;
+                write_calc_operand(b, dest)
+            }
+            Self::Mult(a, n) => {
+                write_calc_operand(a, dest)?;
+                write!(dest, " * {n}")
+            }
+            Self::Div(a, n) => {
+                write_calc_operand(a, dest)?;
+                write!(dest, " / {n}")
+            }
+        }
+    }
+}
+
+/// Sums are parenthesized wherever leaving them bare would parse back
+/// with different precedence.
+fn write_calc_operand<W: Write>(expr: &CalcExpr, dest: &mut W) -> fmt::Result {
+    if matches!(expr, CalcExpr::Add(..) | CalcExpr::Sub(..)) {
+        dest.write_char('(')?;
+        expr.to_css(dest)?;
+        dest.write_char(')')
+    } else {
+        expr.to_css(dest)
+    }
+}
+
+impl ToCss for Dimension {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Auto => dest.write_str("auto"),
+            Self::None => dest.write_str("none"),
+            Self::MinContent => dest.write_str("min-content"),
+            Self::MaxContent => dest.write_str("max-content"),
+            Self::Length(length) => length.to_css(dest),
+        }
+    }
+}
+
+impl ToCss for TrackSize {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Length(length) => length.to_css(dest),
+            Self::Fraction(fraction) => write!(dest, "{fraction}fr"),
+            Self::Auto => dest.write_str("auto"),
+        }
+    }
+}
+
+impl ToCss for GridPlacement {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        self.start.to_css(dest)?;
+        dest.write_str(" / ")?;
+        self.end.to_css(dest)
+    }
+}
+
+impl ToCss for GridLine {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self {
+            Self::Auto => dest.write_str("auto"),
+            Self::Line(n) => write!(dest, "{n}"),
+            Self::Span(n) => write!(dest, "span {n}"),
+        }
+    }
+}
+
+impl ToCss for BoxShadow {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        write!(dest, "{} {} ", self.offset_x, self.offset_y)?;
+        self.color.to_css(dest)
+    }
+}
+
+impl ToCss for Transition {
+    fn to_css<W: Write>(&self, dest: &mut W) -> fmt::Result {
+        match self.property {
+            TransitionProperty::All => dest.write_str("all")?,
+            TransitionProperty::Property(property) => dest.write_str(&property.to_name())?,
+        }
+
+        write!(dest, " {}ms", self.duration.as_secs_f64() * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STYLESHEET: &str = r#"
+        @import "base.css";
+        @property --accent { syntax: "<color>"; inherits: false; initial-value: red }
+
+        :root { --gap: 2; --fg: var(--accent, cyan) }
+
+        .card, #main > .panel:hover {
+            display: flex;
+            margin: 1 2 3;
+            width: calc((100% - 4) / 2);
+            max-height: max-content;
+            border: rounded #ff8800;
+            color: bright-blue !important;
+            background-char: "░";
+            grid-template-columns: 10 1fr auto;
+            grid-column: 2 / span 3;
+            box-shadow: 1 -1 ansi(8);
+            transition: color 200ms, opacity 1.5s;
+            padding: var(--gap);
+
+            .title { font-weight: bold }
+            &:focus { outline: solid }
+        }
+
+        @media (min-width: 80) and (max-height: 40), screen {
+            .wide { flex-basis: 50%; z-index: -1; aspect-ratio: 2 }
+        }
+    "#;
+
+    fn assert_equivalent(declarations: &[Declaration], original: &[Declaration]) {
+        assert_eq!(declarations.len(), original.len());
+
+        for (declaration, original) in declarations.iter().zip(original) {
+            match original.value {
+                // Token types remember the whitespace around the original
+                Value::Unresolved(_) | Value::Custom(_) => {
+                    assert_eq!(declaration.to_css_string(), original.to_css_string());
+                }
+                _ => assert_eq!(declaration, original),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let stylesheet = Stylesheet::parse(STYLESHEET).expect("failed");
+        let css = stylesheet.to_css();
+        let reparsed = Stylesheet::parse(&css).expect("failed");
+
+        assert_eq!(reparsed.imports, stylesheet.imports);
+        assert_eq!(reparsed.properties, stylesheet.properties);
+        assert_eq!(reparsed.rules.len(), stylesheet.rules.len());
+
+        for (rule, original) in reparsed.rules.iter().zip(&stylesheet.rules) {
+            assert_eq!(rule.media, original.media);
+            assert_equivalent(&rule.declarations, &original.declarations);
+            assert_eq!(rule.nested_rules.len(), original.nested_rules.len());
+
+            for (nested, original) in rule.nested_rules.iter().zip(&original.nested_rules) {
+                assert_equivalent(&nested.declarations, &original.declarations);
+                assert_eq!(
+                    nested.selectors.to_css_string(),
+                    original.selectors.to_css_string()
+                );
+            }
+        }
+
+        // Normalized output is stable
+        assert_eq!(reparsed.to_css(), css);
+    }
+
+    #[test]
+    fn serializes_declarations() {
+        let stylesheet =
+            Stylesheet::parse(".a { color: red !important; --x: 1 2; width: calc(10 - (2 + 3)) }")
+                .expect("failed");
+
+        assert_eq!(
+            stylesheet.to_css(),
+            ".a {\n    color: red !important;\n    --x: 1 2;\n    width: calc(10 - (2 + 3));\n}\n"
+        );
+    }
+
+    #[test]
+    fn nested_rules_use_ampersand() {
+        let stylesheet = Stylesheet::parse(".a { &:hover { color: red } }").expect("failed");
+
+        assert_eq!(
+            stylesheet.to_css(),
+            ".a {\n    &:hover {\n        color: red;\n    }\n}\n"
+        );
+    }
+}
@@ -1,13 +1,18 @@
 use cssparser::{Parser, ParserInput, StyleSheetParser};
 
 use crate::{
-    ParseResult,
-    parser::rule::{Rule, TopLevelRuleParser},
+    ParseDiagnostic, ParseResult, PropertyRegistration,
+    parser::rule::{Rule, TopLevelItem, TopLevelRuleParser},
 };
 
 #[derive(Debug, Clone, Default)]
 pub struct Stylesheet {
     pub rules: Vec<Rule>,
+    /// Custom properties registered with `@property`
+    pub properties: Vec<PropertyRegistration>,
+    /// `@import`s that haven't been resolved, see
+    /// [`Stylesheet::parse_with_resolver`]
+    pub imports: Vec<String>,
 }
 
 impl Stylesheet {
@@ -22,32 +27,125 @@ impl Stylesheet {
 
         parse_stylesheet(&mut parser)
     }
+
+    /// Append `other` after this sheet, so its rules win ties in the
+    /// cascade.
+    pub fn merge(&mut self, mut other: Self) {
+        self.rules.append(&mut other.rules);
+        self.properties.append(&mut other.properties);
+        self.imports.append(&mut other.imports);
+    }
+
+    pub const fn rules_mut(&mut self) -> &mut Vec<Rule> {
+        &mut self.rules
+    }
+
+    /// Parse `source`, also returning what was wrong with the rules and
+    /// declarations that got skipped, in source order.
+    #[must_use]
+    pub fn parse_with_errors(source: &str) -> (Self, Vec<ParseDiagnostic>) {
+        let mut input = ParserInput::new(source);
+        let mut parser = Parser::new(&mut input);
+
+        parse_stylesheet_with_errors(&mut parser)
+    }
+
+    /// Parse `source`, asking `resolver` for the contents of each file it
+    /// `@import`s. Imported rules come before the sheet's own, as though
+    /// pasted in where the `@import` was. Imports that would cycle back to a
+    /// file already being imported are skipped, and ones `resolver` can't
+    /// supply are left in `imports`.
+    pub fn parse_with_resolver(
+        source: &str,
+        mut resolver: impl FnMut(&str) -> Option<String>,
+    ) -> ParseResult<'_, Self> {
+        let mut stylesheet = Self::parse(source)?;
+        stylesheet.resolve_imports(&mut resolver, &mut Vec::new());
+
+        Ok(stylesheet)
+    }
+
+    fn resolve_imports(
+        &mut self,
+        resolver: &mut impl FnMut(&str) -> Option<String>,
+        importing: &mut Vec<String>,
+    ) {
+        let mut rules = Vec::new();
+        let mut properties = Vec::new();
+        let mut unresolved = Vec::new();
+
+        for url in std::mem::take(&mut self.imports) {
+            if importing.contains(&url) {
+                continue;
+            }
+
+            let Some(source) = resolver(&url) else {
+                unresolved.push(url);
+                continue;
+            };
+
+            let (mut imported, _errors) = Self::parse_with_errors(&source);
+
+            importing.push(url);
+            imported.resolve_imports(resolver, importing);
+            importing.pop();
+
+            rules.append(&mut imported.rules);
+            properties.append(&mut imported.properties);
+            unresolved.append(&mut imported.imports);
+        }
+
+        rules.append(&mut self.rules);
+        properties.append(&mut self.properties);
+
+        self.rules = rules;
+        self.properties = properties;
+        self.imports = unresolved;
+    }
 }
 
 pub fn parse_stylesheet<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Stylesheet> {
+    let (stylesheet, _errors) = parse_stylesheet_with_errors(input);
+
+    Ok(stylesheet)
+}
+
+fn parse_stylesheet_with_errors(input: &mut Parser<'_, '_>) -> (Stylesheet, Vec<ParseDiagnostic>) {
     let mut stylesheet = Stylesheet::new();
-    let mut rule_parser = TopLevelRuleParser;
+    let mut rule_parser = TopLevelRuleParser::default();
+    let mut errors = Vec::new();
 
     let rules = StyleSheetParser::new(input, &mut rule_parser);
 
     for rule in rules {
         match rule {
-            Ok(rule) => {
+            Ok(TopLevelItem::Rule(rule)) => {
                 stylesheet.rules.push(rule);
             }
-            Err((_err, _slice)) => {
-                // TODO: Logging
+            Ok(TopLevelItem::Media(rules)) => {
+                stylesheet.rules.extend(rules);
+            }
+            Ok(TopLevelItem::Property(registration)) => {
+                stylesheet.properties.push(registration);
+            }
+            Ok(TopLevelItem::Import(url)) => {
+                stylesheet.imports.push(url);
             }
+            Err((err, _slice)) => errors.push(ParseDiagnostic::from(err)),
         }
     }
 
-    Ok(stylesheet)
+    errors.append(&mut rule_parser.errors);
+    errors.sort_by_key(|error| (error.line, error.column));
+
+    (stylesheet, errors)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{CustomValue, Property};
+    use crate::{CustomValue, ParseErrorKind, Property};
+    use cssparser::ToCss;
     use ginyu_force::Pose;
 
     fn get_custom_property<'a>(rule: &'a Rule, name: &str) -> Option<&'a str> {
@@ -178,6 +276,132 @@ mod tests {
         assert!(stylesheet.rules.len() >= 2);
     }
 
+    #[test]
+    fn collects_errors_from_skipped_declarations() {
+        let (stylesheet, errors) = Stylesheet::parse_with_errors(
+            r"
+            .a { color: red }
+            .b { display: sideways; color: blue }
+            .c { margin: 1 }
+        ",
+        );
+
+        assert_eq!(stylesheet.rules.len(), 3);
+        // The bad declaration is skipped, not the rule it sits in
+        assert_eq!(stylesheet.rules[1].declarations.len(), 1);
+        assert_eq!(
+            stylesheet.rules[1].declarations[0].property,
+            Property::Color
+        );
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+        assert!(matches!(
+            errors[0].kind,
+            ParseErrorKind::UnknownKeyword { .. }
+        ));
+    }
+
+    #[test]
+    fn collects_errors_from_rules_and_nesting() {
+        let (stylesheet, errors) = Stylesheet::parse_with_errors(
+            r"
+            .a { .b { colour: red } }
+            ..bad { color: red }
+            @media (min-width: 1) { .c { color: nope } }
+            @font-face { src: none }
+        ",
+        );
+
+        assert_eq!(stylesheet.rules.len(), 2);
+        assert_eq!(errors.len(), 4);
+        assert_eq!(
+            errors[0].kind,
+            ParseErrorKind::UnknownProperty("colour".into())
+        );
+        assert!(matches!(errors[1].kind, ParseErrorKind::BadSelector(_)));
+        assert_eq!(errors[2].kind, ParseErrorKind::UnknownColor("nope".into()));
+        assert_eq!(
+            errors[3].kind,
+            ParseErrorKind::UnknownAtRule("font-face".into())
+        );
+        assert!(errors.windows(2).all(|pair| pair[0].line < pair[1].line));
+    }
+
+    #[test]
+    fn imports_before_rules() {
+        let (stylesheet, errors) = Stylesheet::parse_with_errors(
+            r#"
+            @import "base.css";
+            @import url(theme.css);
+            .a { color: red }
+            @import "late.css";
+        "#,
+        );
+
+        assert_eq!(stylesheet.imports, ["base.css", "theme.css"]);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ParseErrorKind::MisplacedImport);
+    }
+
+    #[test]
+    fn import_chain() {
+        let files = |url: &str| match url {
+            "base.css" => Some(r#"@import "reset.css"; .base { color: red }"#.to_string()),
+            "reset.css" => Some(".reset { color: blue }".to_string()),
+            _ => None,
+        };
+
+        let stylesheet = Stylesheet::parse_with_resolver(
+            r#"@import "base.css"; @import "missing.css"; .app { color: green }"#,
+            files,
+        )
+        .expect("failed");
+
+        let selectors: Vec<_> = stylesheet
+            .rules
+            .iter()
+            .map(|rule| rule.selectors.to_css_string())
+            .collect();
+        assert_eq!(selectors, [".reset", ".base", ".app"]);
+        assert_eq!(stylesheet.imports, ["missing.css"]);
+    }
+
+    #[test]
+    fn import_cycle_skipped() {
+        let mut requests = 0;
+        let files = |url: &str| {
+            requests += 1;
+            (url == "self.css").then(|| r#"@import "self.css"; .a { color: red }"#.to_string())
+        };
+
+        let stylesheet =
+            Stylesheet::parse_with_resolver(r#"@import "self.css";"#, files).expect("failed");
+
+        assert_eq!(stylesheet.rules.len(), 1);
+        assert!(stylesheet.imports.is_empty());
+        assert_eq!(requests, 1);
+    }
+
+    #[test]
+    fn merge_appends_in_order() {
+        let mut stylesheet = Stylesheet::parse(".a { color: red }").expect("failed");
+        let other = Stylesheet::parse(
+            r#"@property --x { syntax: "*"; inherits: true } .b { color: blue }"#,
+        )
+        .expect("failed");
+
+        stylesheet.merge(other);
+
+        assert_eq!(stylesheet.rules.len(), 2);
+        assert_eq!(stylesheet.rules[0].selectors.to_css_string(), ".a");
+        assert_eq!(stylesheet.rules[1].selectors.to_css_string(), ".b");
+        assert_eq!(stylesheet.properties.len(), 1);
+
+        stylesheet.rules_mut().remove(0);
+        assert_eq!(stylesheet.rules[0].selectors.to_css_string(), ".b");
+    }
+
     #[test]
     fn nested_rules_in_stylesheet() {
         let stylesheet = Stylesheet::parse(
@@ -193,6 +417,41 @@ mod tests {
         assert_eq!(stylesheet.rules[0].nested_rules.len(), 1);
     }
 
+    #[test]
+    fn media_rules_flatten() {
+        let stylesheet = Stylesheet::parse(
+            r"
+            .a { color: red }
+            @media (min-width: 80) {
+                .b { color: blue }
+                @media (max-height: 20) { .c { color: green } }
+            }
+            @font-face { src: none }
+        ",
+        )
+        .expect("failed");
+        assert_eq!(stylesheet.rules.len(), 3);
+        assert!(stylesheet.rules[0].media.is_empty());
+        assert_eq!(stylesheet.rules[1].media.len(), 1);
+        // Nested blocks all have to match
+        assert_eq!(stylesheet.rules[2].media.len(), 2);
+    }
+
+    #[test]
+    fn property_registrations() {
+        let stylesheet = Stylesheet::parse(
+            r#"
+            @property --accent { syntax: "<color>"; inherits: false; initial-value: red }
+            @property --broken { syntax: "<color>"; inherits: false; initial-value: 1 }
+            .a { color: var(--accent) }
+        "#,
+        )
+        .expect("failed");
+        assert_eq!(stylesheet.rules.len(), 1);
+        assert_eq!(stylesheet.properties.len(), 1);
+        assert_eq!(stylesheet.properties[0].name, Pose::from("accent"));
+    }
+
     #[test]
     fn custom_properties_throughout() {
         let stylesheet = Stylesheet::parse(
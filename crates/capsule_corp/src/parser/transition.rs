@@ -0,0 +1,129 @@
+use std::time::Duration;
+
+use cssparser::{Parser, Token};
+
+use crate::{
+    ParseErrorKind, ParseResult, Property, Shorthand, Transition, TransitionProperty,
+    parser::error::{error, expected},
+};
+
+/// `none`, or comma separated `<property> <duration>` pairs. The property is
+/// a longhand, a shorthand, which covers all of its longhands, or `all`.
+pub fn parse_transitions<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<Transition>> {
+    if input.try_parse(|i| i.expect_ident_matching("none")).is_ok() {
+        return Ok(Vec::new());
+    }
+
+    let lists = input.parse_comma_separated(parse_transition)?;
+
+    Ok(lists.into_iter().flatten().collect())
+}
+
+fn parse_transition<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Vec<Transition>> {
+    let location = input.current_source_location();
+    let name = input.expect_ident_cloned()?;
+    let duration = parse_duration(input)?;
+
+    let properties: Vec<_> = if name.eq_ignore_ascii_case("all") {
+        vec![TransitionProperty::All]
+    } else if let Some(property) = Property::from_name(&name.to_ascii_lowercase()) {
+        vec![TransitionProperty::Property(property)]
+    } else if let Some(shorthand) = Shorthand::from_name(&name.to_ascii_lowercase()) {
+        shorthand
+            .longhands()
+            .iter()
+            .map(|&property| TransitionProperty::Property(property))
+            .collect()
+    } else {
+        return error(ParseErrorKind::UnknownProperty(name.to_string()), location);
+    };
+
+    Ok(properties
+        .into_iter()
+        .map(|property| Transition::new(property, duration))
+        .collect())
+}
+
+/// `<n>s` or `<n>ms`, never negative.
+fn parse_duration<'i>(input: &mut Parser<'i, '_>) -> ParseResult<'i, Duration> {
+    let location = input.current_source_location();
+
+    match input.next()? {
+        Token::Dimension { value, unit, .. } if *value >= 0.0 => {
+            if unit.eq_ignore_ascii_case("s") {
+                Ok(Duration::from_secs_f64(f64::from(*value)))
+            } else if unit.eq_ignore_ascii_case("ms") {
+                Ok(Duration::from_secs_f64(f64::from(*value) / 1000.0))
+            } else {
+                error(
+                    ParseErrorKind::Expected {
+                        what: "'s' or 'ms'".into(),
+                        got: unit.to_string(),
+                    },
+                    location,
+                )
+            }
+        }
+        other => expected("duration", other, location),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cssparser::ParserInput;
+
+    use super::*;
+
+    fn parse(s: &str) -> Result<Vec<Transition>, String> {
+        let mut input = ParserInput::new(s);
+        let mut parser = Parser::new(&mut input);
+        parse_transitions(&mut parser).map_err(|e| format!("{e:?}"))
+    }
+
+    #[test]
+    fn property_and_duration() {
+        let transitions = parse("background-color 200ms, color 1.5s").expect("failed");
+
+        assert_eq!(
+            transitions,
+            [
+                Transition::new(
+                    TransitionProperty::Property(Property::BackgroundColor),
+                    Duration::from_millis(200)
+                ),
+                Transition::new(
+                    TransitionProperty::Property(Property::Color),
+                    Duration::from_millis(1500)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_and_none() {
+        let transitions = parse("all 1s").expect("failed");
+        assert_eq!(
+            transitions,
+            [Transition::new(
+                TransitionProperty::All,
+                Duration::from_secs(1)
+            )]
+        );
+
+        assert!(parse("none").expect("failed").is_empty());
+    }
+
+    #[test]
+    fn shorthand_covers_its_longhands() {
+        let transitions = parse("border-color 1s").expect("failed");
+        assert_eq!(transitions.len(), 4);
+    }
+
+    #[test]
+    fn rejects_bad_transitions() {
+        assert!(parse("colour 1s").is_err());
+        assert!(parse("color").is_err());
+        assert!(parse("color -1s").is_err());
+        assert!(parse("color 1px").is_err());
+    }
+}
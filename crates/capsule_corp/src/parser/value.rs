@@ -3,11 +3,14 @@ use cssparser::Parser;
 use crate::{
     ParseResult, Property, Value,
     parser::{
-        keyword::parse_align_content, parse_align_items, parse_align_self, parse_border_style,
-        parse_color, parse_dimension, parse_display, parse_flex_direction, parse_flex_wrap,
-        parse_font_style, parse_font_weight, parse_integer, parse_justify_content, parse_length,
-        parse_number, parse_overflow, parse_overflow_wrap, parse_text_align, parse_text_decoration,
-        parse_vertical_align, parse_visibility, parse_white_space,
+        keyword::parse_align_content, parse_align_items, parse_align_self, parse_aspect_ratio,
+        parse_border_style, parse_box_shadow, parse_char, parse_color, parse_cursor,
+        parse_dimension, parse_display, parse_flex_direction, parse_flex_wrap, parse_font_style,
+        parse_font_weight, parse_grid_placement, parse_grid_template, parse_justify_content,
+        parse_length, parse_margin, parse_number, parse_opacity, parse_overflow,
+        parse_overflow_wrap, parse_position, parse_text_align, parse_text_decoration,
+        parse_text_overflow, parse_transitions, parse_vertical_align, parse_visibility,
+        parse_white_space, parse_z_index,
     },
 };
 
@@ -20,6 +23,7 @@ pub fn parse_property_value<'i>(
 
     match property {
         Display => parse_display(input).map(Value::Display),
+        Position => parse_position(input).map(Value::Position),
         FlexDirection => parse_flex_direction(input).map(Value::FlexDirection),
         FlexWrap => parse_flex_wrap(input).map(Value::FlexWrap),
         JustifyContent => parse_justify_content(input).map(Value::JustifyContent),
@@ -27,17 +31,23 @@ pub fn parse_property_value<'i>(
         AlignContent => parse_align_content(input).map(Value::AlignContent),
 
         FlexGrow | FlexShrink => parse_number(input).map(Value::Number),
-        FlexBasis => parse_dimension(input).map(Value::Dimension),
         AlignSelf => parse_align_self(input).map(Value::AlignSelf),
 
-        // TODO: Hmm.. parse grid right
-        GridTemplateColumns | GridTemplateRows | GridColumn | GridRow | Width | Height
-        | MinWidth | MinHeight | MaxWidth | MaxHeight => {
-            parse_dimension(input).map(Value::Dimension)
+        GridTemplateColumns | GridTemplateRows => {
+            parse_grid_template(input).map(Value::GridTemplate)
         }
+        GridColumn | GridRow => parse_grid_placement(input).map(Value::GridPlacement),
 
-        RowGap | ColumnGap | MarginTop | MarginBottom | MarginLeft | MarginRight | PaddingTop
-        | PaddingBottom | PaddingLeft | PaddingRight => parse_length(input).map(Value::Length),
+        FlexBasis | Width | Height | MinWidth | MinHeight | MaxWidth | MaxHeight | Top | Right
+        | Bottom | Left => parse_dimension(input).map(Value::Dimension),
+
+        MarginTop | MarginBottom | MarginLeft | MarginRight => {
+            parse_margin(input).map(Value::Margin)
+        }
+
+        RowGap | ColumnGap | PaddingTop | PaddingBottom | PaddingLeft | PaddingRight => {
+            parse_length(input).map(Value::Length)
+        }
 
         BorderTopStyle | BorderBottomStyle | BorderLeftStyle | BorderRightStyle => {
             parse_border_style(input).map(Value::BorderStyle)
@@ -47,7 +57,14 @@ pub fn parse_property_value<'i>(
             parse_color(input).map(Value::Color)
         }
 
-        Color | BackgroundColor => parse_color(input).map(Value::Color),
+        OutlineStyle => parse_border_style(input).map(Value::BorderStyle),
+
+        Color | BackgroundColor | OutlineColor => parse_color(input).map(Value::Color),
+        BackgroundChar => parse_char(input).map(Value::Char),
+
+        BoxShadow => parse_box_shadow(input).map(Value::BoxShadow),
+        Opacity => parse_opacity(input).map(Value::Number),
+        Transition => parse_transitions(input).map(Value::Transitions),
 
         FontWeight => parse_font_weight(input).map(Value::FontWeight),
         FontStyle => parse_font_style(input).map(Value::FontStyle),
@@ -56,11 +73,16 @@ pub fn parse_property_value<'i>(
         VerticalAlign => parse_vertical_align(input).map(Value::VerticalAlign),
         WhiteSpace => parse_white_space(input).map(Value::WhiteSpace),
         OverflowWrap => parse_overflow_wrap(input).map(Value::OverflowWrap),
+        TextOverflow => parse_text_overflow(input).map(Value::TextOverflow),
 
         OverflowX | OverflowY => parse_overflow(input).map(Value::Overflow),
         Visibility => parse_visibility(input).map(Value::Visibility),
 
-        ZIndex => parse_integer(input).map(Value::Integer),
+        AspectRatio => parse_aspect_ratio(input).map(Value::AspectRatio),
+
+        ZIndex => parse_z_index(input).map(Value::ZIndex),
+
+        Cursor => parse_cursor(input).map(Value::Cursor),
 
         Custom(_) => unreachable!(),
     }
@@ -84,6 +106,65 @@ mod tests {
         assert_eq!(v.as_display(), Some(&Display::Flex));
     }
 
+    #[test]
+    fn position_properties() {
+        let v = parse(Property::Position, "absolute").expect("failed");
+        assert_eq!(v.as_position(), Some(&Position::Absolute));
+
+        let v = parse(Property::Top, "2").expect("failed");
+        assert_eq!(v.as_dimension(), Some(&Dimension::Length(Length::Cells(2))));
+
+        let v = parse(Property::Right, "auto").expect("failed");
+        assert_eq!(v.as_dimension(), Some(&Dimension::Auto));
+
+        assert!(parse(Property::Position, "sticky").is_err());
+    }
+
+    #[test]
+    fn grid_properties() {
+        let v = parse(Property::GridTemplateColumns, "10 1fr repeat(2, auto 50%)").expect("failed");
+        assert_eq!(
+            v.as_grid_template().map(Vec::as_slice),
+            Some(
+                [
+                    TrackSize::Length(Length::Cells(10)),
+                    TrackSize::Fraction(1.0),
+                    TrackSize::Auto,
+                    TrackSize::Length(Length::Percent(50.0)),
+                    TrackSize::Auto,
+                    TrackSize::Length(Length::Percent(50.0)),
+                ]
+                .as_slice()
+            )
+        );
+
+        let v = parse(Property::GridTemplateRows, "none").expect("failed");
+        assert_eq!(v.as_grid_template(), Some(&Vec::new()));
+
+        let v = parse(Property::GridColumn, "2 / span 3").expect("failed");
+        assert_eq!(
+            v.as_grid_placement(),
+            Some(&GridPlacement::new(GridLine::Line(2), GridLine::Span(3)))
+        );
+
+        let v = parse(Property::GridRow, "-1").expect("failed");
+        assert_eq!(
+            v.as_grid_placement(),
+            Some(&GridPlacement::new(GridLine::Line(-1), GridLine::Auto))
+        );
+
+        assert!(parse(Property::GridColumn, "0").is_err());
+        assert!(parse(Property::GridColumn, "span 0").is_err());
+        assert!(parse(Property::GridTemplateColumns, "-1fr").is_err());
+    }
+
+    #[test]
+    fn text_overflow_property() {
+        let v = parse(Property::TextOverflow, "ellipsis").expect("failed");
+        assert_eq!(v.as_text_overflow(), Some(&TextOverflow::Ellipsis));
+        assert!(parse(Property::TextOverflow, "fade").is_err());
+    }
+
     #[test]
     fn width_property() {
         let v = parse(Property::Width, "100").expect("failed");
@@ -105,7 +186,10 @@ mod tests {
     #[test]
     fn margin_property() {
         let v = parse(Property::MarginTop, "10").expect("failed");
-        assert_eq!(v.as_length(), Some(&Length::Cells(10)));
+        assert_eq!(v.as_margin(), Some(&Margin::from(Length::Cells(10))));
+
+        let v = parse(Property::MarginTop, "-2").expect("failed");
+        assert_eq!(v.as_margin(), Some(&Margin::negative(Length::Cells(2))));
     }
 
     #[test]
@@ -123,13 +207,54 @@ mod tests {
         assert_eq!(v.as_number(), Some(2.5));
     }
 
+    #[test]
+    fn aspect_ratio_property() {
+        let v = parse(Property::AspectRatio, "2").expect("failed");
+        assert_eq!(v.as_aspect_ratio(), Some(&AspectRatio::Ratio(2.0)));
+
+        let v = parse(Property::AspectRatio, "16 / 4").expect("failed");
+        assert_eq!(v.as_aspect_ratio(), Some(&AspectRatio::Ratio(4.0)));
+
+        let v = parse(Property::AspectRatio, "auto").expect("failed");
+        assert_eq!(v.as_aspect_ratio(), Some(&AspectRatio::Auto));
+
+        let v = parse(Property::AspectRatio, "0 / 1").expect("failed");
+        assert_eq!(v.as_aspect_ratio(), Some(&AspectRatio::Auto));
+
+        assert!(parse(Property::AspectRatio, "-1").is_err());
+    }
+
     #[test]
     fn z_index_property() {
         let v = parse(Property::ZIndex, "10").expect("failed");
-        assert_eq!(v.as_integer(), Some(10));
+        assert_eq!(v.as_z_index(), Some(&ZIndex::Integer(10)));
 
         let v = parse(Property::ZIndex, "-1").expect("failed");
-        assert_eq!(v.as_integer(), Some(-1));
+        assert_eq!(v.as_z_index(), Some(&ZIndex::Integer(-1)));
+
+        let v = parse(Property::ZIndex, "auto").expect("failed");
+        assert_eq!(v.as_z_index(), Some(&ZIndex::Auto));
+
+        let v = parse(Property::ZIndex, "0").expect("failed");
+        assert_eq!(v.as_z_index(), Some(&ZIndex::Integer(0)));
+    }
+
+    #[test]
+    fn cursor_property() {
+        let v = parse(Property::Cursor, "pointer").expect("failed");
+        assert_eq!(v.as_cursor(), Some(&Cursor::Pointer));
+    }
+
+    #[test]
+    fn background_char_property() {
+        let v = parse(Property::BackgroundChar, "\"░\"").expect("failed");
+        assert_eq!(v.as_char(), Some(Some('░')));
+
+        let v = parse(Property::BackgroundChar, "none").expect("failed");
+        assert_eq!(v.as_char(), Some(None));
+
+        assert!(parse(Property::BackgroundChar, "\"ab\"").is_err());
+        assert!(parse(Property::BackgroundChar, "\"\"").is_err());
     }
 
     #[test]
@@ -143,4 +268,43 @@ mod tests {
         let v = parse(Property::BorderTopColor, "cyan").expect("failed");
         assert_eq!(v.as_color(), Some(&Color::CYAN));
     }
+
+    #[test]
+    fn box_shadow_property() {
+        let v = parse(Property::BoxShadow, "1 2 red").expect("failed");
+        assert_eq!(
+            v.as_box_shadow(),
+            Some(&Some(BoxShadow::new(1, 2, Color::RED)))
+        );
+
+        let v = parse(Property::BoxShadow, "blue -1 1").expect("failed");
+        assert_eq!(
+            v.as_box_shadow(),
+            Some(&Some(BoxShadow::new(-1, 1, Color::BLUE)))
+        );
+
+        let v = parse(Property::BoxShadow, "2 1").expect("failed");
+        assert_eq!(
+            v.as_box_shadow(),
+            Some(&Some(BoxShadow::new(2, 1, Color::BLACK)))
+        );
+
+        let v = parse(Property::BoxShadow, "none").expect("failed");
+        assert_eq!(v.as_box_shadow(), Some(&None));
+
+        assert!(parse(Property::BoxShadow, "1").is_err());
+        assert!(parse(Property::BoxShadow, "red 1").is_err());
+    }
+
+    #[test]
+    fn opacity_property() {
+        let v = parse(Property::Opacity, "0.5").expect("failed");
+        assert_eq!(v.as_number(), Some(0.5));
+
+        let v = parse(Property::Opacity, "1.5").expect("failed");
+        assert_eq!(v.as_number(), Some(1.0));
+
+        let v = parse(Property::Opacity, "-1").expect("failed");
+        assert_eq!(v.as_number(), Some(0.0));
+    }
 }
@@ -1,8 +1,9 @@
 use crate::{
-    AlignContent, AlignItems, AlignSelf, BorderStyle, Color, CustomValue, Dimension, Display,
-    FlexDirection, FlexWrap, FontStyle, FontWeight, JustifyContent, Length, Overflow, OverflowWrap,
-    TextAlign, TextDecoration, UnresolvedValue, VerticalAlign, Visibility, WhiteSpace,
-    macros::keyword_enum,
+    AlignContent, AlignItems, AlignSelf, AspectRatio, BorderStyle, BoxShadow, Color, Cursor,
+    CustomValue, Dimension, Display, FlexDirection, FlexWrap, FontStyle, FontWeight, GridPlacement,
+    JustifyContent, Length, Margin, Overflow, OverflowWrap, Position, TextAlign, TextDecoration,
+    TextOverflow, TrackSize, Transition, UnresolvedValue, VerticalAlign, Visibility, WhiteSpace,
+    ZIndex, macros::keyword_enum,
 };
 
 keyword_enum! {
@@ -10,6 +11,12 @@ keyword_enum! {
     pub enum Property {
         Display = "display",
 
+        Position = "position",
+        Top = "top",
+        Right = "right",
+        Bottom = "bottom",
+        Left = "left",
+
         FlexDirection = "flex-direction",
         FlexWrap = "flex-wrap",
         JustifyContent = "justify-content",
@@ -36,6 +43,7 @@ keyword_enum! {
         MaxWidth = "max-width",
         MinHeight = "min-height",
         MaxHeight = "max-height",
+        AspectRatio = "aspect-ratio",
 
         MarginTop = "margin-top",
         MarginRight = "margin-right",
@@ -56,8 +64,16 @@ keyword_enum! {
         BorderBottomColor = "border-bottom-color",
         BorderLeftColor = "border-left-color",
 
+        OutlineStyle = "outline-style",
+        OutlineColor = "outline-color",
+
+        BoxShadow = "box-shadow",
+        Opacity = "opacity",
+        Transition = "transition",
+
         Color = "color",
         BackgroundColor = "background-color",
+        BackgroundChar = "background-char",
 
         FontWeight = "font-weight",
         FontStyle = "font-style",
@@ -66,6 +82,7 @@ keyword_enum! {
         VerticalAlign = "vertical-align",
         WhiteSpace = "white-space",
         OverflowWrap = "overflow-wrap",
+        TextOverflow = "text-overflow",
 
         OverflowX = "overflow-x",
         OverflowY = "overflow-y",
@@ -73,6 +90,8 @@ keyword_enum! {
 
         ZIndex = "z-index",
 
+        Cursor = "cursor",
+
         @custom
     }
 }
@@ -92,6 +111,7 @@ impl Property {
                 | WhiteSpace
                 | OverflowWrap
                 | Visibility
+                | Cursor
         )
     }
 }
@@ -111,6 +131,8 @@ keyword_enum! {
         Gap = "gap",
         Overflow = "overflow",
         Background = "background",
+        Outline = "outline",
+        All = "all",
     }
 }
 
@@ -151,6 +173,8 @@ impl Shorthand {
             Self::Gap => &[RowGap, ColumnGap],
             Self::Overflow => &[OverflowX, OverflowY],
             Self::Background => &[BackgroundColor],
+            Self::Outline => &[OutlineStyle, OutlineColor],
+            Self::All => Property::ALL,
         }
     }
 }
@@ -201,6 +225,7 @@ macro_rules! impl_accessors {
 #[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Display(Display),
+    Position(Position),
     FlexDirection(FlexDirection),
     FlexWrap(FlexWrap),
     JustifyContent(JustifyContent),
@@ -214,17 +239,27 @@ pub enum Value {
     VerticalAlign(VerticalAlign),
     WhiteSpace(WhiteSpace),
     OverflowWrap(OverflowWrap),
+    TextOverflow(TextOverflow),
     Overflow(Overflow),
     Visibility(Visibility),
     BorderStyle(BorderStyle),
+    Cursor(Cursor),
+    ZIndex(ZIndex),
+    AspectRatio(AspectRatio),
+    GridTemplate(Vec<TrackSize>),
+    GridPlacement(GridPlacement),
+    BoxShadow(Option<BoxShadow>),
+    Transitions(Vec<Transition>),
 
     Length(Length),
+    Margin(Margin),
     Dimension(Dimension),
 
     Color(Color),
 
     Number(f32),
     Integer(i16),
+    Char(Option<char>),
 
     Inherit,
     Initial,
@@ -236,6 +271,7 @@ pub enum Value {
 
 impl_from! {
     Display(Display),
+    Position(Position),
     FlexDirection(FlexDirection),
     FlexWrap(FlexWrap),
     JustifyContent(JustifyContent),
@@ -248,16 +284,26 @@ impl_from! {
     VerticalAlign(VerticalAlign),
     WhiteSpace(WhiteSpace),
     OverflowWrap(OverflowWrap),
+    TextOverflow(TextOverflow),
     Overflow(Overflow),
     Visibility(Visibility),
     BorderStyle(BorderStyle),
+    Cursor(Cursor),
+    ZIndex(ZIndex),
+    AspectRatio(AspectRatio),
+    GridTemplate(Vec<TrackSize>),
+    GridPlacement(GridPlacement),
+    BoxShadow(Option<BoxShadow>),
+    Transitions(Vec<Transition>),
     Length(Length),
+    Margin(Margin),
     Dimension(Dimension),
     Color(Color),
 }
 
 impl_accessors! {
     as_display -> Display(Display),
+    as_position -> Position(Position),
     as_flex_direction -> FlexDirection(FlexDirection),
     as_flex_wrap -> FlexWrap(FlexWrap),
     as_justify_content -> JustifyContent(JustifyContent),
@@ -270,10 +316,19 @@ impl_accessors! {
     as_vertical_align -> VerticalAlign(VerticalAlign),
     as_white_space -> WhiteSpace(WhiteSpace),
     as_overflow_wrap -> OverflowWrap(OverflowWrap),
+    as_text_overflow -> TextOverflow(TextOverflow),
     as_overflow -> Overflow(Overflow),
     as_visibility -> Visibility(Visibility),
     as_border_style -> BorderStyle(BorderStyle),
+    as_cursor -> Cursor(Cursor),
+    as_z_index -> ZIndex(ZIndex),
+    as_aspect_ratio -> AspectRatio(AspectRatio),
+    as_grid_template -> GridTemplate(Vec<TrackSize>),
+    as_grid_placement -> GridPlacement(GridPlacement),
+    as_box_shadow -> BoxShadow(Option<BoxShadow>),
+    as_transitions -> Transitions(Vec<Transition>),
     as_length -> Length(Length),
+    as_margin -> Margin(Margin),
     as_dimension -> Dimension(Dimension),
     as_color -> Color(Color),
 }
@@ -310,6 +365,14 @@ impl Value {
         }
     }
 
+    #[must_use]
+    pub const fn as_char(&self) -> Option<Option<char>> {
+        match self {
+            Self::Char(v) => Some(*v),
+            _ => None,
+        }
+    }
+
     #[must_use]
     pub const fn is_unresolved(&self) -> bool {
         matches!(self, Self::Unresolved(_))
@@ -395,10 +458,19 @@ mod tests {
         assert!(longhands.contains(&Property::MarginLeft));
     }
 
+    #[test]
+    fn all_shorthand_excludes_custom() {
+        let longhands = Shorthand::All.longhands();
+        assert!(longhands.contains(&Property::Color));
+        assert!(longhands.contains(&Property::Display));
+        assert!(longhands.iter().all(|p| !p.is_custom()));
+    }
+
     #[test]
     fn property_inherited() {
         assert!(Property::Color.inherited());
         assert!(Property::FontWeight.inherited());
+        assert!(Property::Cursor.inherited());
         assert!(!Property::Display.inherited());
         assert!(!Property::MarginTop.inherited());
     }
@@ -54,6 +54,26 @@ impl Border {
     }
 }
 
+/// A solid copy of the border box, offset by whole cells and painted behind
+/// it. There's no blur, terminals can't do it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoxShadow {
+    pub offset_x: i16,
+    pub offset_y: i16,
+    pub color: Color,
+}
+
+impl BoxShadow {
+    #[must_use]
+    pub const fn new(offset_x: i16, offset_y: i16, color: Color) -> Self {
+        Self {
+            offset_x,
+            offset_y,
+            color,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -17,6 +17,25 @@ impl Color {
     pub const MAGENTA: Self = Self::Basic(BasicColor::Magenta);
     pub const CYAN: Self = Self::Basic(BasicColor::Cyan);
     pub const WHITE: Self = Self::Basic(BasicColor::White);
+
+    /// Mix towards `other`, `t` of `0.0` gives `self` and `1.0` gives `other`.
+    /// Only RGB colors can be mixed, anything else switches over halfway.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        match (self, other) {
+            (Self::Rgb(r, g, b), Self::Rgb(other_r, other_g, other_b)) => {
+                let mix = |from: u8, to: u8| {
+                    let (from, to) = (f32::from(from), f32::from(to));
+                    (to - from).mul_add(t, from).round().clamp(0.0, 255.0) as u8
+                };
+
+                Self::Rgb(mix(r, other_r), mix(g, other_g), mix(b, other_b))
+            }
+            _ if t < 0.5 => self,
+            _ => other,
+        }
+    }
 }
 
 /// Basic terminal colors (ANSI 0-7).
@@ -48,6 +67,20 @@ impl BasicColor {
         }
     }
 
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Black => "black",
+            Self::Red => "red",
+            Self::Green => "green",
+            Self::Yellow => "yellow",
+            Self::Blue => "blue",
+            Self::Magenta => "magenta",
+            Self::Cyan => "cyan",
+            Self::White => "white",
+        }
+    }
+
     #[must_use]
     pub const fn ansi_code(self) -> u8 {
         self as u8
@@ -68,6 +101,7 @@ mod tests {
         assert_eq!(BasicColor::from_name("red"), Some(BasicColor::Red));
         assert_eq!(BasicColor::from_name("cyan"), Some(BasicColor::Cyan));
         assert_eq!(BasicColor::from_name("purple"), None);
+        assert_eq!(BasicColor::Magenta.name(), "magenta");
     }
 
     #[test]
@@ -87,4 +121,18 @@ mod tests {
         assert_eq!(Color::RED, Color::Basic(BasicColor::Red));
         assert_eq!(Color::CYAN, Color::Basic(BasicColor::Cyan));
     }
+
+    #[test]
+    fn color_lerp() {
+        let from = Color::Rgb(0, 100, 200);
+        let to = Color::Rgb(100, 100, 0);
+
+        assert_eq!(from.lerp(to, 0.0), from);
+        assert_eq!(from.lerp(to, 0.5), Color::Rgb(50, 100, 100));
+        assert_eq!(from.lerp(to, 1.0), to);
+
+        // Named colors can't be mixed, so they flip at the halfway mark
+        assert_eq!(Color::RED.lerp(to, 0.4), Color::RED);
+        assert_eq!(Color::RED.lerp(to, 0.5), to);
+    }
 }
@@ -0,0 +1,29 @@
+use crate::macros::keyword_enum;
+
+keyword_enum! {
+    #[derive(Default)]
+    pub enum Cursor {
+        #[default]
+        Default = "default",
+        Pointer = "pointer",
+        Text = "text",
+        Hidden = "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor() {
+        assert_eq!(Cursor::from_name("pointer"), Some(Cursor::Pointer));
+        assert_eq!(Cursor::from_name("none"), Some(Cursor::Hidden));
+        assert_eq!(Cursor::Text.to_name(), "text");
+    }
+
+    #[test]
+    fn cursor_default() {
+        assert_eq!(Cursor::default(), Cursor::Default);
+    }
+}
@@ -4,7 +4,7 @@ use ginyu_force::Pose;
 use rustc_hash::{FxHashMap, FxHashSet};
 use thiserror::Error;
 
-use crate::{SubstituteError, UnresolvedValue};
+use crate::{SubstituteError, UnresolvedValue, parser::matches_syntax};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct CustomPropertiesMap {
@@ -40,6 +40,31 @@ pub enum CustomValue {
     Initial,
 }
 
+/// The values a registered custom property accepts, from the `syntax`
+/// descriptor of `@property`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertySyntax {
+    /// `"*"`, anything goes
+    Universal,
+    Color,
+    Length,
+    Number,
+    Integer,
+}
+
+/// A custom property registered with `@property`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyRegistration {
+    pub name: Pose,
+    pub syntax: PropertySyntax,
+    pub inherits: bool,
+    /// Used when the property isn't set, or is set to something that
+    /// doesn't match `syntax`
+    pub initial_value: Option<String>,
+}
+
+pub type PropertyRegistry = FxHashMap<Pose, PropertyRegistration>;
+
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
 pub enum ResolutionError {
     #[error("cycled detected in property {0}")]
@@ -54,6 +79,7 @@ pub enum ResolutionError {
 
 pub struct CustomPropertiesResolver<'a> {
     inherited: Option<&'a CustomPropertiesMap>,
+    registry: Option<&'a PropertyRegistry>,
     declarations: Vec<(Pose, CustomValue)>,
 }
 
@@ -62,10 +88,19 @@ impl<'a> CustomPropertiesResolver<'a> {
     pub const fn new(inherited: Option<&'a CustomPropertiesMap>) -> Self {
         Self {
             inherited,
+            registry: None,
             declarations: Vec::new(),
         }
     }
 
+    /// Resolve against the properties registered with `@property`, giving
+    /// them their initial values and throwing out values of the wrong type.
+    #[must_use]
+    pub const fn with_registry(mut self, registry: &'a PropertyRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
     pub fn add(&mut self, name: Pose, value: CustomValue) {
         self.declarations.push((name, value));
     }
@@ -73,12 +108,18 @@ impl<'a> CustomPropertiesResolver<'a> {
     /// Build the resolved custom properties map.
     ///
     /// Resolution order:
-    /// 1. Start with inherited values
+    /// 1. Start with inherited values, and the initial values of registered
+    ///    properties that are missing or don't inherit
     /// 2. Apply declarations in cascade order
     /// 3. Resolve `var()` references, detecting cycles
     #[must_use]
     pub fn build(self) -> CustomPropertiesMap {
-        if self.declarations.is_empty() {
+        let registry = self.registry.filter(|registry| !registry.is_empty());
+        let needs_initial_values = registry.is_some_and(|registry| {
+            self.inherited.is_none() || registry.values().any(|r| !r.inherits)
+        });
+
+        if self.declarations.is_empty() && !needs_initial_values {
             return self.inherited.cloned().unwrap_or_default();
         }
 
@@ -88,6 +129,12 @@ impl<'a> CustomPropertiesResolver<'a> {
             .map(|v| v.as_ref().clone())
             .unwrap_or_default();
 
+        for registration in registry.into_iter().flat_map(|r| r.values()) {
+            if !registration.inherits || !values.contains_key(&registration.name) {
+                reset_to_initial(registration.name, &mut values, registry);
+            }
+        }
+
         let mut pending: FxHashMap<Pose, CustomValue> = FxHashMap::default();
 
         for (name, value) in self.declarations {
@@ -104,6 +151,7 @@ impl<'a> CustomPropertiesResolver<'a> {
                 &mut values,
                 &mut resolving,
                 self.inherited,
+                registry,
             );
         }
 
@@ -124,13 +172,17 @@ fn resolve_property(
     resolved: &mut FxHashMap<Pose, String>,
     resolving: &mut FxHashSet<Pose>,
     inherited: Option<&CustomPropertiesMap>,
+    registry: Option<&PropertyRegistry>,
 ) -> Result<(), ResolutionError> {
     match value {
         CustomValue::Initial => {
-            resolved.remove(&name);
+            reset_to_initial(name, resolved, registry);
+            return Ok(());
+        }
+        CustomValue::Inherit => {
+            inherit(name, resolved, inherited, registry);
             return Ok(());
         }
-        CustomValue::Inherit => return Ok(()),
         _ => {}
     }
 
@@ -141,7 +193,7 @@ fn resolve_property(
 
     let result = match value {
         CustomValue::Resolved(str) => {
-            resolved.insert(name, str.clone());
+            settle(name, str.clone(), resolved, inherited, registry);
             Ok(())
         }
         CustomValue::Unresolved(unresolved) => {
@@ -156,6 +208,7 @@ fn resolve_property(
                         resolved,
                         resolving,
                         inherited,
+                        registry,
                     );
 
                     if result.is_err() {
@@ -168,11 +221,13 @@ fn resolve_property(
 
             match unresolved.substitute(|dep_name| resolved.get(&dep_name).map(String::as_str)) {
                 Ok(substituted) => {
-                    resolved.insert(name, substituted);
+                    settle(name, substituted, resolved, inherited, registry);
                     Ok(())
                 }
                 Err(err) => {
-                    if inherited.and_then(|i| i.get(name)).is_none() {
+                    if registry.is_some_and(|r| r.contains_key(&name)) {
+                        unset(name, resolved, inherited, registry);
+                    } else if inherited.and_then(|i| i.get(name)).is_none() {
                         resolved.remove(&name);
                     }
 
@@ -187,6 +242,73 @@ fn resolve_property(
     result
 }
 
+/// Store a computed value, unless `name` is registered with a syntax the
+/// value doesn't match.
+fn settle(
+    name: Pose,
+    value: String,
+    resolved: &mut FxHashMap<Pose, String>,
+    inherited: Option<&CustomPropertiesMap>,
+    registry: Option<&PropertyRegistry>,
+) {
+    match registry.and_then(|r| r.get(&name)) {
+        Some(registration) if !matches_syntax(registration.syntax, &value) => {
+            unset(name, resolved, inherited, registry);
+        }
+        _ => {
+            resolved.insert(name, value);
+        }
+    }
+}
+
+/// What an invalid value falls back to, the inherited value for properties
+/// that inherit and the initial value for the rest.
+fn unset(
+    name: Pose,
+    resolved: &mut FxHashMap<Pose, String>,
+    inherited: Option<&CustomPropertiesMap>,
+    registry: Option<&PropertyRegistry>,
+) {
+    match registry.and_then(|r| r.get(&name)) {
+        Some(registration) if !registration.inherits => {
+            reset_to_initial(name, resolved, registry);
+        }
+        _ => inherit(name, resolved, inherited, registry),
+    }
+}
+
+fn inherit(
+    name: Pose,
+    resolved: &mut FxHashMap<Pose, String>,
+    inherited: Option<&CustomPropertiesMap>,
+    registry: Option<&PropertyRegistry>,
+) {
+    match inherited.and_then(|i| i.get(name)) {
+        Some(value) => {
+            resolved.insert(name, value.to_owned());
+        }
+        None => reset_to_initial(name, resolved, registry),
+    }
+}
+
+fn reset_to_initial(
+    name: Pose,
+    resolved: &mut FxHashMap<Pose, String>,
+    registry: Option<&PropertyRegistry>,
+) {
+    match registry
+        .and_then(|r| r.get(&name))
+        .and_then(|registration| registration.initial_value.clone())
+    {
+        Some(initial) => {
+            resolved.insert(name, initial);
+        }
+        None => {
+            resolved.remove(&name);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -344,4 +466,51 @@ mod tests {
         // Falls back to inherited value
         assert_eq!(child.get(Pose::from("color")), Some("inherited-red"));
     }
+
+    fn registry(syntax: PropertySyntax, inherits: bool, initial: &str) -> PropertyRegistry {
+        let name = Pose::from("x");
+        let registration = PropertyRegistration {
+            name,
+            syntax,
+            inherits,
+            initial_value: Some(initial.into()),
+        };
+
+        std::iter::once((name, registration)).collect()
+    }
+
+    #[test]
+    fn registered_initial_value() {
+        let registry = registry(PropertySyntax::Color, true, "red");
+        let map = CustomPropertiesResolver::new(None)
+            .with_registry(&registry)
+            .build();
+
+        assert_eq!(map.get(Pose::from("x")), Some("red"));
+    }
+
+    #[test]
+    fn registered_non_inherited_resets() {
+        let registry = registry(PropertySyntax::Color, false, "red");
+        let mut parent_builder = CustomPropertiesResolver::new(None).with_registry(&registry);
+        parent_builder.add(Pose::from("x"), CustomValue::Resolved("blue".into()));
+        let parent = parent_builder.build();
+
+        let child = CustomPropertiesResolver::new(Some(&parent))
+            .with_registry(&registry)
+            .build();
+
+        assert_eq!(parent.get(Pose::from("x")), Some("blue"));
+        assert_eq!(child.get(Pose::from("x")), Some("red"));
+    }
+
+    #[test]
+    fn registered_rejects_wrong_type() {
+        let registry = registry(PropertySyntax::Integer, false, "1");
+        let mut builder = CustomPropertiesResolver::new(None).with_registry(&registry);
+        builder.add(Pose::from("x"), CustomValue::Resolved("blue".into()));
+
+        let map = builder.build();
+        assert_eq!(map.get(Pose::from("x")), Some("1"));
+    }
 }
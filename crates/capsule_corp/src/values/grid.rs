@@ -0,0 +1,100 @@
+use crate::Length;
+
+/// Size of a single row or column track.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackSize {
+    Length(Length),
+    /// Share of the space left over once the other tracks are sized, `fr`
+    Fraction(f32),
+    /// Just big enough for the items in the track
+    Auto,
+}
+
+/// One end of an item's placement on a grid axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridLine {
+    #[default]
+    Auto,
+    /// 1-based line number, negative numbers count back from the last line
+    Line(i16),
+    /// Number of tracks the item covers
+    Span(u16),
+}
+
+/// Where an item sits along one grid axis, as in `grid-column: 1 / span 2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct GridPlacement {
+    pub start: GridLine,
+    pub end: GridLine,
+}
+
+impl GridPlacement {
+    #[must_use]
+    pub const fn new(start: GridLine, end: GridLine) -> Self {
+        Self { start, end }
+    }
+
+    /// The 0-based track the item starts in, if it has a definite one, and
+    /// the number of tracks it spans. `tracks` is the number of explicit
+    /// tracks, which negative line numbers count back from.
+    #[must_use]
+    pub fn resolve(self, tracks: u16) -> (Option<u16>, u16) {
+        let line = |n: i16| -> u16 {
+            if n > 0 {
+                n.unsigned_abs() - 1
+            } else {
+                (tracks + 1).saturating_sub(n.unsigned_abs())
+            }
+        };
+
+        match (self.start, self.end) {
+            (GridLine::Line(start), GridLine::Line(end)) => {
+                let (start, end) = (line(start), line(end));
+                (Some(start.min(end)), start.abs_diff(end).max(1))
+            }
+            (GridLine::Line(start), GridLine::Span(span)) => (Some(line(start)), span.max(1)),
+            (GridLine::Line(start), GridLine::Auto) => (Some(line(start)), 1),
+            (GridLine::Span(span), GridLine::Line(end)) => {
+                let span = span.max(1);
+                (Some(line(end).saturating_sub(span)), span)
+            }
+            (GridLine::Auto, GridLine::Line(end)) => (Some(line(end).saturating_sub(1)), 1),
+            (GridLine::Span(span), _) | (GridLine::Auto, GridLine::Span(span)) => {
+                (None, span.max(1))
+            }
+            (GridLine::Auto, GridLine::Auto) => (None, 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_lines() {
+        let placement = GridPlacement::new(GridLine::Line(2), GridLine::Line(4));
+        assert_eq!(placement.resolve(3), (Some(1), 2));
+
+        // Line -1 is the last line of the explicit grid
+        let placement = GridPlacement::new(GridLine::Line(1), GridLine::Line(-1));
+        assert_eq!(placement.resolve(3), (Some(0), 3));
+    }
+
+    #[test]
+    fn resolve_spans() {
+        let placement = GridPlacement::new(GridLine::Line(2), GridLine::Span(2));
+        assert_eq!(placement.resolve(3), (Some(1), 2));
+
+        let placement = GridPlacement::new(GridLine::Span(2), GridLine::Line(4));
+        assert_eq!(placement.resolve(3), (Some(1), 2));
+
+        let placement = GridPlacement::new(GridLine::Span(3), GridLine::Auto);
+        assert_eq!(placement.resolve(3), (None, 3));
+    }
+
+    #[test]
+    fn resolve_auto() {
+        assert_eq!(GridPlacement::default().resolve(3), (None, 1));
+    }
+}
@@ -12,6 +12,25 @@ keyword_enum! {
     }
 }
 
+keyword_enum! {
+    #[derive(Default)]
+    pub enum Position {
+        #[default]
+        Static = "static",
+        Relative = "relative",
+        Absolute = "absolute",
+    }
+}
+
+impl Position {
+    /// Whether the box is a containing block for absolutely positioned
+    /// descendants.
+    #[must_use]
+    pub const fn is_positioned(self) -> bool {
+        !matches!(self, Self::Static)
+    }
+}
+
 keyword_enum! {
     #[derive(Default)]
     pub enum FlexDirection {
@@ -121,6 +140,68 @@ keyword_enum! {
     }
 }
 
+/// Preferred width to height ratio of a box, in cells.
+///
+/// Cells are usually about twice as tall as they are wide, so a visually
+/// square box is closer to `2 / 1` than `1 / 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum AspectRatio {
+    #[default]
+    Auto,
+    Ratio(f32),
+}
+
+impl AspectRatio {
+    /// Height for a box `width` cells wide, rounded to the nearest cell.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn height_for(self, width: u16) -> Option<u16> {
+        match self {
+            Self::Auto => None,
+            Self::Ratio(ratio) => {
+                Some((f32::from(width) / ratio).round().min(f32::from(u16::MAX)) as u16)
+            }
+        }
+    }
+
+    /// Width for a box `height` cells tall, rounded to the nearest cell.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn width_for(self, height: u16) -> Option<u16> {
+        match self {
+            Self::Auto => None,
+            Self::Ratio(ratio) => {
+                Some((f32::from(height) * ratio).round().min(f32::from(u16::MAX)) as u16)
+            }
+        }
+    }
+}
+
+/// Stacking order of a box among its siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ZIndex {
+    /// Painted in document order alongside `0`
+    #[default]
+    Auto,
+    Integer(i16),
+}
+
+impl ZIndex {
+    /// The layer this box paints in, with `auto` sharing layer `0`
+    #[must_use]
+    pub const fn layer(self) -> i16 {
+        match self {
+            Self::Auto => 0,
+            Self::Integer(n) => n,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_auto(self) -> bool {
+        matches!(self, Self::Auto)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -160,4 +241,22 @@ mod tests {
             AlignItems::FlexStart
         );
     }
+
+    #[test]
+    fn aspect_ratio_dimensions() {
+        let ratio = AspectRatio::Ratio(2.0);
+        assert_eq!(ratio.height_for(10), Some(5));
+        assert_eq!(ratio.width_for(5), Some(10));
+        assert_eq!(ratio.height_for(5), Some(3));
+        assert_eq!(AspectRatio::Auto.height_for(10), None);
+    }
+
+    #[test]
+    fn z_index_layer() {
+        assert_eq!(ZIndex::Auto.layer(), 0);
+        assert_eq!(ZIndex::Integer(0).layer(), 0);
+        assert_eq!(ZIndex::Integer(-2).layer(), -2);
+        assert!(ZIndex::Auto.is_auto());
+        assert!(!ZIndex::Integer(0).is_auto());
+    }
 }
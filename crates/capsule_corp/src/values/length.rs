@@ -25,12 +25,58 @@ impl Default for Length {
     }
 }
 
+/// A margin side. Unlike every other length it can be negative, pulling the
+/// box and whatever follows it back.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Margin {
+    pub length: Length,
+    /// Whether `length` is taken away instead of added, as in `margin: -2`
+    pub negative: bool,
+}
+
+impl Margin {
+    pub const ZERO: Self = Self {
+        length: Length::ZERO,
+        negative: false,
+    };
+
+    #[must_use]
+    pub const fn negative(length: Length) -> Self {
+        Self {
+            length,
+            negative: true,
+        }
+    }
+
+    /// Resolve against the parent size, as signed cells.
+    #[must_use]
+    pub fn resolve(&self, parent: u16) -> i16 {
+        let cells = i16::try_from(self.length.resolve(parent)).unwrap_or(i16::MAX);
+
+        if self.negative { -cells } else { cells }
+    }
+}
+
+impl From<Length> for Margin {
+    fn from(length: Length) -> Self {
+        Self {
+            length,
+            negative: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum Dimension {
     #[default]
     Auto,
     Length(Length),
     None,
+    /// As narrow as the content gets without overflowing, the widest word
+    /// for text
+    MinContent,
+    /// As wide as the content wants with nothing wrapped
+    MaxContent,
 }
 
 impl Dimension {
@@ -39,13 +85,15 @@ impl Dimension {
     #[must_use]
     pub fn resolve(&self, parent: u16) -> Option<u16> {
         match self {
-            Self::Auto | Self::None => None,
+            // Intrinsic sizes need the content laid out, see brief
+            Self::Auto | Self::None | Self::MinContent | Self::MaxContent => None,
             Self::Length(l) => Some(l.resolve(parent)),
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::use_self)]
 pub enum CalcExpr {
     Cells(i16),
     Percent(f32),
@@ -92,6 +140,18 @@ mod tests {
         assert_eq!(l.resolve(80), 40);
     }
 
+    #[test]
+    fn negative_margin_resolves() {
+        let m = Margin::negative(Length::Cells(2));
+        assert_eq!(m.resolve(100), -2);
+
+        let m = Margin::negative(Length::Percent(10.0));
+        assert_eq!(m.resolve(50), -5);
+
+        let m = Margin::from(Length::Cells(3));
+        assert_eq!(m.resolve(100), 3);
+    }
+
     #[test]
     fn length_percent_rounds() {
         let l = Length::Percent(50.0);
@@ -0,0 +1,98 @@
+use crate::Size;
+
+/// A condition on the viewport, as in `(min-width: 80)`. Sizes are in cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaFeature {
+    Width(u16),
+    MinWidth(u16),
+    MaxWidth(u16),
+    Height(u16),
+    MinHeight(u16),
+    MaxHeight(u16),
+}
+
+impl MediaFeature {
+    #[must_use]
+    pub const fn matches(self, viewport: Size) -> bool {
+        match self {
+            Self::Width(width) => viewport.width == width,
+            Self::MinWidth(width) => viewport.width >= width,
+            Self::MaxWidth(width) => viewport.width <= width,
+            Self::Height(height) => viewport.height == height,
+            Self::MinHeight(height) => viewport.height >= height,
+            Self::MaxHeight(height) => viewport.height <= height,
+        }
+    }
+}
+
+/// Features joined by `and`, all of which have to hold.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MediaQuery {
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    #[must_use]
+    pub fn matches(&self, viewport: Size) -> bool {
+        self.features
+            .iter()
+            .all(|feature| feature.matches(viewport))
+    }
+}
+
+/// The comma separated queries of an `@media` rule, which applies when any of
+/// them match.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MediaQueryList {
+    pub queries: Vec<MediaQuery>,
+}
+
+impl MediaQueryList {
+    #[must_use]
+    pub fn matches(&self, viewport: Size) -> bool {
+        self.queries.iter().any(|query| query.matches(viewport))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn features_against_viewport() {
+        let viewport = Size::new(80, 24);
+
+        assert!(MediaFeature::MinWidth(80).matches(viewport));
+        assert!(!MediaFeature::MinWidth(81).matches(viewport));
+        assert!(MediaFeature::MaxHeight(24).matches(viewport));
+        assert!(!MediaFeature::Height(25).matches(viewport));
+    }
+
+    #[test]
+    fn query_needs_every_feature() {
+        let query = MediaQuery {
+            features: vec![MediaFeature::MinWidth(40), MediaFeature::MaxWidth(100)],
+        };
+
+        assert!(query.matches(Size::new(80, 24)));
+        assert!(!query.matches(Size::new(120, 24)));
+    }
+
+    #[test]
+    fn list_needs_any_query() {
+        let list = MediaQueryList {
+            queries: vec![
+                MediaQuery {
+                    features: vec![MediaFeature::MaxWidth(40)],
+                },
+                MediaQuery {
+                    features: vec![MediaFeature::MinHeight(50)],
+                },
+            ],
+        };
+
+        assert!(list.matches(Size::new(30, 10)));
+        assert!(list.matches(Size::new(100, 60)));
+        assert!(!list.matches(Size::new(100, 10)));
+    }
+}
@@ -1,23 +1,31 @@
 mod border;
 mod color;
+mod cursor;
 mod custom;
 mod edges;
 mod element;
+mod grid;
 mod keyword;
 mod layout;
 mod length;
+mod media;
 mod overflow;
 mod text;
+mod transition;
 mod unresolved;
 
 pub use border::*;
 pub use color::*;
+pub use cursor::*;
 pub use custom::*;
 pub use edges::*;
 pub use element::*;
+pub use grid::*;
 pub use keyword::*;
 pub use layout::*;
 pub use length::*;
+pub use media::*;
 pub use overflow::*;
 pub use text::*;
+pub use transition::*;
 pub use unresolved::*;
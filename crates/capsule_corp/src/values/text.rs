@@ -68,6 +68,17 @@ keyword_enum! {
     }
 }
 
+keyword_enum! {
+    /// How a line of text that's cut off by `overflow` ends.
+    #[derive(Default)]
+    pub enum TextOverflow {
+        #[default]
+        Clip = "clip",
+        /// Replace the last visible cell with `…`
+        Ellipsis = "ellipsis",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
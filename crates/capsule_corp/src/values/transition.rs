@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use crate::Property;
+
+/// Which properties a [`Transition`] covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionProperty {
+    All,
+    Property(Property),
+}
+
+/// One entry of `transition`, as in `background-color 200ms`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Transition {
+    pub property: TransitionProperty,
+    pub duration: Duration,
+}
+
+impl Transition {
+    #[must_use]
+    pub const fn new(property: TransitionProperty, duration: Duration) -> Self {
+        Self { property, duration }
+    }
+
+    #[must_use]
+    pub fn covers(&self, property: Property) -> bool {
+        match self.property {
+            TransitionProperty::All => true,
+            TransitionProperty::Property(p) => p == property,
+        }
+    }
+}
+
+/// How long `property` takes to transition. The last matching entry wins,
+/// like with any other repeated declaration.
+#[must_use]
+pub fn transition_duration(transitions: &[Transition], property: Property) -> Option<Duration> {
+    transitions
+        .iter()
+        .rev()
+        .find(|transition| transition.covers(property))
+        .map(|transition| transition.duration)
+        .filter(|duration| !duration.is_zero())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_matching_entry_wins() {
+        let transitions = [
+            Transition::new(TransitionProperty::All, Duration::from_secs(1)),
+            Transition::new(
+                TransitionProperty::Property(Property::Color),
+                Duration::from_millis(200),
+            ),
+        ];
+
+        assert_eq!(
+            transition_duration(&transitions, Property::Color),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            transition_duration(&transitions, Property::Opacity),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(transition_duration(&[], Property::Color), None);
+    }
+}
@@ -0,0 +1,12 @@
+/// Clipboard event data.
+///
+/// Terminals only ever report pastes, and only with bracketed paste enabled.
+///
+/// Specification: <https://w3c.github.io/clipboard-apis/#clipboard-event-interfaces>
+#[derive(Clone, Debug)]
+pub struct ClipboardEvent {
+    /// The pasted text.
+    ///
+    /// Specification: <https://w3c.github.io/clipboard-apis/#dom-clipboardevent-clipboarddata>
+    pub data: String,
+}
@@ -0,0 +1,26 @@
+use std::ops::Deref;
+
+use crate::{ClientPoint, MouseButton, mouse::MouseEvent};
+
+/// Pointer drag data: a mouse move while a button is held.
+///
+/// Not the HTML drag and drop `DragEvent`, there's no data transfer involved.
+/// It's aimed at things like scrollbar thumbs and splitters that follow the
+/// cursor after being pressed.
+#[derive(Clone, Debug)]
+pub struct DragEvent<T, U> {
+    /// Inherited mouse event data, for the current position.
+    pub mouse: MouseEvent<T, U>,
+    /// The button held down for the drag.
+    pub drag_button: MouseButton,
+    /// Where the button was pressed, in viewport coordinates.
+    pub start: ClientPoint<U>,
+}
+
+impl<T, U> Deref for DragEvent<T, U> {
+    type Target = MouseEvent<T, U>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.mouse
+    }
+}
@@ -1,10 +1,10 @@
-use std::ops::Deref;
+use std::{ops::Deref, time::Duration};
 
 use ginyu_force::{Pose, pose};
 
 use crate::{
-    CompositionEvent, CustomEvent, FocusEvent, InputEvent, KeyboardEvent, MouseEvent, PointerEvent,
-    WheelEvent,
+    ClipboardEvent, CompositionEvent, CustomEvent, DragEvent, FocusEvent, InputEvent,
+    KeyboardEvent, MouseEvent, PointerEvent, WheelEvent,
 };
 
 /// The phase of event propagation.
@@ -48,6 +48,11 @@ pub struct Event<T, U> {
     ///
     /// Specification: <https://dom.spec.whatwg.org/#dom-event-eventphase>
     pub phase: EventPhase,
+    /// When the event was created, relative to the time origin of whatever
+    /// dispatched it. Zero unless the dispatcher sets it.
+    ///
+    /// Specification: <https://dom.spec.whatwg.org/#dom-event-timestamp>
+    pub time_stamp: Duration,
     /// Whether propagation has been stopped.
     propagation_stopped: bool,
     /// Whether immediate propagation has been stopped.
@@ -64,6 +69,7 @@ impl<T, U> Event<T, U> {
             target,
             current_target,
             phase: EventPhase::None,
+            time_stamp: Duration::ZERO,
             propagation_stopped: false,
             immediate_propagation_stopped: false,
             default_prevented: false,
@@ -149,6 +155,10 @@ pub enum EventType<T, U> {
     // Ref: https://w3c.github.io/uievents/#events-wheel-types
     Wheel(WheelEvent<T, U>),
 
+    // Drag events
+    // Not part of any spec, see `DragEvent`
+    Drag(DragEvent<T, U>),
+
     // Keyboard events
     // Ref: https://w3c.github.io/uievents/#events-keyboard-types
     KeyDown(KeyboardEvent),
@@ -172,6 +182,10 @@ pub enum EventType<T, U> {
     CompositionUpdate(CompositionEvent),
     CompositionEnd(CompositionEvent),
 
+    // Clipboard events
+    // Ref: https://w3c.github.io/clipboard-apis/#clipboard-event-paste
+    Paste(ClipboardEvent),
+
     // Custom events
     // Ref: https://dom.spec.whatwg.org/#interface-customevent
     Custom(CustomEvent),
@@ -205,6 +219,8 @@ impl<T, U> EventType<T, U> {
 
             Self::Wheel(_) => pose!("wheel"),
 
+            Self::Drag(_) => pose!("drag"),
+
             Self::KeyDown(_) => pose!("keydown"),
             Self::KeyUp(_) => pose!("keyup"),
 
@@ -220,6 +236,8 @@ impl<T, U> EventType<T, U> {
             Self::CompositionUpdate(_) => pose!("compositionupdate"),
             Self::CompositionEnd(_) => pose!("compositionend"),
 
+            Self::Paste(_) => pose!("paste"),
+
             Self::Custom(e) => e.name,
         }
     }
@@ -259,6 +277,9 @@ event_type_accessors! {
     as_wheel => WheelEvent<T, U> {
         Wheel,
     };
+    as_drag => DragEvent<T, U> {
+        Drag,
+    };
     as_keyboard => KeyboardEvent {
         KeyDown, KeyUp,
     };
@@ -271,6 +292,9 @@ event_type_accessors! {
     as_composition => CompositionEvent {
         CompositionStart, CompositionUpdate, CompositionEnd,
     };
+    as_clipboard => ClipboardEvent {
+        Paste,
+    };
     as_custom => CustomEvent {
         Custom,
     };
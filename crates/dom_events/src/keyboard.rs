@@ -12,3 +12,72 @@ pub struct KeyboardEvent {
     pub is_composing: bool,
     pub location: Location,
 }
+
+/// The modifiers that are held down, rather than toggled like caps lock.
+const HELD: Modifiers = Modifiers::SHIFT
+    .union(Modifiers::CONTROL)
+    .union(Modifiers::ALT)
+    .union(Modifiers::META);
+
+/// Shorthand checks for [`Modifiers`], for use in key handlers.
+///
+/// Lock states (caps lock, num lock, ...) are ignored, so `is_none` still
+/// holds with caps lock on.
+pub trait ModifiersExt {
+    fn is_ctrl(&self) -> bool;
+    fn is_alt(&self) -> bool;
+    fn is_shift(&self) -> bool;
+    /// No modifier is held.
+    fn is_none(&self) -> bool;
+    /// `flag` is held and nothing else is.
+    fn only(&self, flag: Modifiers) -> bool;
+}
+
+impl ModifiersExt for Modifiers {
+    fn is_ctrl(&self) -> bool {
+        self.contains(Self::CONTROL)
+    }
+
+    fn is_alt(&self) -> bool {
+        self.contains(Self::ALT)
+    }
+
+    fn is_shift(&self) -> bool {
+        self.contains(Self::SHIFT)
+    }
+
+    fn is_none(&self) -> bool {
+        !self.intersects(HELD)
+    }
+
+    fn only(&self, flag: Modifiers) -> bool {
+        self.intersection(HELD) == flag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_none_ignores_locks() {
+        assert!(Modifiers::empty().is_none());
+        assert!((Modifiers::CAPS_LOCK | Modifiers::NUM_LOCK).is_none());
+
+        assert!(!Modifiers::SHIFT.is_none());
+        assert!(!(Modifiers::META | Modifiers::CAPS_LOCK).is_none());
+    }
+
+    #[test]
+    fn only_ignores_locks() {
+        assert!(Modifiers::CONTROL.only(Modifiers::CONTROL));
+        assert!((Modifiers::CONTROL | Modifiers::CAPS_LOCK).only(Modifiers::CONTROL));
+        assert!(
+            (Modifiers::CONTROL | Modifiers::SHIFT).only(Modifiers::CONTROL | Modifiers::SHIFT)
+        );
+
+        assert!(!(Modifiers::CONTROL | Modifiers::SHIFT).only(Modifiers::CONTROL));
+        assert!(!Modifiers::empty().only(Modifiers::CONTROL));
+        assert!(!Modifiers::CAPS_LOCK.only(Modifiers::CAPS_LOCK));
+    }
+}
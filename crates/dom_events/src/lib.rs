@@ -1,5 +1,7 @@
+mod clipboard;
 mod composition;
 mod custom;
+mod drag;
 mod event;
 mod focus;
 mod input;
@@ -9,8 +11,10 @@ mod pointer;
 mod units;
 mod wheel;
 
+pub use clipboard::*;
 pub use composition::*;
 pub use custom::*;
+pub use drag::*;
 pub use event::*;
 pub use focus::*;
 pub use input::*;
@@ -19,4 +23,3 @@ pub use mouse::*;
 pub use pointer::*;
 pub use units::*;
 pub use wheel::*;
-
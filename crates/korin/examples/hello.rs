@@ -1,4 +1,5 @@
 use std::io;
+use std::sync::Arc;
 
 use capsule_corp::CapsuleDocument;
 use capsule_corp::ComputedStyle;
@@ -20,10 +21,10 @@ fn main() -> io::Result<()> {
     // TODO: We.. probably shouldn't have to do this? This shouldn't really be relevant for paint should it?
     document.set_style(
         root,
-        ComputedStyle {
+        Arc::new(ComputedStyle {
             display: Display::Block,
             ..Default::default()
-        },
+        }),
         CustomPropertiesMap::default(),
     );
 
@@ -1,13 +1,31 @@
-use std::sync::atomic::{AtomicU64, Ordering};
-
-use capsule_corp::{Bulma, ComputedStyle, CustomPropertiesMap, ElementState, Layout};
+use std::{
+    collections::{HashSet, VecDeque},
+    ptr::NonNull,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use capsule_corp::{
+    ANIMATABLE, Bulma, ComputedStyle, CustomPropertiesMap, ElementState, Layout, RestyleHint,
+    restyle_subtree,
+};
 use ginyu_force::Pose;
 use indextree::{Arena, NodeId};
+use rustc_hash::FxHashMap;
 use slotmap::SlotMap;
 use smallvec::SmallVec;
 use tracing::{debug, trace};
 
-use crate::{Event, EventHandler, HandlerId, element::Element, node::Node};
+use crate::{
+    Event, EventHandler, HandlerId,
+    element::Element,
+    events::{ClickTracker, Clock, DragOrigin, EventLog, SavedFocus},
+    node::{Node, NodeData},
+    transition::Transitions,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DocumentId(pub(crate) u64);
@@ -32,11 +50,20 @@ pub struct Document {
     pub(crate) arena: Arena<Node>,
     pub(crate) root: NodeId,
     stylist: Bulma,
+    transitions: Transitions,
 
     handlers: SlotMap<HandlerId, EventHandler>,
     focused: Option<NodeId>,
     hovered: Option<NodeId>,
     active_node: Option<NodeId>,
+    pub(crate) focus_wrap: bool,
+    pub(crate) focus_scope: Option<NodeId>,
+    pub(crate) focus_history: Vec<Option<SavedFocus>>,
+    pub(crate) click_tracker: ClickTracker,
+    pub(crate) drag_origin: Option<DragOrigin>,
+
+    pub(crate) event_log: Option<EventLog>,
+    pub(crate) clock: Clock,
 }
 
 impl Document {
@@ -52,11 +79,20 @@ impl Document {
             arena,
             root,
             stylist: Bulma::new(),
+            transitions: Transitions::default(),
 
             handlers: SlotMap::default(),
             focused: None,
             hovered: None,
             active_node: None,
+            focus_wrap: true,
+            focus_scope: None,
+            focus_history: Vec::new(),
+            click_tracker: ClickTracker::default(),
+            drag_origin: None,
+
+            event_log: None,
+            clock: Clock::default(),
         }
     }
 
@@ -87,6 +123,66 @@ impl Document {
         self.arena.get_mut(id).map(indextree::Node::get_mut)
     }
 
+    /// Number of live nodes, including the root and any detached subtrees.
+    #[must_use]
+    pub fn node_count(&self) -> usize {
+        self.arena.iter().filter(|node| !node.is_removed()).count()
+    }
+
+    /// Every live node in storage order, ignoring the tree structure.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> + '_ {
+        self.arena
+            .iter()
+            .filter(|node| !node.is_removed())
+            .map(indextree::Node::get)
+    }
+
+    /// Mutable version of [`Self::nodes`].
+    pub fn nodes_mut(&mut self) -> impl Iterator<Item = &mut Node> + '_ {
+        self.arena
+            .iter_mut()
+            .filter(|node| !node.is_removed())
+            .map(indextree::Node::get_mut)
+    }
+
+    /// Whether the root is the only node left.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes().nth(1).is_none()
+    }
+
+    /// Remove every node except the root, detached subtrees included, and
+    /// every event handler, along with any state that pointed at the removed
+    /// nodes: focus and its history, hover, the active node, a drag in
+    /// progress, click sequences and running transitions. The stylesheets,
+    /// event log and clock are kept.
+    pub fn clear(&mut self) {
+        let root = self.root;
+        let tops: SmallVec<[NodeId; 8]> = self
+            .arena
+            .iter()
+            .filter(|node| !node.is_removed() && node.parent().is_none_or(|parent| parent == root))
+            .filter_map(|node| self.arena.get_node_id(node))
+            .filter(|&id| id != root)
+            .collect();
+        for id in tops {
+            id.remove_subtree(&mut self.arena);
+        }
+
+        self.handlers.clear();
+
+        self.focused = None;
+        self.hovered = None;
+        self.active_node = None;
+        self.focus_scope = None;
+        self.focus_history.clear();
+        self.drag_origin = None;
+        self.click_tracker.reset();
+        self.transitions.clear();
+
+        debug!(doc = %self.id, "cleared");
+    }
+
     pub fn create_element(&mut self, tag: Pose) -> NodeId {
         let element = Element::new(tag);
         let id = self.arena.new_node(Node::element(element));
@@ -108,6 +204,13 @@ impl Document {
         id
     }
 
+    pub fn create_raw_text(&mut self, content: impl Into<String>) -> NodeId {
+        let content = content.into();
+        let id = self.arena.new_node(Node::raw_text(content.clone()));
+        trace!(doc = %self.id, node = ?id, content = %content, "created raw text node");
+        id
+    }
+
     pub fn create_marker(&mut self) -> NodeId {
         let id = self.arena.new_node(Node::marker());
         trace!(doc = %self.id, node = ?id, "created marker node");
@@ -123,6 +226,10 @@ impl Document {
             self.arena.get(child).is_some(),
             "child {child:?} does not exist"
         );
+        debug_assert!(
+            !parent.ancestors(&self.arena).any(|id| id == child),
+            "cannot move {child:?} under its own descendant {parent:?}"
+        );
 
         trace!(doc = %self.id, parent = ?parent, child = ?child, "append_child");
         parent.append(child, &mut self.arena);
@@ -137,6 +244,10 @@ impl Document {
             self.arena.get(child).is_some(),
             "child {child:?} does not exist"
         );
+        debug_assert!(
+            !parent.ancestors(&self.arena).any(|id| id == child),
+            "cannot move {child:?} under its own descendant {parent:?}"
+        );
 
         trace!(doc = %self.id, parent = ?parent, child = ?child, "prepend_child");
         parent.prepend(child, &mut self.arena);
@@ -151,6 +262,10 @@ impl Document {
             self.arena.get(new_node).is_some(),
             "new_node {new_node:?} does not exist"
         );
+        debug_assert!(
+            !sibling.ancestors(&self.arena).any(|id| id == new_node),
+            "cannot move {new_node:?} beside its own descendant {sibling:?}"
+        );
 
         trace!(doc = %self.id, sibling = ?sibling, new_node = ?new_node, "insert_before");
         sibling.insert_before(new_node, &mut self.arena);
@@ -165,11 +280,77 @@ impl Document {
             self.arena.get(new_node).is_some(),
             "new_node {new_node:?} does not exist"
         );
+        debug_assert!(
+            !sibling.ancestors(&self.arena).any(|id| id == new_node),
+            "cannot move {new_node:?} beside its own descendant {sibling:?}"
+        );
 
         trace!(doc = %self.id, sibling = ?sibling, new_node = ?new_node, "insert_after");
         sibling.insert_after(new_node, &mut self.arena);
     }
 
+    /// Insert `child` so it ends up at `index` among `parent`'s children,
+    /// appending if `index` is past the end.
+    pub fn insert_child_at(&mut self, parent: NodeId, child: NodeId, index: usize) {
+        debug_assert!(
+            self.arena.get(parent).is_some(),
+            "parent {parent:?} does not exist"
+        );
+        debug_assert!(
+            self.arena.get(child).is_some(),
+            "child {child:?} does not exist"
+        );
+        debug_assert!(
+            !parent.ancestors(&self.arena).any(|id| id == child),
+            "cannot move {child:?} under its own descendant {parent:?}"
+        );
+
+        trace!(doc = %self.id, parent = ?parent, child = ?child, index, "insert_child_at");
+
+        // Detach first so moving a child later within the same parent lands at `index`
+        child.detach(&mut self.arena);
+
+        match parent.children(&self.arena).nth(index) {
+            Some(sibling) => sibling.insert_before(child, &mut self.arena),
+            None => parent.append(child, &mut self.arena),
+        }
+    }
+
+    /// Deep copy `id` and everything under it into fresh, unattached nodes,
+    /// returning the copy of `id`.
+    ///
+    /// Event handlers are not carried over, since handler ids belong to the
+    /// node they were registered on. Styles and layout are left to be
+    /// recomputed once the copy is attached.
+    pub fn clone_subtree(&mut self, id: NodeId) -> NodeId {
+        debug_assert!(self.arena.get(id).is_some(), "node {id:?} does not exist");
+        debug_assert!(id != self.root, "cannot clone root node");
+
+        let node = match &self.arena[id].get().data {
+            NodeData::Element(element) => Node::element(Element {
+                handlers: FxHashMap::default(),
+                ..element.clone()
+            }),
+            NodeData::Text(content) => Node::text(content.clone()),
+            NodeData::RawText(content) => Node::raw_text(content.clone()),
+            NodeData::Marker | NodeData::Root => Node::marker(),
+        };
+        let copy = self.arena.new_node(node);
+
+        let children: SmallVec<[NodeId; 8]> = self.children(id).collect();
+        for child in children {
+            let child_copy = self.clone_subtree(child);
+            copy.append(child_copy, &mut self.arena);
+        }
+
+        trace!(doc = %self.id, node = ?id, copy = ?copy, "clone_subtree");
+        copy
+    }
+
+    /// Unlink `id` from its parent, keeping it and its descendants alive.
+    ///
+    /// The subtree is orphaned until it is attached again with one of the
+    /// insert methods. Use [`Self::remove`] to drop it instead.
     pub fn detach(&mut self, id: NodeId) {
         debug_assert!(self.arena.get(id).is_some(), "node {id:?} does not exist");
         trace!(doc = %self.id, node = ?id, "detach");
@@ -180,8 +361,29 @@ impl Document {
         debug_assert!(self.arena.get(id).is_some(), "node {id:?} does not exist");
         debug_assert!(id != self.root, "cannot remove root node");
 
+        let refocus = self
+            .focused
+            .filter(|&focused| focused == id || self.is_ancestor_of(id, focused))
+            .map(|focused| self.focus_successor(focused, id));
+
+        // The ids get reused, so a transition left behind would carry on
+        // against whatever node takes the slot next
+        if !self.transitions.is_empty() {
+            for node in id.descendants(&self.arena) {
+                self.transitions.remove(node);
+            }
+        }
+
         debug!(doc = %self.id, node = ?id, "remove subtree");
         id.remove_subtree(&mut self.arena);
+
+        if let Some(next) = refocus {
+            self.focused = None;
+
+            if let Some(next) = next {
+                self.focus(next);
+            }
+        }
     }
 
     #[must_use]
@@ -197,10 +399,56 @@ impl Document {
         id.ancestors(&self.arena).skip(1)
     }
 
+    /// Number of ancestors above `id`, so the root and detached nodes are 0.
+    #[must_use]
+    pub fn depth(&self, id: NodeId) -> usize {
+        self.ancestors(id).count()
+    }
+
+    /// Whether `ancestor` is a strict ancestor of `descendant`.
+    #[must_use]
+    pub fn is_ancestor_of(&self, ancestor: NodeId, descendant: NodeId) -> bool {
+        self.ancestors(descendant).any(|id| id == ancestor)
+    }
+
+    /// Deepest node that is `a` or one of its ancestors and also `b` or one of
+    /// its ancestors, `None` if the two are in disconnected subtrees.
+    #[must_use]
+    pub fn common_ancestor(&self, a: NodeId, b: NodeId) -> Option<NodeId> {
+        let path: HashSet<_> = a.ancestors(&self.arena).collect();
+        b.ancestors(&self.arena).find(|id| path.contains(id))
+    }
+
     pub fn descendants(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
         id.descendants(&self.arena).skip(1)
     }
 
+    /// Like [`Self::descendants`], but level by level instead of depth-first.
+    pub fn descendants_breadth_first(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut queue: VecDeque<_> = self.children(id).collect();
+
+        std::iter::from_fn(move || {
+            let next = queue.pop_front()?;
+            queue.extend(self.children(next));
+            Some(next)
+        })
+    }
+
+    /// First descendant of `start`, in depth-first order, whose node matches
+    /// `predicate`.
+    pub fn find(&self, start: NodeId, mut predicate: impl FnMut(&Node) -> bool) -> Option<NodeId> {
+        self.descendants(start)
+            .find(|&id| self.get(id).is_some_and(&mut predicate))
+    }
+
+    /// Every descendant of `start` whose node matches `predicate`, in
+    /// depth-first order.
+    pub fn find_all(&self, start: NodeId, mut predicate: impl FnMut(&Node) -> bool) -> Vec<NodeId> {
+        self.descendants(start)
+            .filter(|&id| self.get(id).is_some_and(&mut predicate))
+            .collect()
+    }
+
     pub fn following_siblings(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
         id.following_siblings(&self.arena).skip(1)
     }
@@ -229,6 +477,14 @@ impl Document {
         self.arena.get(id)?.previous_sibling()
     }
 
+    /// Position of `id` among its parent's children, `None` for the root or
+    /// detached nodes.
+    #[must_use]
+    pub fn index_in_parent(&self, id: NodeId) -> Option<usize> {
+        self.parent(id)?;
+        Some(self.preceding_siblings(id).count())
+    }
+
     pub fn add_event_handler<F>(&mut self, callback: F) -> HandlerId
     where
         F: FnMut(&mut Event) + 'static,
@@ -336,6 +592,36 @@ impl Document {
             self.set_active_node(None);
         }
     }
+
+    /// Advances running style transitions by `dt`. Returns whether any are
+    /// still going, so callers know to keep drawing frames.
+    pub fn tick(&mut self, dt: Duration) -> bool {
+        for (id, style) in self.transitions.tick(dt) {
+            let Some(node) = self.get_mut(id) else {
+                continue;
+            };
+
+            let inherited_changed = node.style.as_ref().is_none_or(|shown| {
+                ANIMATABLE
+                    .iter()
+                    .any(|&property| property.inherited() && shown.differs(&style, property))
+            });
+            node.style = Some(style);
+
+            // Children inherit what's on screen, so they have to follow along
+            if inherited_changed {
+                restyle_subtree(self, id, RestyleHint::RESTYLE_DESCENDANTS);
+            }
+        }
+
+        !self.transitions.is_empty()
+    }
+
+    /// Whether any style transitions are still running.
+    #[must_use]
+    pub fn is_transitioning(&self) -> bool {
+        !self.transitions.is_empty()
+    }
 }
 
 impl Default for Document {
@@ -387,7 +673,7 @@ impl capsule_corp::CapsuleDocument for Document {
     }
 
     fn computed_style(&self, id: Self::NodeId) -> Option<&ComputedStyle> {
-        self.get(id)?.style.as_ref()
+        self.get(id)?.style.as_deref()
     }
 
     fn custom_properties(&self, id: Self::NodeId) -> Option<&CustomPropertiesMap> {
@@ -397,13 +683,17 @@ impl capsule_corp::CapsuleDocument for Document {
     fn set_style(
         &mut self,
         node: Self::NodeId,
-        style: ComputedStyle,
+        style: Arc<ComputedStyle>,
         custom_properties: CustomPropertiesMap,
     ) {
-        if let Some(n) = self.get_mut(node) {
-            n.style = Some(style);
-            n.custom_properties = Some(custom_properties);
-        }
+        let Some(n) = self.arena.get_mut(node).map(indextree::Node::get_mut) else {
+            return;
+        };
+
+        // Nothing transitions into its very first style
+        let shown = n.custom_properties.is_some().then_some(n.style.as_ref());
+        n.style = Some(self.transitions.restyle(node, shown.flatten(), style));
+        n.custom_properties = Some(custom_properties);
     }
 
     fn take_stylist(&mut self) -> Bulma {
@@ -520,18 +810,33 @@ impl capsule_corp::CapsuleElement for ElementHandle {
     fn has_children(&self) -> bool {
         self.arena_node().first_child().is_some()
     }
+
+    fn first_child(&self) -> Option<Self> {
+        let mut current = self.arena_node().first_child();
+        while let Some(child_id) = current {
+            if let Some(elem_ref) = Self::new(child_id, self.arena()) {
+                return Some(elem_ref);
+            }
+            current = self.arena().get(child_id)?.next_sibling();
+        }
+        None
+    }
+
+    fn identity(&self) -> NonNull<()> {
+        NonNull::from(self.arena_node()).cast()
+    }
 }
 
 impl capsule_corp::CapsuleNode for Node {
     fn computed_style(&self) -> Option<&ComputedStyle> {
-        self.style.as_ref()
+        self.style.as_deref()
     }
 
     fn custom_properties(&self) -> Option<&CustomPropertiesMap> {
         self.custom_properties.as_ref()
     }
 
-    fn set_style(&mut self, style: ComputedStyle, custom_properties: CustomPropertiesMap) {
+    fn set_style(&mut self, style: Arc<ComputedStyle>, custom_properties: CustomPropertiesMap) {
         self.style = Some(style);
         self.custom_properties = Some(custom_properties);
     }
@@ -559,6 +864,10 @@ impl capsule_corp::CapsuleNode for Node {
     fn text_content(&self) -> Option<&str> {
         self.as_text()
     }
+
+    fn is_preformatted(&self) -> bool {
+        self.is_raw_text()
+    }
 }
 
 #[cfg(test)]
@@ -592,9 +901,197 @@ mod tests {
         doc.append_child(div, span);
         doc.append_child(span, text);
 
+        assert_eq!(doc.node_count(), 4);
+
         doc.remove(div);
 
         assert_eq!(doc.children(doc.root()).count(), 0);
+        assert_eq!(doc.node_count(), 1);
+    }
+
+    #[test]
+    fn append_child_moves_subtree() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+        let text = doc.create_text("hello");
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(doc.root(), b);
+        doc.append_child(a, text);
+
+        doc.append_child(b, a);
+
+        assert_eq!(doc.children(doc.root()).collect::<Vec<_>>(), vec![b]);
+        assert_eq!(doc.children(b).collect::<Vec<_>>(), vec![a]);
+        assert_eq!(
+            doc.ancestors(text).collect::<Vec<_>>(),
+            vec![a, b, doc.root()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "under its own descendant")]
+    fn append_child_rejects_cycle() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(a, b);
+
+        doc.append_child(b, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "under its own descendant")]
+    fn prepend_child_rejects_cycle() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(a, b);
+
+        doc.prepend_child(b, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "beside its own descendant")]
+    fn insert_before_rejects_cycle() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(a, b);
+
+        doc.insert_before(b, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "beside its own descendant")]
+    fn insert_after_rejects_cycle() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(a, b);
+
+        doc.insert_after(b, a);
+    }
+
+    #[test]
+    fn clone_subtree_is_independent() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let span = doc.create_element(pose!("span"));
+        let text = doc.create_text("hello");
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, span);
+        doc.append_child(span, text);
+
+        let copy = doc.clone_subtree(div);
+        assert_eq!(doc.parent(copy), None);
+
+        let copied: Vec<_> = doc.descendants(copy).collect();
+        assert_eq!(copied.len(), 2);
+        assert_eq!(doc.parent(copied[1]), Some(copied[0]));
+        assert_eq!(doc.parent(copied[0]), Some(copy));
+
+        let copy_text = copied[1];
+        if let Some(NodeData::Text(content)) = doc.get_mut(copy_text).map(|n| &mut n.data) {
+            content.push_str(" world");
+        }
+        assert_eq!(doc.get(text).and_then(Node::as_text), Some("hello"));
+        assert_eq!(
+            doc.get(copy_text).and_then(Node::as_text),
+            Some("hello world")
+        );
+
+        doc.remove(span);
+        assert_eq!(doc.descendants(copy).count(), 2);
+    }
+
+    #[test]
+    fn detach_keeps_subtree() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let span = doc.create_element(pose!("span"));
+        let text = doc.create_text("hello");
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, span);
+        doc.append_child(span, text);
+
+        doc.detach(span);
+
+        assert_eq!(doc.children(div).count(), 0);
+        assert_eq!(doc.parent(span), None);
+        assert_eq!(doc.descendants(span).collect::<Vec<_>>(), vec![text]);
+
+        doc.append_child(doc.root(), span);
+        assert_eq!(
+            doc.ancestors(text).collect::<Vec<_>>(),
+            vec![span, doc.root()]
+        );
+    }
+
+    #[test]
+    fn clear_keeps_root() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let text = doc.create_text("hello");
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, text);
+        doc.clear();
+
+        assert_eq!(doc.node_count(), 1);
+        assert!(doc.get(doc.root()).is_some());
+        assert!(doc.get(div).is_none());
+    }
+
+    #[test]
+    fn clear_drops_detached_nodes_and_handlers() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let detached = doc.create_element(pose!("span"));
+        let marker = doc.create_marker();
+        doc.append_child(doc.root(), div);
+        doc.append_child(detached, marker);
+        assert!(!doc.is_empty());
+
+        let handler = doc.add_event_handler(|_| {});
+        doc.register_event_handler(div, pose!("click"), handler);
+        doc.clear();
+
+        assert!(doc.is_empty());
+        assert!(doc.get(detached).is_none());
+        assert!(doc.get(marker).is_none());
+        assert!(!doc.has_event_handler(handler));
+    }
+
+    #[test]
+    fn nodes_mut_visits_everything() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let detached = doc.create_text("hello");
+        let removed = doc.create_text("bye");
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, removed);
+        doc.remove(removed);
+
+        for node in doc.nodes_mut() {
+            node.needs_layout = false;
+        }
+
+        assert_eq!(doc.nodes().count(), 3);
+        assert!(doc.nodes().all(|node| !node.needs_layout));
+        assert_eq!(doc.get(detached).map(|node| node.needs_layout), Some(false));
     }
 
     #[test]
@@ -611,6 +1108,33 @@ mod tests {
         assert_eq!(doc.children(doc.root()).collect::<Vec<_>>(), vec![a, b, c]);
     }
 
+    #[test]
+    fn insert_child_at_index() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+        let c = doc.create_element(pose!("c"));
+        let d = doc.create_element(pose!("d"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(doc.root(), c);
+        doc.insert_child_at(doc.root(), b, 1);
+        doc.insert_child_at(doc.root(), d, 10);
+
+        assert_eq!(
+            doc.children(doc.root()).collect::<Vec<_>>(),
+            vec![a, b, c, d]
+        );
+        assert_eq!(doc.parent(b), Some(doc.root()));
+
+        // Moving within the same parent
+        doc.insert_child_at(doc.root(), a, 2);
+        assert_eq!(
+            doc.children(doc.root()).collect::<Vec<_>>(),
+            vec![b, c, a, d]
+        );
+    }
+
     #[test]
     fn traversal() {
         let mut doc = Document::new();
@@ -627,11 +1151,90 @@ mod tests {
         let desc: Vec<_> = doc.descendants(div).collect();
         assert_eq!(desc, vec![span1, text, span2]);
 
+        let bfs: Vec<_> = doc.descendants_breadth_first(div).collect();
+        assert_eq!(bfs, vec![span1, span2, text]);
+
         let anc: Vec<_> = doc.ancestors(text).collect();
         assert_eq!(anc, vec![span1, div, doc.root()]);
 
         assert_eq!(doc.next_sibling(span1), Some(span2));
         assert_eq!(doc.prev_sibling(span2), Some(span1));
+
+        assert_eq!(doc.depth(doc.root()), 0);
+        assert_eq!(doc.depth(text), 3);
+
+        assert!(doc.is_ancestor_of(div, text));
+        assert!(doc.is_ancestor_of(doc.root(), text));
+        assert!(!doc.is_ancestor_of(text, div));
+        assert!(!doc.is_ancestor_of(span2, text));
+        assert!(!doc.is_ancestor_of(text, text));
+    }
+
+    #[test]
+    fn sibling_edges() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+        let c = doc.create_element(pose!("c"));
+        let detached = doc.create_element(pose!("d"));
+
+        doc.append_child(doc.root(), a);
+        doc.append_child(doc.root(), b);
+        doc.append_child(doc.root(), c);
+
+        assert_eq!(doc.prev_sibling(a), None);
+        assert_eq!(doc.next_sibling(c), None);
+        assert_eq!(doc.next_sibling(doc.root()), None);
+
+        assert_eq!(doc.index_in_parent(a), Some(0));
+        assert_eq!(doc.index_in_parent(b), Some(1));
+        assert_eq!(doc.index_in_parent(c), Some(2));
+        assert_eq!(doc.index_in_parent(doc.root()), None);
+        assert_eq!(doc.index_in_parent(detached), None);
+    }
+
+    #[test]
+    fn find_by_predicate() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let span = doc.create_element(pose!("span"));
+        let first = doc.create_text("hello");
+        let second = doc.create_text("hello");
+        let detached = doc.create_text("hello");
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, span);
+        doc.append_child(span, first);
+        doc.append_child(div, second);
+
+        let is_hello = |node: &Node| node.as_text() == Some("hello");
+
+        assert_eq!(doc.find(doc.root(), is_hello), Some(first));
+        assert_eq!(doc.find_all(doc.root(), is_hello), vec![first, second]);
+        assert!(!doc.find_all(doc.root(), is_hello).contains(&detached));
+        assert_eq!(doc.find(span, |node| node.as_element().is_some()), None);
+    }
+
+    #[test]
+    fn common_ancestor() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        let span1 = doc.create_element(pose!("span"));
+        let span2 = doc.create_element(pose!("span"));
+        let text = doc.create_text("hello");
+        let detached = doc.create_element(pose!("p"));
+
+        doc.append_child(doc.root(), div);
+        doc.append_child(div, span1);
+        doc.append_child(div, span2);
+        doc.append_child(span1, text);
+
+        assert_eq!(doc.common_ancestor(text, span2), Some(div));
+        assert_eq!(doc.common_ancestor(span2, text), Some(div));
+        assert_eq!(doc.common_ancestor(div, text), Some(div));
+        assert_eq!(doc.common_ancestor(text, div), Some(div));
+        assert_eq!(doc.common_ancestor(text, text), Some(text));
+        assert_eq!(doc.common_ancestor(text, detached), None);
     }
 
     #[test]
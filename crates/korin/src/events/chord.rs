@@ -0,0 +1,274 @@
+use std::time::Duration;
+
+use dom_events::{Key, KeyboardEvent, Modifiers};
+
+/// How long the matcher waits for the next key of a chord before giving up on
+/// the sequence.
+pub const CHORD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A single key press along with the modifiers held for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyStroke {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl KeyStroke {
+    /// Only shift, ctrl, alt and meta are kept. Shift is dropped for
+    /// characters, since it's already reflected in the character itself.
+    #[must_use]
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        let mut modifiers = modifiers
+            .intersection(Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META);
+
+        if matches!(key, Key::Character(_)) {
+            modifiers.remove(Modifiers::SHIFT);
+        }
+
+        Self { key, modifiers }
+    }
+
+    #[must_use]
+    pub fn char(c: char) -> Self {
+        Self::new(Key::Character(c.to_string()), Modifiers::empty())
+    }
+
+    #[must_use]
+    pub fn ctrl(c: char) -> Self {
+        Self::new(Key::Character(c.to_string()), Modifiers::CONTROL)
+    }
+}
+
+impl From<&KeyboardEvent> for KeyStroke {
+    fn from(event: &KeyboardEvent) -> Self {
+        Self::new(event.key.clone(), event.modifiers)
+    }
+}
+
+/// A sequence of key strokes, like `g g` or `Ctrl+K Ctrl+C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChord(Vec<KeyStroke>);
+
+impl KeyChord {
+    #[must_use]
+    pub fn new(strokes: impl IntoIterator<Item = KeyStroke>) -> Self {
+        Self(strokes.into_iter().collect())
+    }
+
+    #[must_use]
+    pub fn strokes(&self) -> &[KeyStroke] {
+        &self.0
+    }
+}
+
+/// Matches incoming key strokes against a set of registered chords.
+///
+/// Strokes accumulate until they spell out a whole chord, which is then
+/// reported and the sequence starts over. A stroke that can't continue any
+/// chord abandons the sequence and is retried as the start of a new one, as
+/// is a stroke arriving more than the timeout after the previous one.
+#[derive(Debug)]
+pub struct ChordMatcher<T> {
+    chords: Vec<(KeyChord, T)>,
+    pending: Vec<KeyStroke>,
+    last_stroke: Option<Duration>,
+    timeout: Duration,
+}
+
+impl<T: Clone> ChordMatcher<T> {
+    #[must_use]
+    pub const fn new(timeout: Duration) -> Self {
+        Self {
+            chords: Vec::new(),
+            pending: Vec::new(),
+            last_stroke: None,
+            timeout,
+        }
+    }
+
+    /// Report `id` whenever `chord` is typed. When one chord is a prefix of
+    /// another, the shorter one wins as soon as it completes.
+    pub fn register(&mut self, chord: KeyChord, id: T) {
+        debug_assert!(!chord.0.is_empty(), "cannot register an empty chord");
+
+        self.chords.push((chord, id));
+    }
+
+    /// Feed the next stroke, returning the id of the chord it completes.
+    ///
+    /// `at` is when the stroke happened, normally [`Document::now`] so that
+    /// a clock set with [`Document::set_clock`] applies.
+    ///
+    /// [`Document::now`]: crate::Document::now
+    /// [`Document::set_clock`]: crate::Document::set_clock
+    pub fn feed(&mut self, stroke: KeyStroke, at: Duration) -> Option<T> {
+        if self
+            .last_stroke
+            .is_some_and(|last| at.saturating_sub(last) > self.timeout)
+        {
+            self.pending.clear();
+        }
+
+        self.last_stroke = Some(at);
+
+        let fresh = self.pending.is_empty();
+        self.pending.push(stroke);
+
+        match self.step() {
+            Step::Complete(id) => return Some(id),
+            Step::Partial => return None,
+            Step::Mismatch if fresh => {
+                self.pending.clear();
+                return None;
+            }
+            Step::Mismatch => {}
+        }
+
+        // Abandoned, give the stroke a chance to start a new sequence
+        self.pending.drain(..self.pending.len() - 1);
+
+        match self.step() {
+            Step::Complete(id) => Some(id),
+            Step::Partial => None,
+            Step::Mismatch => {
+                self.pending.clear();
+                None
+            }
+        }
+    }
+
+    fn step(&mut self) -> Step<T> {
+        let pending = self.pending.as_slice();
+
+        if let Some((_, id)) = self.chords.iter().find(|(chord, _)| chord.0 == pending) {
+            let id = id.clone();
+            self.pending.clear();
+
+            return Step::Complete(id);
+        }
+
+        if self
+            .chords
+            .iter()
+            .any(|(chord, _)| chord.0.starts_with(pending))
+        {
+            Step::Partial
+        } else {
+            Step::Mismatch
+        }
+    }
+
+    /// Whether a chord has been started but not finished.
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Drop the sequence in progress.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.last_stroke = None;
+    }
+}
+
+enum Step<T> {
+    Complete(T),
+    Partial,
+    Mismatch,
+}
+
+impl<T: Clone> Default for ChordMatcher<T> {
+    fn default() -> Self {
+        Self::new(CHORD_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Action {
+        Top,
+        Comment,
+        Delete,
+    }
+
+    fn matcher() -> ChordMatcher<Action> {
+        let mut matcher = ChordMatcher::default();
+        matcher.register(
+            KeyChord::new([KeyStroke::char('g'), KeyStroke::char('g')]),
+            Action::Top,
+        );
+        matcher.register(
+            KeyChord::new([KeyStroke::ctrl('k'), KeyStroke::ctrl('c')]),
+            Action::Comment,
+        );
+        matcher.register(KeyChord::new([KeyStroke::char('x')]), Action::Delete);
+        matcher
+    }
+
+    #[test]
+    fn completes_sequence() {
+        let mut matcher = matcher();
+        let start = Duration::ZERO;
+
+        assert_eq!(matcher.feed(KeyStroke::ctrl('k'), start), None);
+        assert!(matcher.is_pending());
+        assert_eq!(
+            matcher.feed(KeyStroke::ctrl('c'), start + MS),
+            Some(Action::Comment)
+        );
+        assert!(!matcher.is_pending());
+
+        assert_eq!(
+            matcher.feed(KeyStroke::char('x'), start + MS * 2),
+            Some(Action::Delete)
+        );
+    }
+
+    #[test]
+    fn mismatch_abandons_and_retries() {
+        let mut matcher = matcher();
+        let start = Duration::ZERO;
+
+        matcher.feed(KeyStroke::char('g'), start);
+        // `g x` isn't a chord, but `x` on its own is
+        assert_eq!(
+            matcher.feed(KeyStroke::char('x'), start + MS),
+            Some(Action::Delete)
+        );
+
+        matcher.feed(KeyStroke::char('g'), start + MS * 2);
+        assert_eq!(matcher.feed(KeyStroke::char('q'), start + MS * 3), None);
+        assert!(!matcher.is_pending());
+    }
+
+    #[test]
+    fn timeout_abandons_sequence() {
+        let mut matcher = matcher();
+        let start = Duration::ZERO;
+
+        matcher.feed(KeyStroke::char('g'), start);
+        assert_eq!(matcher.feed(KeyStroke::char('g'), start + MS * 1500), None);
+        assert!(matcher.is_pending());
+
+        assert_eq!(
+            matcher.feed(KeyStroke::char('g'), start + MS * 1600),
+            Some(Action::Top)
+        );
+    }
+
+    #[test]
+    fn shift_is_folded_into_characters() {
+        assert_eq!(
+            KeyStroke::new(
+                Key::Character("G".into()),
+                Modifiers::SHIFT | Modifiers::CAPS_LOCK
+            ),
+            KeyStroke::new(Key::Character("G".into()), Modifiers::empty())
+        );
+    }
+}
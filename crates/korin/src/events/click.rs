@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use capsule_corp::Point;
+use dom_events::MouseButton;
+
+use crate::Document;
+
+/// How long after a press the next one still counts towards the same click
+/// sequence.
+pub const DOUBLE_CLICK_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// How many cells the cursor may drift between presses of a click sequence.
+pub const DOUBLE_CLICK_DISTANCE: u16 = 1;
+
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    position: Point,
+    at: Duration,
+    count: u32,
+}
+
+/// Counts consecutive presses of each mouse button, so repeated clicks can be
+/// reported as double (or triple, ...) clicks.
+#[derive(Debug)]
+pub struct ClickTracker {
+    threshold: Duration,
+    last: [Option<LastClick>; 5],
+}
+
+impl ClickTracker {
+    #[must_use]
+    pub const fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            last: [None; 5],
+        }
+    }
+
+    /// Record a press of `button` and return its click count: 1 for a single
+    /// click, 2 for a double click and so on.
+    ///
+    /// A press continues the previous sequence when it lands within the
+    /// threshold and [`DOUBLE_CLICK_DISTANCE`] of the last press of the same
+    /// button, otherwise a new sequence starts. `at` is the time of the
+    /// press on the document's clock, see [`Document::now`].
+    pub fn press(&mut self, button: MouseButton, position: Point, at: Duration) -> u32 {
+        let slot = &mut self.last[button as usize];
+
+        let count = match *slot {
+            Some(last)
+                if at.saturating_sub(last.at) <= self.threshold
+                    && last.position.x.abs_diff(position.x) <= DOUBLE_CLICK_DISTANCE
+                    && last.position.y.abs_diff(position.y) <= DOUBLE_CLICK_DISTANCE =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        };
+
+        *slot = Some(LastClick {
+            position,
+            at,
+            count,
+        });
+
+        count
+    }
+
+    /// Forget every click sequence in progress.
+    pub const fn reset(&mut self) {
+        self.last = [None; 5];
+    }
+}
+
+impl Default for ClickTracker {
+    fn default() -> Self {
+        Self::new(DOUBLE_CLICK_THRESHOLD)
+    }
+}
+
+impl Document {
+    /// Set how quickly presses must follow each other to count as a double
+    /// click. Defaults to [`DOUBLE_CLICK_THRESHOLD`].
+    pub const fn set_double_click_threshold(&mut self, threshold: Duration) {
+        self.click_tracker.threshold = threshold;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn quick_presses_count_up() {
+        let mut tracker = ClickTracker::default();
+        let start = Duration::ZERO;
+        let at = Point::new(3, 4);
+
+        assert_eq!(tracker.press(MouseButton::Primary, at, start), 1);
+        assert_eq!(tracker.press(MouseButton::Primary, at, start + MS * 200), 2);
+        assert_eq!(tracker.press(MouseButton::Primary, at, start + MS * 400), 3);
+    }
+
+    #[test]
+    fn slow_press_starts_over() {
+        let mut tracker = ClickTracker::default();
+        let start = Duration::ZERO;
+        let at = Point::new(3, 4);
+
+        tracker.press(MouseButton::Primary, at, start);
+        assert_eq!(tracker.press(MouseButton::Primary, at, start + MS * 600), 1);
+    }
+
+    #[test]
+    fn distant_press_starts_over() {
+        let mut tracker = ClickTracker::default();
+        let start = Duration::ZERO;
+
+        tracker.press(MouseButton::Primary, Point::new(3, 4), start);
+        assert_eq!(
+            tracker.press(MouseButton::Primary, Point::new(4, 5), start + MS),
+            2
+        );
+        assert_eq!(
+            tracker.press(MouseButton::Primary, Point::new(10, 5), start + MS * 2),
+            1
+        );
+    }
+
+    #[test]
+    fn buttons_are_tracked_separately() {
+        let mut tracker = ClickTracker::default();
+        let start = Duration::ZERO;
+        let at = Point::new(0, 0);
+
+        tracker.press(MouseButton::Primary, at, start);
+        assert_eq!(tracker.press(MouseButton::Secondary, at, start + MS), 1);
+        assert_eq!(tracker.press(MouseButton::Primary, at, start + MS * 2), 2);
+    }
+}
@@ -0,0 +1,157 @@
+use dom_events::{ClipboardEvent, Code, Key, KeyboardEvent, Location, Modifiers, NamedKey};
+use ratatui::crossterm::event::{
+    Event as CrosstermEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers,
+};
+
+use crate::events::EventType;
+
+/// Convert a crossterm event into the equivalent korin event, if there is one.
+#[must_use]
+pub fn from_crossterm(event: &CrosstermEvent) -> Option<EventType> {
+    match event {
+        CrosstermEvent::Key(key) => Some(key_event(key)),
+        CrosstermEvent::Paste(data) => {
+            Some(EventType::Paste(ClipboardEvent { data: data.clone() }))
+        }
+        _ => None,
+    }
+}
+
+/// Presses and repeats become `keydown`, releases become `keyup`.
+///
+/// Releases are only reported by terminals that support the kitty keyboard
+/// protocol with `REPORT_EVENT_TYPES` enabled, so `keyup` never fires on
+/// anything else.
+fn key_event(event: &KeyEvent) -> EventType {
+    let (key, code) = key_and_code(event.code);
+
+    let keyboard_event = KeyboardEvent {
+        key,
+        code,
+        modifiers: modifiers(event.modifiers),
+        repeat: event.kind == KeyEventKind::Repeat,
+        is_composing: false,
+        location: Location::Standard,
+    };
+
+    match event.kind {
+        KeyEventKind::Press | KeyEventKind::Repeat => EventType::KeyDown(keyboard_event),
+        KeyEventKind::Release => EventType::KeyUp(keyboard_event),
+    }
+}
+
+/// Crossterm doesn't report physical keys, so characters get `Code::Unidentified`.
+fn key_and_code(code: KeyCode) -> (Key, Code) {
+    let named = |key: NamedKey, code: Code| (Key::Named(key), code);
+
+    match code {
+        KeyCode::Char(c) => (Key::Character(c.to_string()), Code::Unidentified),
+        KeyCode::Backspace => named(NamedKey::Backspace, Code::Backspace),
+        KeyCode::Enter => named(NamedKey::Enter, Code::Enter),
+        KeyCode::Left => named(NamedKey::ArrowLeft, Code::ArrowLeft),
+        KeyCode::Right => named(NamedKey::ArrowRight, Code::ArrowRight),
+        KeyCode::Up => named(NamedKey::ArrowUp, Code::ArrowUp),
+        KeyCode::Down => named(NamedKey::ArrowDown, Code::ArrowDown),
+        KeyCode::Home => named(NamedKey::Home, Code::Home),
+        KeyCode::End => named(NamedKey::End, Code::End),
+        KeyCode::PageUp => named(NamedKey::PageUp, Code::PageUp),
+        KeyCode::PageDown => named(NamedKey::PageDown, Code::PageDown),
+        // Shift is already in the modifiers
+        KeyCode::Tab | KeyCode::BackTab => named(NamedKey::Tab, Code::Tab),
+        KeyCode::Delete => named(NamedKey::Delete, Code::Delete),
+        KeyCode::Insert => named(NamedKey::Insert, Code::Insert),
+        KeyCode::Esc => named(NamedKey::Escape, Code::Escape),
+        KeyCode::CapsLock => named(NamedKey::CapsLock, Code::CapsLock),
+        KeyCode::ScrollLock => named(NamedKey::ScrollLock, Code::ScrollLock),
+        KeyCode::NumLock => named(NamedKey::NumLock, Code::NumLock),
+        KeyCode::PrintScreen => named(NamedKey::PrintScreen, Code::PrintScreen),
+        KeyCode::Pause => named(NamedKey::Pause, Code::Pause),
+        KeyCode::Menu => named(NamedKey::ContextMenu, Code::ContextMenu),
+        KeyCode::F(n) => {
+            let name = format!("F{n}");
+
+            named(
+                name.parse().unwrap_or(NamedKey::Unidentified),
+                name.parse().unwrap_or(Code::Unidentified),
+            )
+        }
+        _ => named(NamedKey::Unidentified, Code::Unidentified),
+    }
+}
+
+fn modifiers(mods: KeyModifiers) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+
+    modifiers.set(Modifiers::SHIFT, mods.contains(KeyModifiers::SHIFT));
+    modifiers.set(Modifiers::CONTROL, mods.contains(KeyModifiers::CONTROL));
+    modifiers.set(Modifiers::ALT, mods.contains(KeyModifiers::ALT));
+    // Super and hyper are legacy in the spec, everything folds into meta
+    modifiers.set(
+        Modifiers::META,
+        mods.intersects(KeyModifiers::SUPER | KeyModifiers::HYPER | KeyModifiers::META),
+    );
+
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyEventState;
+
+    use dom_events::ModifiersExt;
+
+    use super::*;
+
+    fn key(code: KeyCode, kind: KeyEventKind) -> CrosstermEvent {
+        CrosstermEvent::Key(KeyEvent {
+            code,
+            modifiers: KeyModifiers::CONTROL,
+            kind,
+            state: KeyEventState::NONE,
+        })
+    }
+
+    #[test]
+    fn press_is_keydown() {
+        let event = from_crossterm(&key(KeyCode::Char('a'), KeyEventKind::Press)).expect("failed");
+
+        let EventType::KeyDown(key) = event else {
+            panic!("expected keydown, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Character("a".into()));
+        assert!(key.modifiers.only(Modifiers::CONTROL));
+        assert!(!key.repeat);
+    }
+
+    #[test]
+    fn repeat_is_keydown() {
+        let event = from_crossterm(&key(KeyCode::Enter, KeyEventKind::Repeat)).expect("failed");
+
+        let EventType::KeyDown(key) = event else {
+            panic!("expected keydown, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Named(NamedKey::Enter));
+        assert!(key.repeat);
+    }
+
+    #[test]
+    fn paste_carries_text() {
+        let event = from_crossterm(&CrosstermEvent::Paste("hello\nworld".into())).expect("failed");
+
+        let EventType::Paste(paste) = event else {
+            panic!("expected paste, got {event:?}");
+        };
+        assert_eq!(paste.data, "hello\nworld");
+    }
+
+    #[test]
+    fn release_is_keyup() {
+        let event = from_crossterm(&key(KeyCode::F(5), KeyEventKind::Release)).expect("failed");
+
+        let EventType::KeyUp(key) = event else {
+            panic!("expected keyup, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Named(NamedKey::F5));
+        assert_eq!(key.code, Code::F5);
+    }
+}
@@ -1,3 +1,4 @@
+use capsule_corp::Point;
 use dom_events::{Key, NamedKey};
 
 use crate::{Document, Event, events::EventType};
@@ -11,23 +12,41 @@ impl Document {
                 let target = self.hit_test(mouse_event.client.x, mouse_event.client.y);
                 self.update_hover(target, mouse_event);
 
-                target.map(|target| self.dispatch(target, event_type))
+                let mouse_event = mouse_event.clone();
+                let event = target.map(|target| self.dispatch(target, event_type));
+                self.drag(&mouse_event);
+
+                event
             }
             MouseDown(mouse_event) => {
                 let target = self.hit_test(mouse_event.client.x, mouse_event.client.y);
 
+                let mut mouse_event = mouse_event.clone();
+                if let Some(button) = mouse_event.button {
+                    let position = Point::new(mouse_event.client.x, mouse_event.client.y);
+                    let now = self.now();
+                    mouse_event.detail = self.click_tracker.press(button, position, now);
+                }
+
                 target.map(|target| {
-                    let event = self.dispatch(target, event_type);
+                    self.start_drag(target, &mouse_event);
+                    let event = self.dispatch(target, MouseDown(mouse_event.clone()));
 
                     if !event.default_prevented() {
                         self.set_active(target, true);
                     }
 
+                    // The second press of a sequence completes a double click
+                    if mouse_event.detail == 2 {
+                        self.dispatch(target, DblClick(mouse_event));
+                    }
+
                     event
                 })
             }
             MouseUp(mouse_event) => {
                 let target = self.hit_test(mouse_event.client.x, mouse_event.client.y);
+                self.end_drag();
 
                 if let Some(target) = target {
                     let event = self.dispatch(target, event_type);
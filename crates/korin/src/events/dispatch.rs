@@ -1,13 +1,45 @@
-use dom_events::EventPhase;
+use std::{
+    cell::OnceCell,
+    fmt,
+    time::{Duration, Instant},
+};
+
+use dom_events::{CustomEvent, EventPhase};
 use indextree::NodeId;
 use smallvec::SmallVec;
 use tracing::trace;
 
 use crate::{
     Document, HandlerId,
-    events::{Event, EventType},
+    events::{Event, EventLogEntry, EventType},
 };
 
+/// Where [`Document::now`] reads the time from. [`Instant`] is only looked
+/// at once it's needed and no clock was set.
+#[derive(Default)]
+pub struct Clock {
+    origin: OnceCell<Instant>,
+    custom: Option<Box<dyn Fn() -> Duration>>,
+}
+
+impl Clock {
+    fn now(&self) -> Duration {
+        self.custom.as_ref().map_or_else(
+            || self.origin.get_or_init(Instant::now).elapsed(),
+            |clock| clock(),
+        )
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("origin", &self.origin)
+            .field("custom", &self.custom.as_ref().map(|_| "<fn>"))
+            .finish()
+    }
+}
+
 impl Document {
     pub fn dispatch(&mut self, target: NodeId, event_type: EventType) -> Event {
         self.dispatch_impl(target, event_type, true)
@@ -17,6 +49,13 @@ impl Document {
         self.dispatch_impl(target, event_type, false)
     }
 
+    /// Dispatch an application-defined event to `target`, bubbling up from
+    /// there. Handlers register under the event's name and can read the typed
+    /// detail back with [`CustomEvent::detail_ref`].
+    pub fn dispatch_custom(&mut self, target: NodeId, event: CustomEvent) -> Event {
+        self.dispatch(target, EventType::Custom(event))
+    }
+
     fn dispatch_impl(&mut self, target: NodeId, event_type: EventType, bubbles: bool) -> Event {
         debug_assert!(
             self.get(target).is_some(),
@@ -27,6 +66,8 @@ impl Document {
         trace!(doc = %self.id(), ?target, %event_name, "dispatching event");
 
         let mut event = Event::new(target, target, event_type);
+        event.time_stamp = self.now();
+        let mut handled = false;
 
         if bubbles {
             let path: SmallVec<[NodeId; 16]> = target.ancestors(&self.arena).collect();
@@ -39,7 +80,7 @@ impl Document {
                     EventPhase::Bubbling
                 };
 
-                self.dispatch_to_node(node, &mut event);
+                handled |= self.dispatch_to_node(node, &mut event);
 
                 if event.is_propagation_stopped() {
                     trace!(doc = %self.id(), ?node, "propagation stopped");
@@ -49,18 +90,41 @@ impl Document {
         } else {
             event.current_target = target;
             event.phase = EventPhase::AtTarget;
-            self.dispatch_to_node(target, &mut event);
+            handled = self.dispatch_to_node(target, &mut event);
         }
 
-        trace!(doc = %self.id(), ?target, %event_name, "dispatch complete");
+        trace!(doc = %self.id(), ?target, %event_name, handled, "dispatch complete");
+
+        if let Some(log) = &mut self.event_log {
+            log.push(EventLogEntry {
+                name: event_name,
+                target,
+                handled,
+            });
+        }
 
         event
     }
 
-    fn dispatch_to_node(&mut self, node: NodeId, event: &mut Event) {
+    /// The time used to stamp dispatched events. Comes from the clock set
+    /// with [`Document::set_clock`], or else is the time since `now` was
+    /// first called, measured with [`Instant`].
+    #[must_use]
+    pub fn now(&self) -> Duration {
+        self.clock.now()
+    }
+
+    /// Replace the clock events are stamped with, for platforms without
+    /// [`Instant`] or for tests. `None` goes back to the default.
+    pub fn set_clock(&mut self, clock: Option<Box<dyn Fn() -> Duration>>) {
+        self.clock.custom = clock;
+    }
+
+    /// Invoke the handlers registered on `node`. Returns whether any ran.
+    fn dispatch_to_node(&mut self, node: NodeId, event: &mut Event) -> bool {
         let handler_ids: SmallVec<[HandlerId; 2]> = {
             let Some(element) = self.get(node).and_then(|node| node.as_element()) else {
-                return;
+                return false;
             };
 
             element
@@ -71,7 +135,7 @@ impl Document {
         };
 
         if handler_ids.is_empty() {
-            return;
+            return false;
         }
 
         trace!(
@@ -91,5 +155,7 @@ impl Document {
                 }
             }
         }
+
+        true
     }
 }
@@ -0,0 +1,51 @@
+use dom_events::{ClientPoint, DragEvent, EventType, MouseButton};
+use indextree::NodeId;
+
+use crate::{Document, Event, events::MouseEvent};
+
+/// Where a held button went down, remembered until it's released.
+#[derive(Debug, Clone, Copy)]
+pub struct DragOrigin {
+    pub target: NodeId,
+    pub button: MouseButton,
+    pub start: ClientPoint<u16>,
+}
+
+impl Document {
+    pub(crate) fn start_drag(&mut self, target: NodeId, mouse_event: &MouseEvent) {
+        self.drag_origin = mouse_event.button.map(|button| DragOrigin {
+            target,
+            button,
+            start: mouse_event.client,
+        });
+    }
+
+    pub(crate) const fn end_drag(&mut self) {
+        self.drag_origin = None;
+    }
+
+    /// Send a `drag` for a mouse move made while a button is held. It goes to
+    /// the node the button was pressed on, wherever the cursor is now.
+    pub(crate) fn drag(&mut self, mouse_event: &MouseEvent) -> Option<Event> {
+        let origin = self.drag_origin?;
+
+        if origin.target.is_removed(&self.arena) {
+            self.end_drag();
+            return None;
+        }
+
+        let event_type = EventType::Drag(DragEvent {
+            mouse: mouse_event.clone(),
+            drag_button: origin.button,
+            start: origin.start,
+        });
+
+        Some(self.dispatch(origin.target, event_type))
+    }
+
+    /// The press a drag started from, while a button is held.
+    #[must_use]
+    pub const fn drag_origin(&self) -> Option<DragOrigin> {
+        self.drag_origin
+    }
+}
@@ -1,6 +1,6 @@
-use std::sync::OnceLock;
+use std::{cmp::Reverse, sync::OnceLock};
 
-use capsule_corp::{ElementState, QuerySelector, SelectorList};
+use capsule_corp::{ElementState, QuerySelector, Rect, SelectorList};
 use dom_events::{EventType, FocusEvent};
 use ginyu_force::pose;
 use indextree::NodeId;
@@ -9,16 +9,28 @@ use tracing::debug;
 use crate::{Document, Node};
 
 impl Document {
-    pub fn focus(&mut self, id: NodeId) {
+    /// Focus `id`, returning whether it ended up focused. Disabled elements
+    /// refuse focus and leave the current focus alone.
+    ///
+    /// The old element gets `blur` then `focusout`, the new one `focus` then
+    /// `focusin`. Only `focusout` and `focusin` bubble, so a container holding
+    /// both sees `focusout` followed by `focusin` when focus moves between its
+    /// children.
+    pub fn focus(&mut self, id: NodeId) -> bool {
         debug_assert!(
             self.get(id).is_some_and(Node::is_element),
             "node {id:?} doesn't exist or is not an element"
         );
 
+        if self.is_disabled(id) {
+            debug!(doc = %self.id(), node = ?id, "refused focus on disabled element");
+            return false;
+        }
+
         let old_focus = self.focused();
 
         if old_focus == Some(id) {
-            return;
+            return true;
         }
 
         debug!(doc = %self.id(), old = ?old_focus, new = ?id, "focus change");
@@ -28,6 +40,7 @@ impl Document {
         }
 
         self.focus_node(id, old_focus);
+        true
     }
 
     pub fn blur(&mut self) {
@@ -67,30 +80,34 @@ impl Document {
         self.dispatch(id, event_type);
     }
 
+    fn is_disabled(&self, id: NodeId) -> bool {
+        self.get(id)
+            .and_then(Node::as_element)
+            .is_some_and(|element| element.state.contains(ElementState::DISABLED))
+    }
+
     pub fn is_tabbable(&self, id: NodeId) -> bool {
         debug_assert!(self.get(id).is_some(), "node {id:?} doesn't exist");
 
-        if let Some(element) = self.get(id).and_then(Node::as_element) {
-            if element.state.contains(ElementState::DISABLED) {
-                return false;
-            }
+        if self.is_disabled(id) {
+            return false;
+        }
 
-            if let Some(tabindex) = self.tabindex(id)
-                && tabindex.is_negative()
-            {
-                return false;
-            }
+        if self.get(id).is_some_and(Node::is_element)
+            && let Some(tabindex) = self.tabindex(id)
+            && tabindex.is_negative()
+        {
+            return false;
         }
 
         self.matches_parsed(id, tabbable_selector())
     }
 
+    #[must_use]
     pub fn is_focusable(&self, id: NodeId) -> bool {
         debug_assert!(self.get(id).is_some(), "node {id:?} doesn't exist");
 
-        if let Some(element) = self.get(id).and_then(Node::as_element)
-            && element.state.contains(ElementState::DISABLED)
-        {
+        if self.is_disabled(id) {
             return false;
         }
 
@@ -117,11 +134,27 @@ impl Document {
         None
     }
 
+    /// Whether `id` would appear in [`Self::tab_order`], without building it.
+    #[must_use]
+    pub fn in_tab_order(&self, id: NodeId) -> bool {
+        let in_scope = self
+            .focus_scope
+            .filter(|&scope| self.get(scope).is_some())
+            .is_none_or(|scope| self.is_ancestor_of(scope, id));
+
+        in_scope && self.is_tabbable(id)
+    }
+
     #[must_use]
     pub fn tab_order(&self) -> Vec<NodeId> {
         let mut tab_order = Vec::new();
 
-        for (index, id) in self.descendants(self.root).enumerate() {
+        let scope = self
+            .focus_scope
+            .filter(|&scope| self.get(scope).is_some())
+            .unwrap_or(self.root);
+
+        for (index, id) in self.descendants(scope).enumerate() {
             if self.is_tabbable(id) {
                 let tabindex = self.tabindex(id).unwrap_or(0);
 
@@ -157,17 +190,13 @@ impl Document {
         }
 
         let current = self.focused();
+        let position = current.and_then(|focused| tab_order.iter().position(|&id| id == focused));
 
-        let next = current.map_or_else(
-            || tab_order[0],
-            |focused| {
-                let position = tab_order.iter().position(|&id| id == focused);
-
-                position.map_or(tab_order[0], |index| {
-                    tab_order[(index + 1) % tab_order.len()]
-                })
-            },
-        );
+        let next = match position {
+            Some(index) if index + 1 < tab_order.len() => tab_order[index + 1],
+            Some(_) if !self.focus_wrap => return None,
+            _ => tab_order[0],
+        };
 
         debug!(doc = %self.id(), from = ?current, to = ?next, "focus_next");
 
@@ -178,35 +207,136 @@ impl Document {
 
     pub fn focus_prev(&mut self) -> Option<NodeId> {
         let tab_order = self.tab_order();
-
-        if tab_order.is_empty() {
-            return None;
-        }
+        let last = *tab_order.last()?;
 
         let current = self.focused();
-        let last = *tab_order.last()?;
+        let position = current.and_then(|focused| tab_order.iter().position(|&id| id == focused));
 
-        let prev = current.map_or_else(
-            || last,
-            |focused| {
-                let position = tab_order.iter().position(|&id| id == focused);
-
-                position.map_or_else(
-                    || last,
-                    |index| {
-                        if index == 0 {
-                            return last;
-                        }
-
-                        tab_order[index - 1]
-                    },
-                )
-            },
-        );
+        let prev = match position {
+            Some(index) if index > 0 => tab_order[index - 1],
+            Some(_) if !self.focus_wrap => return None,
+            _ => last,
+        };
 
         self.focus(prev);
         Some(prev)
     }
+
+    /// Confine the tab order, and so every focus traversal method, to the
+    /// descendants of `scope`. Useful for independent regions like a sidebar
+    /// or a modal. `None` goes back to the whole document.
+    pub fn set_focus_scope(&mut self, scope: Option<NodeId>) {
+        debug!(doc = %self.id(), ?scope, "set_focus_scope");
+        self.focus_scope = scope;
+    }
+
+    #[must_use]
+    pub const fn focus_scope(&self) -> Option<NodeId> {
+        self.focus_scope
+    }
+
+    /// Remember the current focus so [`Self::pop_focus`] can return to it,
+    /// e.g. when a modal opens.
+    pub fn push_focus(&mut self) {
+        let saved = self.focused().map(|node| SavedFocus {
+            node,
+            index: self.tab_order().iter().position(|&id| id == node),
+        });
+
+        self.focus_history.push(saved);
+    }
+
+    /// Restore the focus saved by the last [`Self::push_focus`]. If that node
+    /// has since been removed, focus goes to whatever now sits at its old
+    /// position in the tab order, clamped to the end.
+    pub fn pop_focus(&mut self) -> Option<NodeId> {
+        let Some(saved) = self.focus_history.pop()? else {
+            self.blur();
+            return None;
+        };
+
+        let target = if self.get(saved.node).is_some() {
+            saved.node
+        } else {
+            let tab_order = self.tab_order();
+            let index = saved.index?.min(tab_order.len().checked_sub(1)?);
+            tab_order[index]
+        };
+
+        debug!(doc = %self.id(), saved = ?saved.node, to = ?target, "pop_focus");
+
+        self.focus(target).then_some(target)
+    }
+
+    /// Whether [`Self::focus_next`] and [`Self::focus_prev`] wrap around at
+    /// the ends of the tab order. On by default; when off they stop at the
+    /// ends and return `None`.
+    pub const fn set_focus_wrap(&mut self, wrap: bool) {
+        self.focus_wrap = wrap;
+    }
+
+    /// Where focus should go when `removed` is about to be dropped while
+    /// `focused` (inside it) has focus: the next tabbable node outside the
+    /// subtree, or the previous one if there is nothing after it.
+    pub(crate) fn focus_successor(&self, focused: NodeId, removed: NodeId) -> Option<NodeId> {
+        let tab_order = self.tab_order();
+        let outside = |id: &NodeId| *id != removed && !self.is_ancestor_of(removed, *id);
+
+        let Some(index) = tab_order.iter().position(|&id| id == focused) else {
+            return tab_order.into_iter().find(outside);
+        };
+
+        let (before, after) = tab_order.split_at(index);
+
+        after
+            .iter()
+            .copied()
+            .find(outside)
+            .or_else(|| before.iter().rev().copied().find(outside))
+    }
+
+    /// Move focus to the nearest tabbable node in `direction`, judged by
+    /// border boxes. Candidates sharing the most of the perpendicular axis
+    /// with the focused node win, then the closest along `direction`, then tab
+    /// order. With nothing focused this is [`Self::focus_first`].
+    pub fn focus_direction(&mut self, direction: FocusDirection) -> Option<NodeId> {
+        let Some(current) = self.focused() else {
+            return self.focus_first();
+        };
+        let from = self.border_box(current)?;
+
+        let next = self
+            .tab_order()
+            .into_iter()
+            .filter(|&id| id != current)
+            .filter_map(|id| Some((id, self.border_box(id)?)))
+            .filter_map(|(id, to)| Some((id, direction.score(from, to)?)))
+            .min_by_key(|&(_, (overlap, distance))| (Reverse(overlap), distance))
+            .map(|(id, _)| id)?;
+
+        debug!(doc = %self.id(), from = ?current, to = ?next, ?direction, "focus_direction");
+
+        self.focus(next);
+        Some(next)
+    }
+
+    pub fn focus_first(&mut self) -> Option<NodeId> {
+        let first = *self.tab_order().first()?;
+
+        debug!(doc = %self.id(), to = ?first, "focus_first");
+
+        self.focus(first);
+        Some(first)
+    }
+
+    pub fn focus_last(&mut self) -> Option<NodeId> {
+        let last = *self.tab_order().last()?;
+
+        debug!(doc = %self.id(), to = ?last, "focus_last");
+
+        self.focus(last);
+        Some(last)
+    }
 }
 
 const TABBABLE_SELECTOR: &str = concat!(
@@ -225,6 +355,49 @@ fn tabbable_selector() -> &'static SelectorList {
     })
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl FocusDirection {
+    /// `(overlap, distance)` of `to` relative to `from`, or `None` if `to`
+    /// doesn't lie entirely in this direction.
+    fn score(self, from: Rect, to: Rect) -> Option<(u16, u16)> {
+        let (ahead, distance) = match self {
+            Self::Up => (to.bottom() <= from.y, from.y.saturating_sub(to.bottom())),
+            Self::Down => (to.y >= from.bottom(), to.y.saturating_sub(from.bottom())),
+            Self::Left => (to.right() <= from.x, from.x.saturating_sub(to.right())),
+            Self::Right => (to.x >= from.right(), to.x.saturating_sub(from.right())),
+        };
+
+        if !ahead {
+            return None;
+        }
+
+        let overlap = match self {
+            Self::Up | Self::Down => overlap(from.x, from.right(), to.x, to.right()),
+            Self::Left | Self::Right => overlap(from.y, from.bottom(), to.y, to.bottom()),
+        };
+
+        Some((overlap, distance))
+    }
+}
+
+fn overlap(a_start: u16, a_end: u16, b_start: u16, b_end: u16) -> u16 {
+    a_end.min(b_end).saturating_sub(a_start.max(b_start))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SavedFocus {
+    node: NodeId,
+    /// Position in the tab order when saved, used if `node` goes away
+    index: Option<usize>,
+}
+
 #[derive(Debug, Clone, Copy)]
 struct TabOrderEntry {
     node: NodeId,
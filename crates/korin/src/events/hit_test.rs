@@ -1,4 +1,4 @@
-use capsule_corp::Layout;
+use capsule_corp::{Point, Rect};
 use indextree::NodeId;
 use tracing::trace;
 
@@ -7,46 +7,83 @@ use crate::Document;
 impl Document {
     // TODO: take z-index into account when hit testing <3
     pub fn hit_test(&self, x: u16, y: u16) -> Option<NodeId> {
-        let result = self.hit_test_node(self.root(), x, y);
+        let result = self.hit_test_node(self.root(), Point::ZERO, x, y);
 
         trace!(doc = %self.id(), x, y, result = ?result, "hit test");
 
         result
     }
 
-    fn hit_test_node(&self, id: NodeId, x: u16, y: u16) -> Option<NodeId> {
+    /// Border box of `id` in absolute cell coordinates.
+    #[must_use]
+    pub fn border_box(&self, id: NodeId) -> Option<Rect> {
+        let layout = self.get(id)?.layout;
+        let size = layout.resolved_box.border_box_size();
+
+        let mut x = layout.location.x;
+        let mut y = layout.location.y;
+
+        // Locations are relative to the parent's content box
+        for ancestor in self.ancestors(id) {
+            let layout = self.get(ancestor)?.layout;
+            let resolved = &layout.resolved_box;
+
+            x = x
+                .saturating_add(layout.location.x)
+                .saturating_add(resolved.border.left)
+                .saturating_add(resolved.padding.left);
+            y = y
+                .saturating_add(layout.location.y)
+                .saturating_add(resolved.border.top)
+                .saturating_add(resolved.padding.top);
+        }
+
+        Some(Rect::new(x, y, size.width, size.height))
+    }
+
+    /// `origin` is the absolute position of the parent's content box, which
+    /// layout locations are relative to. The root is never a hit itself.
+    fn hit_test_node(&self, id: NodeId, origin: Point, x: u16, y: u16) -> Option<NodeId> {
         let node = self.get(id)?;
+        let is_root = id == self.root();
 
-        if !node.is_element() {
+        if !is_root && !node.is_element() {
             return None;
         }
 
-        let layout = node.layout;
+        let layout = &node.layout;
+        let size = layout.resolved_box.border_box_size();
+        let border_box = Rect::new(
+            origin.x.saturating_add(layout.location.x),
+            origin.y.saturating_add(layout.location.y),
+            size.width,
+            size.height,
+        );
 
-        if !is_in_layout(&layout, x, y) {
+        if !is_root && !border_box.contains(Point::new(x, y)) {
             return None;
         }
 
+        let resolved = &layout.resolved_box;
+        let content_origin = Point::new(
+            border_box
+                .x
+                .saturating_add(resolved.border.left)
+                .saturating_add(resolved.padding.left),
+            border_box
+                .y
+                .saturating_add(resolved.border.top)
+                .saturating_add(resolved.padding.top),
+        );
+
         let children: Vec<NodeId> = self.children(id).collect();
 
         for &child in children.iter().rev() {
-            if let Some(hit) = self.hit_test_node(child, x, y) {
+            if let Some(hit) = self.hit_test_node(child, content_origin, x, y) {
                 return Some(hit);
             }
         }
 
-        Some(id)
+        (!is_root).then_some(id)
     }
 }
-
-#[inline]
-const fn is_in_layout(layout: &Layout, x: u16, y: u16) -> bool {
-    let border_box = layout.resolved_box.border_box_size();
-
-    let left = layout.location.x;
-    let top = layout.location.y;
-    let right = left.saturating_add(border_box.width);
-    let bottom = top.saturating_add(border_box.height);
-
-    x >= left && x < right && y >= top && y < bottom
-}
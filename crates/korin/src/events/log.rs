@@ -0,0 +1,143 @@
+use std::collections::{VecDeque, vec_deque};
+
+use ginyu_force::Pose;
+use indextree::NodeId;
+
+use crate::Document;
+
+/// A record of a single dispatched event, kept for debugging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventLogEntry {
+    pub name: Pose,
+    pub target: NodeId,
+    /// Whether at least one handler was invoked for the event.
+    pub handled: bool,
+}
+
+/// Bounded log of the most recently dispatched events. Once full, the oldest
+/// entry is dropped to make room.
+#[derive(Debug)]
+pub struct EventLog {
+    entries: VecDeque<EventLogEntry>,
+    capacity: usize,
+}
+
+impl EventLog {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, entry: EventLogEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(entry);
+    }
+}
+
+impl Document {
+    /// Start recording dispatched events, keeping at most `capacity` entries.
+    pub fn enable_event_log(&mut self, capacity: usize) {
+        self.event_log = Some(EventLog::new(capacity));
+    }
+
+    pub fn disable_event_log(&mut self) {
+        self.event_log = None;
+    }
+
+    /// Recently dispatched events, oldest first. Empty unless the log was
+    /// enabled with [`Document::enable_event_log`].
+    #[must_use]
+    pub fn event_log(&self) -> vec_deque::Iter<'_, EventLogEntry> {
+        self.event_log
+            .as_ref()
+            .map(|log| log.entries.iter())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use dom_events::FocusEvent;
+    use ginyu_force::pose;
+
+    use super::*;
+    use crate::EventType;
+
+    const fn focus_in() -> EventType {
+        EventType::FocusIn(FocusEvent {
+            related_target: None,
+        })
+    }
+
+    const fn blur() -> EventType {
+        EventType::Blur(FocusEvent {
+            related_target: None,
+        })
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut doc = Document::new();
+        let div = doc.create_element(pose!("div"));
+        doc.append_child(doc.root(), div);
+
+        doc.dispatch(div, focus_in());
+
+        assert_eq!(doc.event_log().len(), 0);
+    }
+
+    #[test]
+    fn records_dispatched_events() {
+        let mut doc = Document::new();
+        let button = doc.create_element(pose!("button"));
+        let div = doc.create_element(pose!("div"));
+        doc.append_child(doc.root(), button);
+        doc.append_child(doc.root(), div);
+
+        let handler = doc.add_event_handler(|_| {});
+        doc.register_event_handler(button, pose!("focusin"), handler);
+
+        doc.enable_event_log(8);
+
+        doc.dispatch(button, focus_in());
+        doc.dispatch_direct(div, blur());
+
+        let log: Vec<_> = doc.event_log().collect();
+        assert_eq!(log.len(), 2);
+
+        assert_eq!(log[0].name, pose!("focusin"));
+        assert_eq!(log[0].target, button);
+        assert!(log[0].handled);
+
+        assert_eq!(log[1].name, pose!("blur"));
+        assert_eq!(log[1].target, div);
+        assert!(!log[1].handled);
+    }
+
+    #[test]
+    fn drops_oldest_when_full() {
+        let mut doc = Document::new();
+        let a = doc.create_element(pose!("a"));
+        let b = doc.create_element(pose!("b"));
+        doc.append_child(doc.root(), a);
+        doc.append_child(doc.root(), b);
+
+        doc.enable_event_log(1);
+
+        doc.dispatch(a, focus_in());
+        doc.dispatch(b, focus_in());
+
+        let log: Vec<_> = doc.event_log().collect();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].target, b);
+    }
+}
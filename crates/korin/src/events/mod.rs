@@ -1,12 +1,32 @@
+mod chord;
+mod click;
+mod crossterm;
 mod default;
 mod dispatch;
+mod drag;
 mod focus;
 mod handler;
 mod hit_test;
 mod hover;
+mod log;
+mod resize;
+#[cfg(feature = "termwiz")]
+mod termwiz;
 
+pub use chord::{CHORD_TIMEOUT, ChordMatcher, KeyChord, KeyStroke};
+pub use click::{ClickTracker, DOUBLE_CLICK_DISTANCE, DOUBLE_CLICK_THRESHOLD};
+pub use crossterm::from_crossterm;
+pub use dispatch::Clock;
+pub use drag::DragOrigin;
+pub use focus::FocusDirection;
+pub use focus::SavedFocus;
 pub use handler::{EventHandler, HandlerId};
 use indextree::NodeId;
+pub use log::EventLog;
+pub use log::EventLogEntry;
+pub use resize::{RESIZE_QUIET_PERIOD, ResizeDebouncer};
+#[cfg(feature = "termwiz")]
+pub use termwiz::{TermwizInput, from_termwiz};
 
 pub type EventType = dom_events::EventType<NodeId, u16>;
 pub type Event = dom_events::Event<NodeId, u16>;
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use capsule_corp::Size;
+
+/// How long the terminal has to stay the same size before a resize is
+/// reported.
+pub const RESIZE_QUIET_PERIOD: Duration = Duration::from_millis(50);
+
+/// Coalesces the burst of resizes a terminal reports while a window edge is
+/// dragged, so layout only runs for the size it settles on.
+///
+/// Times are on whatever clock the caller uses, typically [`Document::now`].
+///
+/// [`Document::now`]: crate::Document::now
+#[derive(Debug)]
+pub struct ResizeDebouncer {
+    quiet_period: Duration,
+    pending: Option<(Size, Duration)>,
+}
+
+impl ResizeDebouncer {
+    #[must_use]
+    pub const fn new(quiet_period: Duration) -> Self {
+        Self {
+            quiet_period,
+            pending: None,
+        }
+    }
+
+    /// Record a resize to `size` reported at `at`, replacing any pending one.
+    pub const fn push(&mut self, size: Size, at: Duration) {
+        self.pending = Some((size, at));
+    }
+
+    /// The pending size, once nothing has been pushed for the quiet period.
+    pub fn poll(&mut self, now: Duration) -> Option<Size> {
+        let (_, at) = self.pending?;
+
+        if now.saturating_sub(at) < self.quiet_period {
+            return None;
+        }
+
+        self.flush()
+    }
+
+    /// The pending size right away, quiet period or not.
+    pub const fn flush(&mut self) -> Option<Size> {
+        match self.pending.take() {
+            Some((size, _)) => Some(size),
+            None => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+impl Default for ResizeDebouncer {
+    fn default() -> Self {
+        Self::new(RESIZE_QUIET_PERIOD)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn burst_flushes_to_last_size() {
+        let mut debouncer = ResizeDebouncer::default();
+        let start = Duration::from_secs(1);
+
+        for (i, width) in (0..).zip([80, 85, 90, 95]) {
+            debouncer.push(Size::new(width, 24), start + MS * i);
+        }
+
+        assert_eq!(debouncer.flush(), Some(Size::new(95, 24)));
+        assert_eq!(debouncer.flush(), None);
+    }
+
+    #[test]
+    fn poll_waits_for_quiet_period() {
+        let mut debouncer = ResizeDebouncer::default();
+        let start = Duration::from_secs(1);
+
+        debouncer.push(Size::new(80, 24), start);
+        debouncer.push(Size::new(100, 30), start + MS * 20);
+
+        // Only 40ms since the last resize
+        assert_eq!(debouncer.poll(start + MS * 60), None);
+        assert!(debouncer.is_pending());
+
+        assert_eq!(debouncer.poll(start + MS * 70), Some(Size::new(100, 30)));
+        assert!(!debouncer.is_pending());
+    }
+}
@@ -0,0 +1,410 @@
+use dom_events::{
+    ClientPoint, ClipboardEvent, Code, DeltaMode, Key, KeyboardEvent, Location, Modifiers,
+    MouseButton, MouseButtons, NamedKey, OffsetPoint, PagePoint, ScreenPoint, WheelEvent,
+};
+use ratatui::termwiz::input::{
+    InputEvent, KeyCode, KeyEvent, Modifiers as TermwizModifiers, MouseButtons as TermwizButtons,
+    MouseEvent as TermwizMouseEvent,
+};
+use smallvec::{SmallVec, smallvec};
+
+use crate::events::{EventType, MouseEvent};
+
+/// Termwiz passes on the terminal's 1-based mouse positions, except for the
+/// Windows console which is 0-based already.
+const MOUSE_ORIGIN: u16 = if cfg!(windows) { 0 } else { 1 };
+
+/// Convert a termwiz input event into the equivalent korin event, if there is
+/// one. The termwiz counterpart of [`from_crossterm`](super::from_crossterm).
+///
+/// Mouse reports only say which buttons are held, so telling a press from a
+/// move needs the previous report. Those go through [`TermwizInput`] instead
+/// and are ignored here.
+#[must_use]
+pub fn from_termwiz(event: &InputEvent) -> Option<EventType> {
+    match event {
+        InputEvent::Key(key) => Some(key_event(key)),
+        InputEvent::Paste(data) => Some(EventType::Paste(ClipboardEvent { data: data.clone() })),
+        _ => None,
+    }
+}
+
+/// Converts termwiz input into korin events, keeping track of the held mouse
+/// buttons so presses and releases can be told apart from moves.
+#[derive(Debug, Default)]
+pub struct TermwizInput {
+    buttons: MouseButtons,
+}
+
+impl TermwizInput {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The korin events for `event`. A mouse report becomes a `wheel`, a
+    /// `mousedown` or `mouseup` per button that changed, or otherwise a
+    /// `mousemove`. Everything else goes through [`from_termwiz`].
+    pub fn translate(&mut self, event: &InputEvent) -> SmallVec<[EventType; 1]> {
+        match event {
+            InputEvent::Mouse(mouse) => self.mouse(mouse),
+            other => from_termwiz(other).into_iter().collect(),
+        }
+    }
+
+    fn mouse(&mut self, event: &TermwizMouseEvent) -> SmallVec<[EventType; 1]> {
+        let buttons = &event.mouse_buttons;
+
+        if buttons.intersects(TermwizButtons::VERT_WHEEL | TermwizButtons::HORZ_WHEEL) {
+            // Positive is up or left, the opposite of the DOM's deltas
+            let delta = if buttons.contains(TermwizButtons::WHEEL_POSITIVE) {
+                -1.0
+            } else {
+                1.0
+            };
+            let (delta_x, delta_y) = if buttons.contains(TermwizButtons::VERT_WHEEL) {
+                (0.0, delta)
+            } else {
+                (delta, 0.0)
+            };
+
+            return smallvec![EventType::Wheel(WheelEvent {
+                mouse: self.mouse_event(event, None),
+                delta_x,
+                delta_y,
+                delta_z: 0.0,
+                delta_mode: DeltaMode::Line,
+            })];
+        }
+
+        let held = held_buttons(buttons);
+        let pressed = held.difference(self.buttons);
+        let released = self.buttons.difference(held);
+
+        if pressed.is_empty() && released.is_empty() {
+            return smallvec![EventType::MouseMove(self.mouse_event(event, None))];
+        }
+
+        let mut events = SmallVec::new();
+
+        for (flag, button) in BUTTONS {
+            if released.contains(flag) {
+                self.buttons.remove(flag);
+                events.push(EventType::MouseUp(self.mouse_event(event, Some(button))));
+            }
+        }
+
+        for (flag, button) in BUTTONS {
+            if pressed.contains(flag) {
+                self.buttons.insert(flag);
+                events.push(EventType::MouseDown(self.mouse_event(event, Some(button))));
+            }
+        }
+
+        events
+    }
+
+    fn mouse_event(&self, event: &TermwizMouseEvent, button: Option<MouseButton>) -> MouseEvent {
+        let x = event.x.saturating_sub(MOUSE_ORIGIN);
+        let y = event.y.saturating_sub(MOUSE_ORIGIN);
+
+        MouseEvent {
+            related_target: None,
+            screen: ScreenPoint::new(x, y),
+            client: ClientPoint::new(x, y),
+            page: PagePoint::new(x, y),
+            offset: OffsetPoint::new(0, 0),
+            button,
+            buttons: self.buttons,
+            modifiers: modifiers(event.modifiers),
+            detail: 0,
+        }
+    }
+}
+
+const BUTTONS: [(MouseButtons, MouseButton); 3] = [
+    (MouseButtons::PRIMARY, MouseButton::Primary),
+    (MouseButtons::SECONDARY, MouseButton::Secondary),
+    (MouseButtons::AUXILIARY, MouseButton::Auxiliary),
+];
+
+fn held_buttons(buttons: &TermwizButtons) -> MouseButtons {
+    let mut held = MouseButtons::empty();
+
+    held.set(
+        MouseButtons::PRIMARY,
+        buttons.contains(TermwizButtons::LEFT),
+    );
+    held.set(
+        MouseButtons::SECONDARY,
+        buttons.contains(TermwizButtons::RIGHT),
+    );
+    held.set(
+        MouseButtons::AUXILIARY,
+        buttons.contains(TermwizButtons::MIDDLE),
+    );
+
+    held
+}
+
+/// Termwiz only reports presses, so this is always a `keydown`.
+fn key_event(event: &KeyEvent) -> EventType {
+    let (key, code) = key_and_code(event.key);
+    let location = match event.key {
+        KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::Multiply
+        | KeyCode::Add
+        | KeyCode::Separator
+        | KeyCode::Subtract
+        | KeyCode::Decimal
+        | KeyCode::Divide => Location::Numpad,
+        _ => Location::Standard,
+    };
+
+    EventType::KeyDown(KeyboardEvent {
+        key,
+        code,
+        modifiers: modifiers(event.modifiers),
+        repeat: false,
+        is_composing: false,
+        location,
+    })
+}
+
+/// Termwiz doesn't report physical keys, so characters get `Code::Unidentified`.
+fn key_and_code(code: KeyCode) -> (Key, Code) {
+    let named = |key: NamedKey, code: Code| (Key::Named(key), code);
+    let character = |c: char, code: Code| (Key::Character(c.to_string()), code);
+
+    match code {
+        KeyCode::Char(c) => character(c, Code::Unidentified),
+        KeyCode::Backspace => named(NamedKey::Backspace, Code::Backspace),
+        KeyCode::Enter => named(NamedKey::Enter, Code::Enter),
+        KeyCode::LeftArrow | KeyCode::ApplicationLeftArrow => {
+            named(NamedKey::ArrowLeft, Code::ArrowLeft)
+        }
+        KeyCode::RightArrow | KeyCode::ApplicationRightArrow => {
+            named(NamedKey::ArrowRight, Code::ArrowRight)
+        }
+        KeyCode::UpArrow | KeyCode::ApplicationUpArrow => named(NamedKey::ArrowUp, Code::ArrowUp),
+        KeyCode::DownArrow | KeyCode::ApplicationDownArrow => {
+            named(NamedKey::ArrowDown, Code::ArrowDown)
+        }
+        KeyCode::Home | KeyCode::KeyPadHome => named(NamedKey::Home, Code::Home),
+        KeyCode::End | KeyCode::KeyPadEnd => named(NamedKey::End, Code::End),
+        KeyCode::PageUp | KeyCode::KeyPadPageUp => named(NamedKey::PageUp, Code::PageUp),
+        KeyCode::PageDown | KeyCode::KeyPadPageDown => named(NamedKey::PageDown, Code::PageDown),
+        KeyCode::Tab => named(NamedKey::Tab, Code::Tab),
+        KeyCode::Delete => named(NamedKey::Delete, Code::Delete),
+        KeyCode::Insert => named(NamedKey::Insert, Code::Insert),
+        KeyCode::Escape => named(NamedKey::Escape, Code::Escape),
+        KeyCode::CapsLock => named(NamedKey::CapsLock, Code::CapsLock),
+        KeyCode::ScrollLock => named(NamedKey::ScrollLock, Code::ScrollLock),
+        KeyCode::NumLock => named(NamedKey::NumLock, Code::NumLock),
+        KeyCode::PrintScreen => named(NamedKey::PrintScreen, Code::PrintScreen),
+        KeyCode::Pause => named(NamedKey::Pause, Code::Pause),
+        KeyCode::Menu | KeyCode::Applications => named(NamedKey::ContextMenu, Code::ContextMenu),
+        KeyCode::Numpad0 => character('0', Code::Numpad0),
+        KeyCode::Numpad1 => character('1', Code::Numpad1),
+        KeyCode::Numpad2 => character('2', Code::Numpad2),
+        KeyCode::Numpad3 => character('3', Code::Numpad3),
+        KeyCode::Numpad4 => character('4', Code::Numpad4),
+        KeyCode::Numpad5 => character('5', Code::Numpad5),
+        KeyCode::Numpad6 => character('6', Code::Numpad6),
+        KeyCode::Numpad7 => character('7', Code::Numpad7),
+        KeyCode::Numpad8 => character('8', Code::Numpad8),
+        KeyCode::Numpad9 => character('9', Code::Numpad9),
+        KeyCode::Multiply => character('*', Code::NumpadMultiply),
+        KeyCode::Add => character('+', Code::NumpadAdd),
+        KeyCode::Separator => character(',', Code::NumpadComma),
+        KeyCode::Subtract => character('-', Code::NumpadSubtract),
+        KeyCode::Decimal => character('.', Code::NumpadDecimal),
+        KeyCode::Divide => character('/', Code::NumpadDivide),
+        KeyCode::Function(n) => {
+            let name = format!("F{n}");
+
+            named(
+                name.parse().unwrap_or(NamedKey::Unidentified),
+                name.parse().unwrap_or(Code::Unidentified),
+            )
+        }
+        _ => named(NamedKey::Unidentified, Code::Unidentified),
+    }
+}
+
+fn modifiers(mods: TermwizModifiers) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+
+    modifiers.set(
+        Modifiers::SHIFT,
+        mods.intersects(
+            TermwizModifiers::SHIFT | TermwizModifiers::LEFT_SHIFT | TermwizModifiers::RIGHT_SHIFT,
+        ),
+    );
+    modifiers.set(
+        Modifiers::CONTROL,
+        mods.intersects(
+            TermwizModifiers::CTRL | TermwizModifiers::LEFT_CTRL | TermwizModifiers::RIGHT_CTRL,
+        ),
+    );
+    modifiers.set(
+        Modifiers::ALT,
+        mods.intersects(
+            TermwizModifiers::ALT | TermwizModifiers::LEFT_ALT | TermwizModifiers::RIGHT_ALT,
+        ),
+    );
+    modifiers.set(Modifiers::META, mods.contains(TermwizModifiers::SUPER));
+
+    modifiers
+}
+
+#[cfg(test)]
+mod tests {
+    use dom_events::ModifiersExt;
+
+    use super::*;
+
+    fn key(key: KeyCode, modifiers: TermwizModifiers) -> InputEvent {
+        InputEvent::Key(KeyEvent { key, modifiers })
+    }
+
+    fn mouse(x: u16, y: u16, mouse_buttons: TermwizButtons) -> InputEvent {
+        InputEvent::Mouse(TermwizMouseEvent {
+            x,
+            y,
+            mouse_buttons,
+            modifiers: TermwizModifiers::NONE,
+        })
+    }
+
+    #[test]
+    fn key_is_keydown() {
+        let event =
+            from_termwiz(&key(KeyCode::Char('a'), TermwizModifiers::LEFT_CTRL)).expect("failed");
+
+        let EventType::KeyDown(key) = event else {
+            panic!("expected keydown, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Character("a".into()));
+        assert!(key.modifiers.only(Modifiers::CONTROL));
+    }
+
+    #[test]
+    fn numpad_keys_are_characters() {
+        let event = from_termwiz(&key(KeyCode::Numpad7, TermwizModifiers::NONE)).expect("failed");
+
+        let EventType::KeyDown(key) = event else {
+            panic!("expected keydown, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Character("7".into()));
+        assert_eq!(key.code, Code::Numpad7);
+        assert_eq!(key.location, Location::Numpad);
+    }
+
+    #[test]
+    fn unknown_keys_are_unidentified() {
+        let event =
+            from_termwiz(&key(KeyCode::VolumeMute, TermwizModifiers::NONE)).expect("failed");
+
+        let EventType::KeyDown(key) = event else {
+            panic!("expected keydown, got {event:?}");
+        };
+        assert_eq!(key.key, Key::Named(NamedKey::Unidentified));
+    }
+
+    #[test]
+    fn paste_carries_text() {
+        let event = from_termwiz(&InputEvent::Paste("hello".into())).expect("failed");
+
+        let EventType::Paste(paste) = event else {
+            panic!("expected paste, got {event:?}");
+        };
+        assert_eq!(paste.data, "hello");
+    }
+
+    #[test]
+    fn wheel_is_a_line_scroll() {
+        let mut input = TermwizInput::new();
+
+        let events = input.translate(&mouse(
+            5,
+            3,
+            TermwizButtons::VERT_WHEEL | TermwizButtons::WHEEL_POSITIVE,
+        ));
+        let [EventType::Wheel(wheel)] = events.as_slice() else {
+            panic!("expected wheel, got {events:?}");
+        };
+        assert_eq!((wheel.delta_x, wheel.delta_y), (0.0, -1.0));
+        assert_eq!(wheel.delta_mode, DeltaMode::Line);
+        assert_eq!(wheel.client, ClientPoint::new(4, 2));
+
+        let events = input.translate(&mouse(5, 3, TermwizButtons::HORZ_WHEEL));
+        let [EventType::Wheel(wheel)] = events.as_slice() else {
+            panic!("expected wheel, got {events:?}");
+        };
+        assert_eq!((wheel.delta_x, wheel.delta_y), (1.0, 0.0));
+    }
+
+    #[test]
+    fn motion_is_mousemove() {
+        let mut input = TermwizInput::new();
+
+        let events = input.translate(&mouse(1, 1, TermwizButtons::NONE));
+        let [EventType::MouseMove(moved)] = events.as_slice() else {
+            panic!("expected mousemove, got {events:?}");
+        };
+        assert_eq!(moved.client, ClientPoint::new(0, 0));
+        assert_eq!(moved.button, None);
+
+        // Dragging keeps the button held, so it's still a move
+        input.translate(&mouse(1, 1, TermwizButtons::LEFT));
+        let events = input.translate(&mouse(4, 1, TermwizButtons::LEFT));
+        let [EventType::MouseMove(moved)] = events.as_slice() else {
+            panic!("expected mousemove, got {events:?}");
+        };
+        assert_eq!(moved.buttons, MouseButtons::PRIMARY);
+    }
+
+    #[test]
+    fn button_changes_are_down_and_up() {
+        let mut input = TermwizInput::new();
+
+        let events = input.translate(&mouse(2, 2, TermwizButtons::LEFT));
+        let [EventType::MouseDown(down)] = events.as_slice() else {
+            panic!("expected mousedown, got {events:?}");
+        };
+        assert_eq!(down.button, Some(MouseButton::Primary));
+        assert_eq!(down.buttons, MouseButtons::PRIMARY);
+
+        let events = input.translate(&mouse(2, 2, TermwizButtons::RIGHT));
+        let [EventType::MouseUp(up), EventType::MouseDown(down)] = events.as_slice() else {
+            panic!("expected mouseup then mousedown, got {events:?}");
+        };
+        assert_eq!(up.button, Some(MouseButton::Primary));
+        assert_eq!(down.button, Some(MouseButton::Secondary));
+
+        let events = input.translate(&mouse(2, 2, TermwizButtons::NONE));
+        let [EventType::MouseUp(up)] = events.as_slice() else {
+            panic!("expected mouseup, got {events:?}");
+        };
+        assert_eq!(up.button, Some(MouseButton::Secondary));
+        assert!(up.buttons.is_empty());
+    }
+
+    #[test]
+    fn keys_still_go_through_from_termwiz() {
+        let mut input = TermwizInput::new();
+
+        let events = input.translate(&key(KeyCode::Enter, TermwizModifiers::NONE));
+        assert!(matches!(events.as_slice(), [EventType::KeyDown(_)]));
+    }
+}
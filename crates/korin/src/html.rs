@@ -61,6 +61,13 @@ impl Document {
                     output.push('\n');
                 }
             }
+            NodeData::RawText(text) => {
+                for line in text.lines() {
+                    output.push_str(&indent);
+                    output.push_str(line);
+                    output.push('\n');
+                }
+            }
             NodeData::Marker => output.push_str("<-- MARKER -->"),
         }
     }
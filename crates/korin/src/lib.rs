@@ -4,12 +4,19 @@ mod events;
 mod node;
 mod render;
 mod html;
+mod transition;
 pub mod view;
 
 pub use document::{Document, DocumentId};
 pub use dom_events::*;
 pub use element::Element;
-pub use events::{Event, EventHandler, EventType, HandlerId, MouseEvent};
+pub use events::{
+    CHORD_TIMEOUT, ChordMatcher, ClickTracker, DOUBLE_CLICK_DISTANCE, DOUBLE_CLICK_THRESHOLD,
+    Event, EventHandler, EventLogEntry, EventType, FocusDirection, HandlerId, KeyChord, KeyStroke,
+    MouseEvent, RESIZE_QUIET_PERIOD, ResizeDebouncer, from_crossterm,
+};
+#[cfg(feature = "termwiz")]
+pub use events::{TermwizInput, from_termwiz};
 pub use indextree::NodeId;
 pub use node::{Node, NodeData};
 pub use render::*;
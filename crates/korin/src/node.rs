@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use capsule_corp::{ComputedStyle, CustomPropertiesMap, Layout};
 
 use crate::element::Element;
@@ -5,7 +7,7 @@ use crate::element::Element;
 #[derive(Debug, PartialEq)]
 pub struct Node {
     pub data: NodeData,
-    pub style: Option<ComputedStyle>,
+    pub style: Option<Arc<ComputedStyle>>,
     pub custom_properties: Option<CustomPropertiesMap>,
     pub layout: Layout,
     pub needs_layout: bool,
@@ -27,7 +29,7 @@ impl Node {
     pub fn element(element: Element) -> Self {
         Self {
             data: NodeData::Element(element),
-            style: Some(ComputedStyle::default()),
+            style: Some(Arc::default()),
             custom_properties: None,
             layout: Layout::ZERO,
             needs_layout: true,
@@ -46,6 +48,13 @@ impl Node {
         }
     }
 
+    pub fn raw_text(content: impl Into<String>) -> Self {
+        Self {
+            data: NodeData::RawText(content.into()),
+            ..Self::text(String::new())
+        }
+    }
+
     #[must_use]
     pub const fn marker() -> Self {
         Self {
@@ -74,14 +83,14 @@ impl Node {
     #[must_use]
     pub fn as_text(&self) -> Option<&str> {
         match &self.data {
-            NodeData::Text(s) => Some(s),
+            NodeData::Text(s) | NodeData::RawText(s) => Some(s),
             _ => None,
         }
     }
 
     pub const fn as_text_mut(&mut self) -> Option<&mut String> {
         match &mut self.data {
-            NodeData::Text(s) => Some(s),
+            NodeData::Text(s) | NodeData::RawText(s) => Some(s),
             _ => None,
         }
     }
@@ -98,7 +107,12 @@ impl Node {
 
     #[must_use]
     pub const fn is_text(&self) -> bool {
-        matches!(self.data, NodeData::Text(_))
+        matches!(self.data, NodeData::Text(_) | NodeData::RawText(_))
+    }
+
+    #[must_use]
+    pub const fn is_raw_text(&self) -> bool {
+        matches!(self.data, NodeData::RawText(_))
     }
 
     #[must_use]
@@ -113,6 +127,10 @@ pub enum NodeData {
     Element(Element),
     Text(String),
 
+    /// Pre-rendered text, kept exactly as given. It's never wrapped, whatever
+    /// the parent's `white-space` says.
+    RawText(String),
+
     /// Anonymous marker node for control flow (Show, For, etc.)
     /// Invisible and skipped during layout/render.
     Marker,
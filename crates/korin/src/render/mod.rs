@@ -7,12 +7,18 @@ use crate::Document;
 mod paint;
 mod terminal;
 
+pub use paint::PaintOptions;
+
 pub fn run_once(document: &Document) -> io::Result<()> {
+    run_once_with(document, &PaintOptions::default())
+}
+
+pub fn run_once_with(document: &Document, options: &PaintOptions) -> io::Result<()> {
     let writer = io::stdout();
     let mut terminal = terminal::setup(writer)?;
 
     terminal.draw(|frame| {
-        paint::paint(document, frame);
+        paint::paint(document, frame, options);
     })?;
 
     loop {
@@ -1,6 +1,6 @@
 use capsule_corp::{
-    BasicColor, BorderStyle, CapsuleDocument, CapsuleNode, Color, ComputedStyle, Display, Edges,
-    FontStyle, FontWeight, TextDecoration,
+    BasicColor, BorderStyle, BoxShadow, CapsuleDocument, CapsuleNode, Color, ComputedStyle,
+    Display, Edges, FontStyle, FontWeight, Overflow, TextDecoration, truncate_text,
 };
 use indextree::NodeId;
 use ratatui::{
@@ -12,15 +12,90 @@ use ratatui::{
 
 use crate::Document;
 
-pub fn paint(document: &Document, frame: &mut Frame) {
+/// Controls how the painter fills cells that nothing else draws into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaintOptions {
+    /// Glyph used when clearing the frame and filling backgrounds
+    pub fill_char: char,
+    /// Style applied to cleared cells
+    pub fill_style: Style,
+}
+
+impl Default for PaintOptions {
+    fn default() -> Self {
+        Self {
+            fill_char: ' ',
+            fill_style: Style::default(),
+        }
+    }
+}
+
+/// What a node gets painted onto: how see-through its ancestors have made it,
+/// and the color showing through from behind.
+#[derive(Debug, Clone, Copy)]
+struct Layer {
+    opacity: f32,
+    backdrop: Color,
+}
+
+impl Layer {
+    const OPAQUE: Self = Self {
+        opacity: 1.0,
+        backdrop: Color::Reset,
+    };
+
+    /// Mixes `color` toward the backdrop, see [`Color::lerp`] for colors that
+    /// can't be mixed.
+    fn fade(self, color: Color) -> Color {
+        if self.opacity >= 1.0 {
+            return color;
+        }
+
+        self.backdrop.lerp(color, self.opacity)
+    }
+}
+
+pub fn paint(document: &Document, frame: &mut Frame, options: &PaintOptions) {
     let root = document.root;
 
-    for child in document.children(root) {
-        paint_node(document, child, frame, 0, 0);
+    let area = frame.area();
+    for position in area.positions() {
+        if let Some(cell) = frame.buffer_mut().cell_mut(position) {
+            cell.set_char(options.fill_char)
+                .set_style(options.fill_style);
+        }
+    }
+
+    for child in paint_order(document, root) {
+        paint_node(document, child, frame, options, Layer::OPAQUE, 0, 0);
     }
 }
 
-fn paint_node(document: &Document, id: NodeId, frame: &mut Frame, offset_x: u16, offset_y: u16) {
+/// Children of `id` in the order they should be painted. Siblings are
+/// grouped by z-index layer, and `auto` shares layer 0 with explicit zeros,
+/// so anything in the same layer keeps its document order.
+fn paint_order(document: &Document, id: NodeId) -> Vec<NodeId> {
+    let mut children: Vec<_> = document.children(id).collect();
+
+    children.sort_by_key(|&child| {
+        document
+            .get_node(child)
+            .computed_style()
+            .map_or(0, |style| style.z_index.layer())
+    });
+
+    children
+}
+
+fn paint_node(
+    document: &Document,
+    id: NodeId,
+    frame: &mut Frame,
+    options: &PaintOptions,
+    layer: Layer,
+    offset_x: u16,
+    offset_y: u16,
+) {
     let node = document.get_node(id);
 
     let layout = node.layout;
@@ -36,14 +111,40 @@ fn paint_node(document: &Document, id: NodeId, frame: &mut Frame, offset_x: u16,
     );
 
     if let Some(text) = node.text_content() {
-        let style = document
-            .parent(id)
-            .and_then(|node| document.get_node(node).computed_style())
-            .map(convert_text_style)
+        let parent = document.parent(id).map(|parent| document.get_node(parent));
+        let parent_style = parent.and_then(|parent| parent.computed_style());
+
+        let style = parent_style
+            .map(|style| convert_text_style(style, layer))
             .unwrap_or_default();
 
-        let paragraph = Paragraph::new(text).style(style);
-        frame.render_widget(paragraph, rect);
+        // A parent that hides overflow cuts each line off at its content edge
+        let clip = parent.zip(parent_style).and_then(|(parent, parent_style)| {
+            let visible = parent
+                .layout
+                .resolved_box
+                .content_size
+                .width
+                .saturating_sub(layout.location.x);
+
+            (parent_style.overflow_x != Overflow::Visible && rect.width > visible)
+                .then_some((visible, parent_style.text_overflow))
+        });
+
+        let paragraph = match clip {
+            Some((visible, overflow)) => {
+                let lines: Vec<_> = text
+                    .lines()
+                    .map(|line| truncate_text(line, visible, overflow))
+                    .collect();
+
+                Paragraph::new(lines.join("\n")).style(style)
+            }
+            None => Paragraph::new(text).style(style),
+        };
+
+        let width = clip.map_or(rect.width, |(visible, _)| visible);
+        frame.render_widget(paragraph, Rect { width, ..rect });
         return;
     }
 
@@ -55,17 +156,43 @@ fn paint_node(document: &Document, id: NodeId, frame: &mut Frame, offset_x: u16,
         return;
     }
 
+    // Opacity doesn't inherit, but a faded parent fades everything inside it
+    let layer = Layer {
+        opacity: layer.opacity * style.opacity,
+        ..layer
+    };
+
+    if let Some(shadow) = style.box_shadow {
+        let color = layer.fade(shadow.color);
+        paint_box_shadow(frame, rect, BoxShadow { color, ..shadow });
+    }
+
+    let background = layer.fade(style.background_color);
     let borders = convert_borders(style.border_style);
     let mut block = Block::default()
-        .style(Style::default().bg(convert_color(style.background_color)))
+        .style(Style::default().bg(convert_color(background)))
         .borders(borders);
 
     if !borders.is_empty() {
-        block = block.border_style(Style::default().fg(convert_color(style.border_color.top)));
+        let border_color = layer.fade(style.border_color.top);
+        block = block.border_style(Style::default().fg(convert_color(border_color)));
     }
 
+    let inner = block.inner(rect);
     frame.render_widget(block, rect);
 
+    // A transparent background leaves what's underneath alone, such as an
+    // earlier sibling it overlaps
+    if style.background_char.is_some() || background != Color::Reset {
+        let fill = style.background_char.unwrap_or(options.fill_char);
+        fill_background(
+            frame,
+            inner.intersection(frame.area()),
+            fill,
+            options.fill_style,
+        );
+    }
+
     let resolved = &layout.resolved_box;
     let content_x = x
         .saturating_add(resolved.border.left)
@@ -74,13 +201,78 @@ fn paint_node(document: &Document, id: NodeId, frame: &mut Frame, offset_x: u16,
         .saturating_add(resolved.border.top)
         .saturating_add(resolved.padding.top);
 
-    for child in document.children(id) {
-        paint_node(document, child, frame, content_x, content_y);
+    let inside = Layer {
+        backdrop: if background == Color::Reset {
+            layer.backdrop
+        } else {
+            background
+        },
+        ..layer
+    };
+
+    for child in paint_order(document, id) {
+        paint_node(
+            document, child, frame, options, inside, content_x, content_y,
+        );
+    }
+
+    // Painted last so it sits on top of the children instead of pushing them in
+    if !style.outline_style.is_none() {
+        let outline_color = layer.fade(style.outline_color);
+        let outline =
+            Block::bordered().border_style(Style::default().fg(convert_color(outline_color)));
+        frame.render_widget(outline, rect);
     }
 }
 
-fn convert_text_style(style: &ComputedStyle) -> Style {
-    let mut result = Style::default().fg(convert_color(style.color));
+/// Colors the cells under the shifted border box. The node paints over the
+/// overlap afterwards, so only the offset edge stays visible.
+fn paint_box_shadow(frame: &mut Frame, rect: Rect, shadow: BoxShadow) {
+    // Whatever gets shifted past the top or left edge is cut off
+    let clamp = |n: i32| u16::try_from(n.max(0)).unwrap_or(u16::MAX);
+
+    let left = i32::from(rect.x) + i32::from(shadow.offset_x);
+    let top = i32::from(rect.y) + i32::from(shadow.offset_y);
+    let right = left + i32::from(rect.width);
+    let bottom = top + i32::from(rect.height);
+
+    let x = clamp(left);
+    let y = clamp(top);
+    let area = Rect::new(
+        x,
+        y,
+        clamp(right).saturating_sub(x),
+        clamp(bottom).saturating_sub(y),
+    )
+    .intersection(frame.area());
+
+    let color = convert_color(shadow.color);
+    let buffer = frame.buffer_mut();
+
+    for position in area.positions() {
+        if let Some(cell) = buffer.cell_mut(position) {
+            cell.set_bg(color);
+        }
+    }
+}
+
+fn fill_background(frame: &mut Frame, area: Rect, fill: char, fill_style: Style) {
+    let buffer = frame.buffer_mut();
+
+    for position in area.positions() {
+        if let Some(cell) = buffer.cell_mut(position) {
+            cell.set_char(fill);
+
+            // The node's own background color wins over the fill style's
+            if let Some(fg) = fill_style.fg {
+                cell.set_fg(fg);
+            }
+        }
+    }
+}
+
+fn convert_text_style(style: &ComputedStyle, layer: Layer) -> Style {
+    let mut result = Style::default().fg(convert_color(layer.fade(style.color)));
 
     if matches!(style.font_weight, FontWeight::Bold) {
         result = result.add_modifier(Modifier::BOLD);
@@ -153,3 +345,258 @@ fn convert_borders(border_style: Edges<BorderStyle>) -> Borders {
 
     borders
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use capsule_corp::{
+        CapsuleDocument, ComputedStyle, CustomPropertiesMap, Display, Size, compute_layout,
+        compute_styles,
+    };
+    use ginyu_force::pose;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+    use crate::{
+        fragment,
+        view::{BuildContext, Mountable, View, div, span, text},
+    };
+
+    fn build(view: impl View) -> Document {
+        let mut document = Document::new();
+        let root = document.root();
+
+        let mut ctx = BuildContext::new(&mut document);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut document);
+
+        compute_styles(&mut document);
+        document
+    }
+
+    fn render(view: impl View, width: u16, height: u16) -> Terminal<TestBackend> {
+        render_with(view, width, height, &PaintOptions::default())
+    }
+
+    fn render_with(
+        view: impl View,
+        width: u16,
+        height: u16,
+        options: &PaintOptions,
+    ) -> Terminal<TestBackend> {
+        let mut document = Document::new();
+        let root = document.root();
+
+        document.set_style(
+            root,
+            Arc::new(ComputedStyle {
+                display: Display::Block,
+                ..Default::default()
+            }),
+            CustomPropertiesMap::default(),
+        );
+
+        let mut ctx = BuildContext::new(&mut document);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut document);
+
+        compute_styles(&mut document);
+        compute_layout(&mut document, root, Size::new(width, height));
+
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("failed");
+        terminal
+            .draw(|frame| paint(&document, frame, options))
+            .expect("failed");
+        terminal
+    }
+
+    #[test]
+    fn background_char_fills_area() {
+        let view = div(()).attribute(
+            pose!("style"),
+            "width: 4; height: 2; background-char: \"░\"",
+        );
+
+        let terminal = render(view, 6, 3);
+        let buffer = terminal.backend().buffer();
+
+        assert_eq!(buffer[(0, 0)].symbol(), "░");
+        assert_eq!(buffer[(3, 1)].symbol(), "░");
+        assert_eq!(buffer[(4, 0)].symbol(), " ");
+        assert_eq!(buffer[(0, 2)].symbol(), " ");
+    }
+
+    #[test]
+    fn fill_char_clears_empty_cells() {
+        let options = PaintOptions {
+            fill_char: '·',
+            ..Default::default()
+        };
+        let view = div(()).attribute(pose!("style"), "width: 2; height: 1");
+
+        let terminal = render_with(view, 4, 2, &options);
+        let buffer = terminal.backend().buffer();
+
+        for position in buffer.area.positions() {
+            assert_eq!(buffer[position].symbol(), "·");
+        }
+    }
+
+    #[test]
+    fn background_char_overrides_fill_char() {
+        let options = PaintOptions {
+            fill_char: '·',
+            ..Default::default()
+        };
+        let view = div(()).attribute(
+            pose!("style"),
+            "width: 2; height: 1; background-char: \"░\"",
+        );
+
+        let terminal = render_with(view, 4, 1, &options);
+        let buffer = terminal.backend().buffer();
+
+        assert_eq!(buffer[(0, 0)].symbol(), "░");
+        assert_eq!(buffer[(2, 0)].symbol(), "·");
+    }
+
+    #[test]
+    fn transparent_background_keeps_what_it_overlaps() {
+        let view = div(fragment![
+            div(text("hi")),
+            div(()).attribute(pose!("style"), "width: 4; height: 1; margin-top: -1"),
+        ]);
+
+        let terminal = render(view, 4, 2);
+        let buffer = terminal.backend().buffer();
+
+        assert_eq!(buffer[(0, 0)].symbol(), "h");
+        assert_eq!(buffer[(1, 0)].symbol(), "i");
+    }
+
+    #[test]
+    fn outline_overlays_without_shifting_children() {
+        let outlined = div(span(text("hi"))).attribute(
+            pose!("style"),
+            "width: 6; height: 5; padding: 1; outline: solid",
+        );
+        let terminal = render(outlined, 6, 5);
+        let buffer = terminal.backend().buffer();
+
+        assert_eq!(buffer[(0, 0)].symbol(), "┌");
+        assert_eq!(buffer[(5, 4)].symbol(), "┘");
+        assert_eq!(buffer[(1, 1)].symbol(), "h");
+
+        // A border takes up space, so the same content moves in by a cell
+        let bordered = div(span(text("hi"))).attribute(
+            pose!("style"),
+            "width: 6; height: 5; padding: 1; border: solid",
+        );
+        let terminal = render(bordered, 6, 5);
+        let buffer = terminal.backend().buffer();
+
+        assert_eq!(buffer[(1, 1)].symbol(), " ");
+        assert_eq!(buffer[(2, 2)].symbol(), "h");
+    }
+
+    #[test]
+    fn box_shadow_paints_behind_border_box() {
+        let view = div(()).attribute(
+            pose!("style"),
+            "width: 3; height: 2; background-color: blue; box-shadow: 1 1 red",
+        );
+
+        let terminal = render(view, 5, 4);
+        let buffer = terminal.backend().buffer();
+
+        let red = convert_color(Color::RED);
+        let blue = convert_color(Color::BLUE);
+
+        // The node paints over the shadow wherever they overlap
+        assert_eq!(buffer[(0, 0)].bg, blue);
+        assert_eq!(buffer[(2, 1)].bg, blue);
+        assert_eq!(buffer[(3, 1)].bg, red);
+        assert_eq!(buffer[(1, 2)].bg, red);
+        assert_eq!(buffer[(3, 2)].bg, red);
+        assert_ne!(buffer[(0, 2)].bg, red);
+        assert_ne!(buffer[(4, 0)].bg, red);
+    }
+
+    #[test]
+    fn opacity_blends_toward_backdrop() {
+        let view = div(div(text("hi")).attribute(
+            pose!("style"),
+            "width: 2; height: 1; color: rgb(200, 200, 200); background-color: rgb(100, 0, 0); opacity: 0.5",
+        ))
+        .attribute(
+            pose!("style"),
+            "width: 4; height: 2; background-color: rgb(0, 0, 0); opacity: 0.5",
+        );
+
+        let terminal = render(view, 4, 2);
+        let buffer = terminal.backend().buffer();
+
+        // The inner box is at a quarter once its parent's opacity compounds
+        assert_eq!(buffer[(0, 0)].bg, RatColor::Rgb(25, 0, 0));
+        assert_eq!(buffer[(0, 0)].fg, RatColor::Rgb(69, 50, 50));
+        assert_eq!(buffer[(3, 1)].bg, RatColor::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn text_overflow_ellipsis_truncates() {
+        let view = div(text("hello world")).attribute(
+            pose!("style"),
+            "width: 5; white-space: nowrap; overflow: hidden; text-overflow: ellipsis",
+        );
+
+        let terminal = render(view, 12, 1);
+        let buffer = terminal.backend().buffer();
+
+        let row: String = (0..6).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert_eq!(row, "hell… ");
+    }
+
+    #[test]
+    fn hidden_overflow_clips_text() {
+        let view = div(text("hello world")).attribute(
+            pose!("style"),
+            "width: 5; white-space: nowrap; overflow: hidden",
+        );
+
+        let terminal = render(view, 12, 1);
+        let buffer = terminal.backend().buffer();
+
+        let row: String = (0..6).map(|x| buffer[(x, 0)].symbol()).collect();
+        assert_eq!(row, "hello ");
+    }
+
+    #[test]
+    fn paint_order_keeps_auto_in_document_order() {
+        let document = build(fragment![
+            div(()).attribute(pose!("id"), "a"),
+            div(())
+                .attribute(pose!("id"), "b")
+                .attribute(pose!("style"), "z-index: 1"),
+            div(()).attribute(pose!("id"), "c"),
+            div(())
+                .attribute(pose!("id"), "d")
+                .attribute(pose!("style"), "z-index: -1"),
+            div(())
+                .attribute(pose!("id"), "e")
+                .attribute(pose!("style"), "z-index: 0"),
+        ]);
+
+        let ids: Vec<_> = paint_order(&document, document.root())
+            .into_iter()
+            .filter_map(|id| {
+                document
+                    .get_node(id)
+                    .as_element()?
+                    .get_attribute(pose!("id"))
+            })
+            .collect();
+
+        assert_eq!(ids, ["d", "a", "c", "e", "b"]);
+    }
+}
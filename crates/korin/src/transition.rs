@@ -0,0 +1,145 @@
+use std::{sync::Arc, time::Duration};
+
+use capsule_corp::{ANIMATABLE, ComputedStyle, Property, transition_duration};
+use indextree::NodeId;
+use rustc_hash::FxHashMap;
+
+/// Transitions in flight. One starts whenever a restyle changes an
+/// animatable property that the new style asks to transition.
+#[derive(Debug, Default)]
+pub struct Transitions {
+    running: FxHashMap<NodeId, Transitioning>,
+}
+
+#[derive(Debug)]
+struct Transitioning {
+    /// What the cascade settled on, shown as is once everything has finished
+    target: Arc<ComputedStyle>,
+    properties: Vec<Running>,
+}
+
+#[derive(Debug)]
+struct Running {
+    property: Property,
+    from: Arc<ComputedStyle>,
+    duration: Duration,
+    elapsed: Duration,
+}
+
+impl Running {
+    fn progress(&self) -> f32 {
+        (self.elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+impl Transitioning {
+    fn style(&self) -> Arc<ComputedStyle> {
+        let mut style = (*self.target).clone();
+
+        for running in &self.properties {
+            style.interpolate(
+                running.property,
+                &running.from,
+                &self.target,
+                running.progress(),
+            );
+        }
+
+        Arc::new(style)
+    }
+}
+
+impl Transitions {
+    /// The style to show for `node` now that the cascade computed `target`
+    /// for it, starting transitions away from what's `shown` right now.
+    pub fn restyle(
+        &mut self,
+        node: NodeId,
+        shown: Option<&Arc<ComputedStyle>>,
+        target: Arc<ComputedStyle>,
+    ) -> Arc<ComputedStyle> {
+        // Restyled for some other reason, so carry on from where it was
+        if let Some(transitioning) = self.running.get(&node)
+            && *transitioning.target == *target
+            && let Some(shown) = shown
+        {
+            return shown.clone();
+        }
+
+        let Some(shown) = shown else {
+            self.running.remove(&node);
+            return target;
+        };
+
+        // Starting from what's on screen means interrupting a transition
+        // turns it around instead of jumping
+        let properties: Vec<_> = ANIMATABLE
+            .iter()
+            .filter_map(|&property| {
+                let duration = transition_duration(&target.transitions, property)?;
+
+                shown.differs(&target, property).then(|| Running {
+                    property,
+                    from: shown.clone(),
+                    duration,
+                    elapsed: Duration::ZERO,
+                })
+            })
+            .collect();
+
+        if properties.is_empty() {
+            self.running.remove(&node);
+            return target;
+        }
+
+        let transitioning = Transitioning { target, properties };
+        let style = transitioning.style();
+        self.running.insert(node, transitioning);
+
+        style
+    }
+
+    /// Moves every transition `dt` along, returning the style each node
+    /// should show now.
+    pub fn tick(&mut self, dt: Duration) -> Vec<(NodeId, Arc<ComputedStyle>)> {
+        let mut styles = Vec::with_capacity(self.running.len());
+
+        self.running.retain(|&node, transitioning| {
+            for running in &mut transitioning.properties {
+                running.elapsed = running.elapsed.saturating_add(dt);
+            }
+
+            transitioning
+                .properties
+                .retain(|running| !running.is_finished());
+
+            if transitioning.properties.is_empty() {
+                styles.push((node, transitioning.target.clone()));
+                return false;
+            }
+
+            styles.push((node, transitioning.style()));
+            true
+        });
+
+        styles
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.running.is_empty()
+    }
+
+    /// Drop the transition running on `node`, if any.
+    pub fn remove(&mut self, node: NodeId) {
+        self.running.remove(&node);
+    }
+
+    /// Drop every transition, leaving nodes on whatever they show now.
+    pub fn clear(&mut self) {
+        self.running.clear();
+    }
+}
@@ -19,6 +19,13 @@ impl AnyView {
     {
         Self(Box::new(view))
     }
+
+    /// The view this was made from, if it's a `V`. Only `'static` views can
+    /// be erased, which is what lets their type be checked here.
+    #[must_use]
+    pub fn downcast_ref<V: View + 'static>(&self) -> Option<&V> {
+        self.0.as_any().downcast_ref()
+    }
 }
 
 impl View for AnyView {
@@ -64,6 +71,7 @@ impl Mountable for AnyViewState {
 trait ErasedView {
     fn build_erased(self: Box<Self>, ctx: &mut BuildContext) -> AnyViewState;
     fn rebuild_erased(self: Box<Self>, state: &mut AnyViewState, ctx: &mut RebuildContext);
+    fn as_any(&self) -> &dyn std::any::Any;
 }
 
 impl<V: View + 'static> ErasedView for V
@@ -80,6 +88,10 @@ where
             .expect("AnyView state type mismatch - view type changed between build and rebuild");
         (*self).rebuild(inner, ctx);
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 trait ErasedMountable {
@@ -111,3 +123,17 @@ impl<T: Mountable + 'static> ErasedMountable for T {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::{ElementView, TextView, text};
+
+    #[test]
+    fn downcast_to_concrete_view() {
+        let view = AnyView::new(text("Hello"));
+
+        assert!(view.downcast_ref::<TextView>().is_some());
+        assert!(view.downcast_ref::<ElementView<()>>().is_none());
+    }
+}
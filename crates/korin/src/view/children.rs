@@ -1,7 +1,7 @@
 use std::rc::Rc;
 
 use crate::view::{
-    AnyViewState, View,
+    AnyViewState, Either, EitherState, View,
     any_view::AnyView,
     context::{BuildContext, RebuildContext},
 };
@@ -53,6 +53,72 @@ impl View for ViewFn {
     }
 }
 
+/// A named place in a component for the caller to fill, like a card's header
+/// or footer. Renders nothing when left empty.
+///
+/// Components take their slots as props next to `children`:
+/// ```ignore
+/// struct CardProps {
+///     header: Slot,
+///     children: Children,
+/// }
+///
+/// fn card(props: CardProps) -> impl View {
+///     div(fragment![props.header, div(props.children).class(pose!("body"))])
+/// }
+///
+/// card(CardProps {
+///     header: Slot::new(|| text("Title")),
+///     children: Box::new(|| AnyView::new(text("Body"))),
+/// })
+/// ```
+#[derive(Default)]
+pub struct Slot(Option<ViewFn>);
+
+impl Slot {
+    pub fn new<F, V>(f: F) -> Self
+    where
+        F: Fn() -> V + 'static,
+        V: View + 'static,
+        V::State: 'static,
+    {
+        Self(Some(ViewFn::new(f)))
+    }
+
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(None)
+    }
+
+    #[must_use]
+    pub const fn is_filled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn into_either(self) -> Either<AnyView, ()> {
+        self.0
+            .map_or(Either::Right(()), |view| Either::Left(view.call()))
+    }
+}
+
+impl From<ViewFn> for Slot {
+    fn from(view: ViewFn) -> Self {
+        Self(Some(view))
+    }
+}
+
+impl View for Slot {
+    type State = EitherState<AnyViewState, ()>;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        self.into_either().build(ctx)
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        self.into_either().rebuild(state, ctx);
+    }
+}
+
 impl View for Children {
     type State = AnyViewState;
 
@@ -76,3 +142,58 @@ impl View for ChildrenFn {
         self().rebuild(state, ctx);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ginyu_force::pose;
+
+    use super::*;
+    use crate::{
+        document::Document,
+        fragment,
+        view::{Mountable, div, text},
+    };
+
+    struct CardProps {
+        header: Slot,
+        children: Children,
+    }
+
+    fn card(props: CardProps) -> impl View {
+        div(fragment![
+            props.header,
+            div(props.children)
+                .class(pose!("body"))
+                .attribute(pose!("style"), "border: solid"),
+        ])
+    }
+
+    #[test]
+    fn component_renders_children_and_slots() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = card(CardProps {
+            header: Slot::new(|| text("Title")),
+            children: Box::new(|| AnyView::new(text("Body"))),
+        });
+
+        let mut state = view.build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        let html = doc.debug_html();
+        let title = html.find("Title").expect("failed");
+        let body = html.find(r#"class="body""#).expect("failed");
+        assert!(title < body);
+        assert!(html.contains("border: solid"));
+        assert!(html.contains("Body"));
+
+        let view = card(CardProps {
+            header: Slot::empty(),
+            children: Box::new(|| AnyView::new(text("Body"))),
+        });
+        view.rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+
+        assert!(!doc.debug_html().contains("Title"));
+    }
+}
@@ -35,6 +35,10 @@ impl<'a> BuildContext<'a> {
         self.document.create_text(content)
     }
 
+    pub fn create_raw_text(&mut self, content: impl Into<String>) -> NodeId {
+        self.document.create_raw_text(content)
+    }
+
     pub fn set_attribute(&mut self, node: NodeId, name: Pose, value: impl Into<String>) {
         if let Some(element) = self.document.get_mut(node).and_then(|n| n.as_element_mut()) {
             element.set_attribute(name, value);
@@ -82,14 +86,20 @@ impl<'a> RebuildContext<'a> {
         self.document
     }
 
+    /// Replace the classes of `node`, leaving it alone if they're the same.
     pub fn set_classes(&mut self, node: NodeId, classes: SmallVec<[Pose; 4]>) {
-        if let Some(element) = self.document.get_mut(node).and_then(|n| n.as_element_mut()) {
+        if let Some(element) = self.document.get_mut(node).and_then(|n| n.as_element_mut())
+            && element.classes != classes
+        {
             element.set_classes(classes);
         }
     }
 
+    /// Replace the attributes of `node`, leaving it alone if they're the same.
     pub fn set_attributes(&mut self, node: NodeId, attributes: FxHashMap<Pose, String>) {
-        if let Some(element) = self.document.get_mut(node).and_then(|n| n.as_element_mut()) {
+        if let Some(element) = self.document.get_mut(node).and_then(|n| n.as_element_mut())
+            && element.attributes != attributes
+        {
             element.set_attributes(attributes);
         }
     }
@@ -1,4 +1,5 @@
-use ginyu_force::Pose;
+use capsule_corp::{restyle_for_attribute_change, restyle_for_class_change};
+use ginyu_force::{Pose, pose};
 use indextree::NodeId;
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
@@ -6,16 +7,22 @@ use smallvec::SmallVec;
 use crate::{
     document::Document,
     view::{
-        Mountable, View,
+        Mountable, NodeRef, View,
         context::{BuildContext, RebuildContext},
     },
 };
 
+type Condition = Box<dyn Fn() -> bool>;
+type Binding = Box<dyn Fn() -> String>;
+
 pub struct ElementView<Children> {
     tag: Pose,
     id: Option<Pose>,
     classes: SmallVec<[Pose; 4]>,
+    toggled_classes: SmallVec<[(Pose, Condition); 2]>,
     attributes: FxHashMap<Pose, String>,
+    bound_attributes: SmallVec<[(Pose, Binding); 2]>,
+    node_ref: Option<NodeRef>,
     children: Children,
 }
 
@@ -25,7 +32,10 @@ impl<Children> ElementView<Children> {
             tag,
             id: None,
             classes: SmallVec::new(),
+            toggled_classes: SmallVec::new(),
             attributes: FxHashMap::default(),
+            bound_attributes: SmallVec::new(),
+            node_ref: None,
             children,
         }
     }
@@ -42,15 +52,74 @@ impl<Children> ElementView<Children> {
         self
     }
 
+    /// Add `class` whenever `when` returns true, checked again on every
+    /// rebuild. When the answer flips, whatever depends on the class is
+    /// restyled straight away.
+    ///
+    /// ```ignore
+    /// button(text("Save")).class_if(pose!("active"), move || active.get())
+    /// ```
+    #[must_use]
+    pub fn class_if(mut self, class: Pose, when: impl Fn() -> bool + 'static) -> Self {
+        self.toggled_classes.push((class, Box::new(when)));
+        self
+    }
+
     #[must_use]
     pub fn attribute(mut self, name: Pose, value: impl Into<String>) -> Self {
         self.attributes.insert(name, value.into());
         self
     }
+
+    /// Set `name` to whatever `value` returns, checked again on every
+    /// rebuild. A new value restyles whatever depends on the attribute.
+    ///
+    /// ```ignore
+    /// div(()).attr_signal(pose!("aria-expanded"), move || open.get().to_string())
+    /// ```
+    #[must_use]
+    pub fn attr_signal<V>(mut self, name: Pose, value: impl Fn() -> V + 'static) -> Self
+    where
+        V: Into<String>,
+    {
+        self.bound_attributes
+            .push((name, Box::new(move || value().into())));
+        self
+    }
+
+    /// Bind the inline `style` attribute, see [`Self::attr_signal`].
+    #[must_use]
+    pub fn style_signal<V>(self, style: impl Fn() -> V + 'static) -> Self
+    where
+        V: Into<String>,
+    {
+        self.attr_signal(pose!("style"), style)
+    }
+
+    /// Store the built node in `node_ref`.
+    #[must_use]
+    pub fn node_ref(mut self, node_ref: &NodeRef) -> Self {
+        self.node_ref = Some(node_ref.clone());
+        self
+    }
+
+    /// Apply `f` to the builder only when `condition` holds.
+    ///
+    /// ```ignore
+    /// button(text("Save")).when(focused, |b| b.class(pose!("focused")))
+    /// ```
+    #[must_use]
+    pub fn when(self, condition: bool, f: impl FnOnce(Self) -> Self) -> Self {
+        if condition { f(self) } else { self }
+    }
 }
 
 pub struct ElementViewState<ChildState> {
     node: NodeId,
+    /// What each `class_if` condition last returned
+    toggled_classes: SmallVec<[(Pose, bool); 2]>,
+    /// What each `attr_signal` last returned
+    bound_attributes: SmallVec<[(Pose, String); 2]>,
     children_state: ChildState,
 }
 
@@ -60,24 +129,70 @@ impl<ChildState> ElementViewState<ChildState> {
     }
 }
 
+impl<Children> ElementView<Children> {
+    fn check_toggled_classes(&mut self) -> SmallVec<[(Pose, bool); 2]> {
+        self.toggled_classes
+            .drain(..)
+            .map(|(class, when)| (class, when()))
+            .collect()
+    }
+
+    fn take_classes(&mut self, toggled: &[(Pose, bool)]) -> SmallVec<[Pose; 4]> {
+        let mut classes = std::mem::take(&mut self.classes);
+
+        for &(class, on) in toggled {
+            if on && !classes.contains(&class) {
+                classes.push(class);
+            }
+        }
+
+        classes
+    }
+
+    fn check_bound_attributes(&mut self) -> SmallVec<[(Pose, String); 2]> {
+        self.bound_attributes
+            .drain(..)
+            .map(|(name, value)| (name, value()))
+            .collect()
+    }
+
+    fn take_attributes(&mut self, bound: &[(Pose, String)]) -> FxHashMap<Pose, String> {
+        let mut attributes = std::mem::take(&mut self.attributes);
+
+        for (name, value) in bound {
+            attributes.insert(*name, value.clone());
+        }
+
+        attributes
+    }
+}
+
 impl<Children> View for ElementView<Children>
 where
     Children: View,
 {
     type State = ElementViewState<Children::State>;
 
-    fn build(self, ctx: &mut BuildContext) -> Self::State {
+    fn build(mut self, ctx: &mut BuildContext) -> Self::State {
         let node = ctx.create_element(self.tag);
 
+        if let Some(node_ref) = &self.node_ref {
+            node_ref.set(node);
+        }
+
         if let Some(id) = self.id {
             ctx.set_id(node, id);
         }
 
-        for class in self.classes {
+        let toggled_classes = self.check_toggled_classes();
+
+        for class in self.take_classes(&toggled_classes) {
             ctx.add_class(node, class);
         }
 
-        for (name, value) in self.attributes {
+        let bound_attributes = self.check_bound_attributes();
+
+        for (name, value) in self.take_attributes(&bound_attributes) {
             ctx.set_attribute(node, name, value);
         }
 
@@ -85,19 +200,64 @@ where
 
         ElementViewState {
             node,
+            toggled_classes,
+            bound_attributes,
             children_state,
         }
     }
 
-    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+    fn rebuild(mut self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        let toggled_classes = self.check_toggled_classes();
+        let classes = self.take_classes(&toggled_classes);
+        let bound_attributes = self.check_bound_attributes();
+        let attributes = self.take_attributes(&bound_attributes);
+
+        if let Some(node_ref) = &self.node_ref {
+            node_ref.set(state.node);
+        }
+
         ctx.set_id(state.node, self.id);
-        ctx.set_attributes(state.node, self.attributes);
-        ctx.set_classes(state.node, self.classes);
+        ctx.set_attributes(state.node, attributes);
+        ctx.set_classes(state.node, classes);
 
         self.children.rebuild(&mut state.children_state, ctx);
+
+        let flipped = changed(&state.toggled_classes, &toggled_classes);
+        let rebound = changed(&state.bound_attributes, &bound_attributes);
+        state.toggled_classes = toggled_classes;
+        state.bound_attributes = bound_attributes;
+
+        for class in flipped {
+            restyle_for_class_change(ctx.document_mut(), state.node, class);
+        }
+
+        for name in rebound {
+            restyle_for_attribute_change(ctx.document_mut(), state.node, name);
+        }
     }
 }
 
+/// The names whose value differs from the last rebuild, including those that
+/// were only there last time or are only there now.
+fn changed<T: PartialEq>(last: &[(Pose, T)], now: &[(Pose, T)]) -> SmallVec<[Pose; 2]> {
+    fn value<T>(entries: &[(Pose, T)], name: Pose) -> Option<&T> {
+        entries
+            .iter()
+            .find(|(entry, _)| *entry == name)
+            .map(|(_, value)| value)
+    }
+
+    let mut names = SmallVec::new();
+
+    for (name, _) in last.iter().chain(now) {
+        if !names.contains(name) && value(last, *name) != value(now, *name) {
+            names.push(*name);
+        }
+    }
+
+    names
+}
+
 impl<ChildState: Mountable> Mountable for ElementViewState<ChildState> {
     fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, doc: &mut Document) {
         match marker {
@@ -118,3 +278,143 @@ impl<ChildState: Mountable> Mountable for ElementViewState<ChildState> {
         Some(self.node)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use capsule_corp::{CapsuleDocument, Color, Dimension, Length, Stylesheet, compute_styles};
+
+    use super::*;
+
+    fn styled_document(css: &str) -> Document {
+        let mut doc = Document::new();
+        let stylesheet = Stylesheet::parse(css).expect("failed");
+        doc.stylist_mut().add_stylesheet(&stylesheet);
+        doc
+    }
+
+    fn color(doc: &Document, node: NodeId) -> Color {
+        doc.computed_style(node).expect("failed").color
+    }
+
+    #[test]
+    fn class_if_follows_condition() {
+        let mut doc = styled_document(".active { color: red }");
+        let root = doc.root();
+        let active = Rc::new(Cell::new(false));
+
+        let view = || {
+            let active = active.clone();
+            ElementView::new(pose!("button"), ())
+                .class(pose!("button"))
+                .class_if(pose!("active"), move || active.get())
+        };
+        let has_class = |doc: &Document, node, class| {
+            doc.get(node)
+                .and_then(|n| n.as_element())
+                .is_some_and(|e| e.has_class(class))
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view().build(&mut ctx);
+        state.mount(root, None, &mut doc);
+        compute_styles(&mut doc);
+        assert!(has_class(&doc, state.node(), "button"));
+        assert!(!has_class(&doc, state.node(), "active"));
+        let unstyled = color(&doc, state.node());
+
+        // Flipping restyles right away, without another `compute_styles`
+        active.set(true);
+        view().rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert!(has_class(&doc, state.node(), "button"));
+        assert!(has_class(&doc, state.node(), "active"));
+        assert_eq!(color(&doc, state.node()), Color::RED);
+
+        active.set(false);
+        view().rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert!(!has_class(&doc, state.node(), "active"));
+        assert_eq!(color(&doc, state.node()), unstyled);
+    }
+
+    #[test]
+    fn dropped_class_if_restyles() {
+        let mut doc = styled_document(".active { color: red }");
+        let root = doc.root();
+        let shown = Rc::new(Cell::new(true));
+
+        let view = || {
+            ElementView::new(pose!("button"), ())
+                .when(shown.get(), |view| view.class_if(pose!("active"), || true))
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view().build(&mut ctx);
+        state.mount(root, None, &mut doc);
+        compute_styles(&mut doc);
+        assert_eq!(color(&doc, state.node()), Color::RED);
+
+        // The `class_if` is gone rather than false, the class still goes
+        shown.set(false);
+        view().rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert_ne!(color(&doc, state.node()), Color::RED);
+    }
+
+    #[test]
+    fn attr_signal_follows_value() {
+        let mut doc = styled_document("[aria-label=\"5 wide\"] { color: red }");
+        let root = doc.root();
+        let width = Rc::new(Cell::new(2));
+
+        let view = || {
+            let label = width.clone();
+            let width = width.clone();
+            ElementView::new(pose!("div"), ())
+                .attr_signal(pose!("aria-label"), move || format!("{} wide", label.get()))
+                .style_signal(move || format!("width: {}", width.get()))
+        };
+        let attribute = |doc: &Document, node, name| {
+            doc.get(node)
+                .and_then(|n| n.as_element())
+                .and_then(|e| e.get_attribute(name))
+                .map(str::to_owned)
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view().build(&mut ctx);
+        state.mount(root, None, &mut doc);
+        compute_styles(&mut doc);
+        assert_eq!(
+            attribute(&doc, state.node(), pose!("aria-label")).as_deref(),
+            Some("2 wide")
+        );
+        assert_ne!(color(&doc, state.node()), Color::RED);
+
+        width.set(5);
+        view().rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert_eq!(color(&doc, state.node()), Color::RED);
+        // The inline style is picked up too
+        let style = doc.computed_style(state.node()).expect("failed");
+        assert_eq!(style.width, Dimension::Length(Length::Cells(5)));
+        assert_eq!(
+            attribute(&doc, state.node(), pose!("aria-label")).as_deref(),
+            Some("5 wide")
+        );
+        assert_eq!(
+            attribute(&doc, state.node(), pose!("style")).as_deref(),
+            Some("width: 5")
+        );
+    }
+
+    #[test]
+    fn node_ref_is_set_on_build() {
+        let mut doc = Document::new();
+        let node_ref = NodeRef::new();
+
+        let view = ElementView::new(pose!("div"), ()).node_ref(&node_ref);
+        assert_eq!(node_ref.get(), None);
+
+        let state = view.build(&mut BuildContext::new(&mut doc));
+        assert_eq!(node_ref.get(), Some(state.node()));
+    }
+}
@@ -0,0 +1,272 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use indextree::NodeId;
+
+use crate::{
+    document::Document,
+    view::{BuildContext, Mountable, RebuildContext, View},
+};
+
+/// Renders `fallback` instead of `children` when building or rebuilding the
+/// children panics.
+///
+/// Once it has fallen back it stays that way until `deps` changes, then the
+/// children get built again from scratch.
+///
+/// # Unwind safety
+///
+/// The children are run under [`AssertUnwindSafe`], so anything they were
+/// halfway through changing is left as it was when they panicked. Nodes
+/// they created are never mounted but stay in the document, and any shared
+/// state they touched (hooks, `Rc<RefCell<_>>`, ...) may be inconsistent.
+/// Hook scopes entered with [`potara::with_scope`] (keyed `for_each` and
+/// fragments) are left again on the way out, so hooks after the boundary
+/// keep their keys. The panic hook still runs, so the message is printed as
+/// usual.
+///
+/// # Example
+/// ```ignore
+/// ErrorBoundary::new(
+///     path.clone(),
+///     move || preview(&path),
+///     || text("Couldn't show this file"),
+/// )
+/// ```
+pub struct ErrorBoundary<D, C, F> {
+    deps: D,
+    children: C,
+    fallback: F,
+}
+
+impl<D, C, F, V, W> ErrorBoundary<D, C, F>
+where
+    D: PartialEq,
+    C: FnOnce() -> V,
+    F: FnOnce() -> W,
+    V: View,
+    W: View,
+{
+    pub const fn new(deps: D, children: C, fallback: F) -> Self {
+        Self {
+            deps,
+            children,
+            fallback,
+        }
+    }
+}
+
+pub struct ErrorBoundaryState<D, A, B> {
+    deps: D,
+    marker: NodeId,
+    branch: Branch<A, B>,
+    parent: Option<NodeId>,
+}
+
+enum Branch<A, B> {
+    Children(A),
+    Fallback(B),
+}
+
+impl<D, A: Mountable, B: Mountable> ErrorBoundaryState<D, A, B> {
+    /// Whether the children panicked and the fallback is showing.
+    pub const fn has_failed(&self) -> bool {
+        matches!(self.branch, Branch::Fallback(_))
+    }
+
+    fn replace(&mut self, branch: Branch<A, B>, document: &mut Document) {
+        match &mut self.branch {
+            Branch::Children(a) => a.unmount(document),
+            Branch::Fallback(b) => b.unmount(document),
+        }
+
+        self.branch = branch;
+
+        if let Some(parent) = self.parent {
+            self.mount_branch(parent, document);
+        }
+    }
+
+    fn mount_branch(&mut self, parent: NodeId, document: &mut Document) {
+        match &mut self.branch {
+            Branch::Children(a) => a.mount(parent, Some(self.marker), document),
+            Branch::Fallback(b) => b.mount(parent, Some(self.marker), document),
+        }
+    }
+}
+
+fn try_build<V: View>(children: impl FnOnce() -> V, ctx: &mut BuildContext) -> Option<V::State> {
+    catch_unwind(AssertUnwindSafe(|| children().build(ctx))).ok()
+}
+
+impl<D, C, F, V, W> View for ErrorBoundary<D, C, F>
+where
+    D: PartialEq,
+    C: FnOnce() -> V,
+    F: FnOnce() -> W,
+    V: View,
+    W: View,
+{
+    type State = ErrorBoundaryState<D, V::State, W::State>;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        let marker = ctx.create_marker();
+
+        let branch = match try_build(self.children, ctx) {
+            Some(state) => Branch::Children(state),
+            None => Branch::Fallback((self.fallback)().build(ctx)),
+        };
+
+        ErrorBoundaryState {
+            deps: self.deps,
+            marker,
+            branch,
+            parent: None,
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        let deps_changed = state.deps != self.deps;
+        state.deps = self.deps;
+
+        match &mut state.branch {
+            Branch::Children(a) => {
+                let rebuilt = catch_unwind(AssertUnwindSafe(|| {
+                    (self.children)().rebuild(a, ctx);
+                }));
+
+                if rebuilt.is_err() {
+                    let mut build_ctx = BuildContext::new(ctx.document_mut());
+                    let fallback = (self.fallback)().build(&mut build_ctx);
+
+                    state.replace(Branch::Fallback(fallback), ctx.document_mut());
+                }
+            }
+            Branch::Fallback(b) => {
+                if deps_changed {
+                    let mut build_ctx = BuildContext::new(ctx.document_mut());
+
+                    if let Some(children) = try_build(self.children, &mut build_ctx) {
+                        state.replace(Branch::Children(children), ctx.document_mut());
+                        return;
+                    }
+                }
+
+                (self.fallback)().rebuild(b, ctx);
+            }
+        }
+    }
+}
+
+impl<D, A: Mountable, B: Mountable> Mountable for ErrorBoundaryState<D, A, B> {
+    fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
+        self.parent = Some(parent);
+
+        match marker {
+            Some(marker) => document.insert_before(marker, self.marker),
+            None => document.append_child(parent, self.marker),
+        }
+
+        self.mount_branch(parent, document);
+    }
+
+    fn unmount(&mut self, document: &mut Document) {
+        match &mut self.branch {
+            Branch::Children(a) => a.unmount(document),
+            Branch::Fallback(b) => b.unmount(document),
+        }
+
+        document.detach(self.marker);
+        self.parent = None;
+    }
+
+    fn first_node(&self) -> Option<NodeId> {
+        match &self.branch {
+            Branch::Children(a) => a.first_node(),
+            Branch::Fallback(b) => b.first_node(),
+        }
+        .or(Some(self.marker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::TextView;
+
+    fn texts(doc: &Document, parent: NodeId) -> Vec<String> {
+        doc.children(parent)
+            .filter_map(|child| doc.get(child)?.as_text().map(str::to_owned))
+            .collect()
+    }
+
+    #[test]
+    fn panicking_child_shows_fallback() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = ErrorBoundary::new(
+            0,
+            || -> TextView { panic!("broken child") },
+            || TextView::new("Something went wrong"),
+        );
+
+        let mut state = view.build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        assert!(state.has_failed());
+        assert_eq!(texts(&doc, root), ["Something went wrong"]);
+    }
+
+    #[test]
+    fn panic_inside_a_hook_scope_leaves_it() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let state = potara::use_state_at("error_boundary", 0, 0, || 0);
+        state.set(7);
+        potara::reset_frame();
+
+        let view = ErrorBoundary::new(
+            0,
+            || -> TextView { potara::with_scope("row", || panic!("broken row")) },
+            || TextView::new("Something went wrong"),
+        );
+
+        let mut state = view.build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+        assert!(state.has_failed());
+
+        let state = potara::use_state_at("error_boundary", 0, 0, || 0);
+        assert_eq!(state.get(), 7);
+
+        potara::reset_frame();
+    }
+
+    #[test]
+    fn retries_when_deps_change() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = |attempt: u32| {
+            ErrorBoundary::new(
+                attempt,
+                move || {
+                    assert!(attempt > 0, "not ready yet");
+                    TextView::new("Loaded")
+                },
+                || TextView::new("Failed"),
+            )
+        };
+
+        let mut state = view(0).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+        assert_eq!(texts(&doc, root), ["Failed"]);
+
+        view(0).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert_eq!(texts(&doc, root), ["Failed"]);
+
+        view(1).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert!(!state.has_failed());
+        assert_eq!(texts(&doc, root), ["Loaded"]);
+    }
+}
@@ -14,7 +14,8 @@ type FxIndexSet<T> = IndexSet<T, FxBuildHasher>;
 ///
 /// Iterates over items and renders each with a view function. Items are keyed
 /// for efficient updates - when the list changes, only added/removed/moved items
-/// are updated in the DOM.
+/// are updated in the DOM. An item whose key is still there keeps its nodes and
+/// hook state, reordering moves it instead of building it again.
 ///
 /// # Example
 /// ```ignore
@@ -528,6 +529,39 @@ mod tests {
         assert_eq!(doc.get(children[2]).expect("failed").as_text(), Some("b"));
     }
 
+    #[test]
+    fn for_each_reverse_keeps_nodes() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = ForView {
+            items: vec!["a", "b", "c"].into_iter(),
+            key_fn: |s: &&str| *s,
+            view_fn: |s: &str| text_view(s),
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let before: Vec<_> = doc.children(root).collect();
+
+        let view = ForView {
+            items: vec!["c", "b", "a"].into_iter(),
+            key_fn: |s: &&str| *s,
+            view_fn: |s: &str| text_view(s),
+        };
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        view.rebuild(&mut state, &mut ctx);
+
+        // Same nodes, moved rather than rebuilt
+        let after: Vec<_> = doc.children(root).collect();
+        assert_eq!(after, [before[2], before[1], before[0], before[3]]);
+        assert_eq!(doc.get(after[0]).expect("failed").as_text(), Some("c"));
+        assert_eq!(doc.get(after[2]).expect("failed").as_text(), Some("a"));
+    }
+
     #[test]
     fn for_each_clear() {
         let mut doc = Document::new();
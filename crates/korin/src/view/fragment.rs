@@ -1,4 +1,6 @@
+use ginyu_force::Pose;
 use indextree::NodeId;
+use rustc_hash::FxHashMap;
 
 use crate::{
     document::Document,
@@ -18,50 +20,224 @@ use crate::{
 ///     TextView::new("Third"),
 /// ])
 /// ```
+///
+/// Children can be keyed with [`Fragment::keyed`] (or `keyed_fragment!`), so
+/// when they're reordered or some come and go, the rest keep their nodes and
+/// hook state. Keys should be unique within the fragment.
 pub struct Fragment {
     children: Vec<AnyView>,
+    keys: Option<Vec<Pose>>,
 }
 
 impl Fragment {
     #[must_use]
     pub const fn new(children: Vec<AnyView>) -> Self {
-        Self { children }
+        Self {
+            children,
+            keys: None,
+        }
     }
 
     #[must_use]
-    pub const fn empty() -> Self {
+    pub fn keyed(children: Vec<(Pose, AnyView)>) -> Self {
+        let (keys, children) = children.into_iter().unzip();
+
         Self {
-            children: Vec::new(),
+            children,
+            keys: Some(keys),
         }
     }
+
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self::new(Vec::new())
+    }
 }
 
 impl FromIterator<AnyView> for Fragment {
     fn from_iter<T: IntoIterator<Item = AnyView>>(iter: T) -> Self {
-        Self {
-            children: iter.into_iter().collect(),
-        }
+        Self::new(iter.into_iter().collect())
     }
 }
 
 pub struct FragmentState {
     children: Vec<AnyViewState>,
+    keys: Option<Vec<Pose>>,
+    /// Where a keyed fragment ends, so moved and new children always have a
+    /// node of the fragment's own to go in front of
+    end: Option<NodeId>,
+    parent: Option<NodeId>,
+}
+
+impl FragmentState {
+    fn mount_children(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
+        // Mount children in reverse order so they end up in correct order
+        // Each child mounts before the marker (or previous child)
+        let mut current_marker = marker;
+        for child in self.children.iter_mut().rev() {
+            child.mount(parent, current_marker, document);
+            current_marker = child.first_node().or(current_marker);
+        }
+    }
+
+    fn rebuild_keyed(&mut self, keys: Vec<Pose>, children: Vec<AnyView>, ctx: &mut RebuildContext) {
+        // Unkeyed children can't be matched up, so they all go, leaving an
+        // end marker where they were
+        if self.keys.is_none() {
+            let end = ctx.document_mut().create_marker();
+
+            if let Some(parent) = self.parent {
+                match Mountable::first_node(self) {
+                    Some(first) => ctx.document_mut().insert_before(first, end),
+                    None => ctx.document_mut().append_child(parent, end),
+                }
+            }
+
+            for mut child in self.children.drain(..) {
+                child.unmount(ctx.document_mut());
+            }
+
+            self.end = Some(end);
+        }
+
+        let old_keys = self.keys.take().unwrap_or_default();
+        let mut previous: FxHashMap<Pose, (usize, AnyViewState)> = FxHashMap::default();
+
+        for (index, (key, state)) in old_keys
+            .into_iter()
+            .zip(self.children.drain(..))
+            .enumerate()
+        {
+            // Only one child per key can be matched up, so the others go
+            // rather than leaving their nodes behind
+            if let Some((_, mut duplicate)) = previous.insert(key, (index, state)) {
+                duplicate.unmount(ctx.document_mut());
+            }
+        }
+
+        let mut old_indices = Vec::with_capacity(keys.len());
+
+        for (&key, child) in keys.iter().zip(children) {
+            let (index, state) = potara::with_scope(key, || match previous.remove(&key) {
+                Some((index, mut state)) => {
+                    child.rebuild(&mut state, ctx);
+                    (Some(index), state)
+                }
+                None => (
+                    None,
+                    child.build(&mut BuildContext::new(ctx.document_mut())),
+                ),
+            });
+
+            old_indices.push(index);
+            self.children.push(state);
+        }
+
+        for (_, mut gone) in previous.into_values() {
+            gone.unmount(ctx.document_mut());
+        }
+
+        self.keys = Some(keys);
+
+        if let (Some(parent), Some(end)) = (self.parent, self.end) {
+            self.place_children(parent, end, &old_indices, ctx.document_mut());
+        }
+    }
+
+    /// Put the children back in order after a keyed rebuild. The longest run
+    /// that kept its order stays where it is, everything else (and anything
+    /// new) goes in front of the child that follows it.
+    fn place_children(
+        &mut self,
+        parent: NodeId,
+        end: NodeId,
+        old_indices: &[Option<usize>],
+        document: &mut Document,
+    ) {
+        let stays = longest_increasing(old_indices);
+
+        for ((child, &stays), index) in self.children.iter_mut().zip(&stays).zip(old_indices) {
+            if !stays && index.is_some() {
+                child.unmount(document);
+            }
+        }
+
+        let mut anchor = end;
+        for (child, &stays) in self.children.iter_mut().zip(&stays).rev() {
+            if !stays {
+                child.mount(parent, Some(anchor), document);
+            }
+
+            anchor = child.first_node().unwrap_or(anchor);
+        }
+    }
+}
+
+/// Which of `indices` make up their longest increasing run, skipping `None`s.
+fn longest_increasing(indices: &[Option<usize>]) -> Vec<bool> {
+    // `tails[n]` is where the smallest-ending run of length `n + 1` ends
+    let mut tails: Vec<usize> = Vec::new();
+    let mut previous = vec![None; indices.len()];
+
+    for (position, &index) in indices.iter().enumerate() {
+        if index.is_none() {
+            continue;
+        }
+
+        let length = tails.partition_point(|&tail| indices[tail] < index);
+        previous[position] = length.checked_sub(1).map(|length| tails[length]);
+
+        if length == tails.len() {
+            tails.push(position);
+        } else {
+            tails[length] = position;
+        }
+    }
+
+    let mut stays = vec![false; indices.len()];
+    let mut next = tails.last().copied();
+
+    while let Some(position) = next {
+        stays[position] = true;
+        next = previous[position];
+    }
+
+    stays
 }
 
 impl View for Fragment {
     type State = FragmentState;
 
     fn build(self, ctx: &mut BuildContext) -> Self::State {
-        let children = self
-            .children
-            .into_iter()
-            .map(|child| child.build(ctx))
-            .collect();
-
-        FragmentState { children }
+        let children = match &self.keys {
+            Some(keys) => keys
+                .iter()
+                .zip(self.children)
+                .map(|(&key, child)| potara::with_scope(key, || child.build(ctx)))
+                .collect(),
+            None => self
+                .children
+                .into_iter()
+                .map(|child| child.build(ctx))
+                .collect(),
+        };
+
+        let end = self.keys.is_some().then(|| ctx.create_marker());
+
+        FragmentState {
+            children,
+            keys: self.keys,
+            end,
+            parent: None,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        if let Some(keys) = self.keys {
+            state.rebuild_keyed(keys, self.children, ctx);
+            return;
+        }
+
         for (child, child_state) in self.children.into_iter().zip(state.children.iter_mut()) {
             child.rebuild(child_state, ctx);
         }
@@ -70,25 +246,37 @@ impl View for Fragment {
 
 impl Mountable for FragmentState {
     fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, doc: &mut Document) {
-        // Mount children in reverse order so they end up in correct order
-        // Each child mounts before the marker (or previous child)
-        let mut current_marker = marker;
-        for child in self.children.iter_mut().rev() {
-            child.mount(parent, current_marker, doc);
-            current_marker = child.first_node().or(current_marker);
+        self.parent = Some(parent);
+
+        // A keyed fragment's children go in front of its own end marker
+        if let Some(end) = self.end {
+            match marker {
+                Some(marker) => doc.insert_before(marker, end),
+                None => doc.append_child(parent, end),
+            }
         }
+
+        let marker = self.end.or(marker);
+        self.mount_children(parent, marker, doc);
     }
 
     fn unmount(&mut self, doc: &mut Document) {
         for child in &mut self.children {
             child.unmount(doc);
         }
+
+        if let Some(end) = self.end {
+            doc.detach(end);
+        }
+
+        self.parent = None;
     }
 
     fn first_node(&self) -> Option<NodeId> {
         self.children
             .iter()
             .find_map(super::mountable::Mountable::first_node)
+            .or(self.end)
     }
 }
 
@@ -101,10 +289,21 @@ macro_rules! fragment {
     };
 }
 
+#[macro_export]
+macro_rules! keyed_fragment {
+    ($($key:expr => $child:expr),* $(,)?) => {
+        $crate::view::Fragment::keyed(vec![
+            $(($key, $crate::view::AnyView::new($child))),*
+        ])
+    };
+}
+
 #[cfg(test)]
 mod tests {
+    use ginyu_force::pose;
+
     use super::*;
-    use crate::view::TextView;
+    use crate::view::{Either, TextView};
 
     #[test]
     fn fragment_build_and_mount() {
@@ -268,4 +467,119 @@ mod tests {
 
         assert_eq!(doc.children(root).count(), 3);
     }
+
+    #[test]
+    fn keyed_fragment_keeps_surrounding_nodes() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = keyed_fragment![
+            pose!("a") => TextView::new("A"),
+            pose!("b") => TextView::new("B"),
+            pose!("c") => TextView::new("C"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let before: Vec<_> = doc.children(root).collect();
+
+        let view = keyed_fragment![
+            pose!("a") => TextView::new("A"),
+            pose!("c") => TextView::new("C"),
+        ];
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        view.rebuild(&mut state, &mut ctx);
+
+        // The fragment's end marker comes last
+        let after: Vec<_> = doc.children(root).collect();
+        assert_eq!(after, [before[0], before[2], before[3]]);
+
+        let view = keyed_fragment![
+            pose!("c") => TextView::new("C"),
+            pose!("b") => TextView::new("B"),
+            pose!("a") => TextView::new("A"),
+        ];
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        view.rebuild(&mut state, &mut ctx);
+
+        let after: Vec<_> = doc.children(root).collect();
+        assert_eq!(after.len(), 4);
+        assert_eq!(after[0], before[2]);
+        assert_eq!(doc.get(after[1]).expect("failed").as_text(), Some("B"));
+        assert_eq!(after[2], before[0]);
+    }
+
+    #[test]
+    fn keyed_fragment_with_duplicate_keys_leaves_nothing_behind() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = keyed_fragment![
+            pose!("a") => TextView::new("A1"),
+            pose!("a") => TextView::new("A2"),
+            pose!("b") => TextView::new("B"),
+        ];
+
+        let mut state = view.build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        let view = keyed_fragment![pose!("b") => TextView::new("B")];
+        view.rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+
+        let texts: Vec<_> = doc
+            .children(root)
+            .filter_map(|child| doc.get(child)?.as_text())
+            .collect();
+        assert_eq!(texts, ["B"]);
+    }
+
+    #[test]
+    fn keyed_fragment_reorders_before_changed_sibling() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = |keys: [&'static str; 2], left: bool| {
+            let branch = if left {
+                Either::Left(TextView::new("L"))
+            } else {
+                Either::Right(TextView::new("R"))
+            };
+
+            Fragment::new(vec![
+                AnyView::new(Fragment::keyed(
+                    keys.iter()
+                        .map(|&key| (Pose::from(key), AnyView::new(TextView::new(key))))
+                        .collect(),
+                )),
+                AnyView::new(branch),
+            ])
+        };
+        let texts = |doc: &Document| -> Vec<String> {
+            doc.children(root)
+                .filter_map(|child| doc.get(child)?.as_text().map(str::to_owned))
+                .collect()
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view(["a", "b"], true).build(&mut ctx);
+        state.mount(root, None, &mut doc);
+        assert_eq!(texts(&doc), ["a", "b", "L"]);
+
+        // The marker the keyed fragment was mounted before goes away here
+        view(["a", "b"], false).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert_eq!(texts(&doc), ["a", "b", "R"]);
+
+        view(["b", "a"], false).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert_eq!(texts(&doc), ["b", "a", "R"]);
+    }
+
+    #[test]
+    fn longest_increasing_run() {
+        let stays = longest_increasing(&[Some(2), None, Some(0), Some(1), Some(3)]);
+        assert_eq!(stays, [false, false, true, true, true]);
+    }
 }
@@ -117,3 +117,8 @@ define_elements! {
 pub fn text(content: impl Into<String>) -> crate::view::TextView {
     crate::view::TextView::new(content)
 }
+
+/// Create a raw text node (convenience wrapper around `RawTextView`)
+pub fn raw_text(content: impl Into<String>) -> crate::view::RawTextView {
+    crate::view::RawTextView::new(content)
+}
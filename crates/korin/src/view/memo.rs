@@ -0,0 +1,112 @@
+use indextree::NodeId;
+
+use crate::{
+    document::Document,
+    view::{BuildContext, Mountable, RebuildContext, View},
+};
+
+/// Only runs `view_fn` and rebuilds its view when `deps` changed since the
+/// last rebuild, otherwise the existing nodes are left as they are.
+///
+/// # Example
+/// ```ignore
+/// Memo::new(items.len(), move || expensive_summary(&items))
+/// ```
+pub struct Memo<D, F> {
+    deps: D,
+    view_fn: F,
+}
+
+impl<D, F, V> Memo<D, F>
+where
+    D: PartialEq,
+    F: FnOnce() -> V,
+    V: View,
+{
+    pub const fn new(deps: D, view_fn: F) -> Self {
+        Self { deps, view_fn }
+    }
+}
+
+pub struct MemoState<D, S> {
+    deps: D,
+    state: S,
+}
+
+impl<D, F, V> View for Memo<D, F>
+where
+    D: PartialEq,
+    F: FnOnce() -> V,
+    V: View,
+{
+    type State = MemoState<D, V::State>;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        MemoState {
+            state: (self.view_fn)().build(ctx),
+            deps: self.deps,
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        if state.deps == self.deps {
+            return;
+        }
+
+        (self.view_fn)().rebuild(&mut state.state, ctx);
+        state.deps = self.deps;
+    }
+}
+
+impl<D, S: Mountable> Mountable for MemoState<D, S> {
+    fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
+        self.state.mount(parent, marker, document);
+    }
+
+    fn unmount(&mut self, document: &mut Document) {
+        self.state.unmount(document);
+    }
+
+    fn first_node(&self) -> Option<NodeId> {
+        self.state.first_node()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::view::TextView;
+
+    #[test]
+    fn memo_skips_unchanged_deps() {
+        let mut doc = Document::new();
+        let root = doc.root();
+        let calls = Cell::new(0);
+
+        let calls = &calls;
+        let view = |deps: u32| {
+            Memo::new(deps, move || {
+                calls.set(calls.get() + 1);
+                TextView::new(format!("Count: {deps}"))
+            })
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view(1).build(&mut ctx);
+        state.mount(root, None, &mut doc);
+        assert_eq!(calls.get(), 1);
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        view(1).rebuild(&mut state, &mut ctx);
+        assert_eq!(calls.get(), 1);
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        view(2).rebuild(&mut state, &mut ctx);
+        assert_eq!(calls.get(), 2);
+
+        let node = state.first_node().expect("failed");
+        assert_eq!(doc.get(node).expect("failed").as_text(), Some("Count: 2"));
+    }
+}
@@ -3,24 +3,36 @@ mod children;
 mod context;
 mod either;
 mod element;
+mod error_boundary;
 mod for_loop;
 mod fragment;
 pub mod html_elements;
+mod memo;
 mod mountable;
+mod node_ref;
+mod portal;
 mod show;
+mod suspense;
 mod text;
+mod toast;
 
 pub use any_view::{AnyView, AnyViewState};
-pub use children::{Children, ChildrenFn, ChildrenFnMut, ViewFn};
+pub use children::{Children, ChildrenFn, ChildrenFnMut, Slot, ViewFn};
 pub use context::{BuildContext, RebuildContext};
 pub use either::{Either, EitherState};
 pub use element::{ElementView, ElementViewState};
+pub use error_boundary::{ErrorBoundary, ErrorBoundaryState};
 pub use for_loop::for_each;
 pub use fragment::{Fragment, FragmentState};
 pub use html_elements::*;
+pub use memo::{Memo, MemoState};
 pub use mountable::Mountable;
-pub use show::{show, show_if, show_unless};
-pub use text::{TextView, TextViewState};
+pub use node_ref::NodeRef;
+pub use portal::{Portal, PortalState};
+pub use show::{show, show_if, show_some, show_unless};
+pub use suspense::{Resource, Suspense};
+pub use text::{RawTextView, TextView, TextViewState};
+pub use toast::{Corner, Toast, ToastId, ToastLevel, ToastManager, Toasts, ToastsState};
 /// A View is a declarative description of UI that is built into DOM nodes
 ///
 /// Views are consumed during `build()` to produce `State`, which holds
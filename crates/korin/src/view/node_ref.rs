@@ -0,0 +1,29 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use indextree::NodeId;
+
+/// A handle to the node an element view builds, for when a component
+/// needs to measure, scroll to or focus it.
+///
+/// The node is filled in when the view given the ref through
+/// [`ElementView::node_ref`](crate::view::ElementView::node_ref) is built,
+/// and every clone of the ref sees it.
+#[derive(Debug, Clone, Default)]
+pub struct NodeRef(Arc<Mutex<Option<NodeId>>>);
+
+impl NodeRef {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The node this ref is attached to, `None` until it has been built.
+    #[must_use]
+    pub fn get(&self) -> Option<NodeId> {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn set(&self, node: NodeId) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = Some(node);
+    }
+}
@@ -0,0 +1,120 @@
+use indextree::NodeId;
+
+use crate::{
+    document::Document,
+    view::{BuildContext, Mountable, RebuildContext, View},
+};
+
+/// Renders its children under `target` instead of where the portal sits.
+///
+/// Useful for modals and tooltips that belong to a component but need to
+/// live in an overlay layer. A marker keeps the portal's place among its
+/// siblings, and unmounting the portal removes the children from `target`.
+pub struct Portal<V> {
+    target: NodeId,
+    children: V,
+}
+
+impl<V: View> Portal<V> {
+    #[must_use]
+    pub const fn new(target: NodeId, children: V) -> Self {
+        Self { target, children }
+    }
+}
+
+pub struct PortalState<S> {
+    marker: NodeId,
+    target: NodeId,
+    children: S,
+}
+
+impl<V: View> View for Portal<V> {
+    type State = PortalState<V::State>;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        let marker = ctx.create_marker();
+        let children = self.children.build(ctx);
+
+        PortalState {
+            marker,
+            target: self.target,
+            children,
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        if state.target != self.target {
+            let mounted = ctx.document().parent(state.marker).is_some();
+
+            if mounted {
+                state.children.unmount(ctx.document_mut());
+                state.children.mount(self.target, None, ctx.document_mut());
+            }
+
+            state.target = self.target;
+        }
+
+        self.children.rebuild(&mut state.children, ctx);
+    }
+}
+
+impl<S: Mountable> Mountable for PortalState<S> {
+    fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
+        match marker {
+            Some(marker) => document.insert_before(marker, self.marker),
+            None => document.append_child(parent, self.marker),
+        }
+
+        self.children.mount(self.target, None, document);
+    }
+
+    fn unmount(&mut self, document: &mut Document) {
+        self.children.unmount(document);
+        document.detach(self.marker);
+    }
+
+    fn first_node(&self) -> Option<NodeId> {
+        Some(self.marker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ginyu_force::pose;
+
+    use super::*;
+    use crate::view::TextView;
+
+    #[test]
+    fn portal_mounts_under_target() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let parent = doc.create_element(pose!("div"));
+        let overlay = doc.create_element(pose!("div"));
+        doc.append_child(root, parent);
+        doc.append_child(root, overlay);
+
+        let view = Portal::new(overlay, TextView::new("Tooltip"));
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(parent, None, &mut doc);
+
+        let inside: Vec<_> = doc.children(parent).collect();
+        assert_eq!(inside.len(), 1);
+        assert!(doc.get(inside[0]).expect("failed").is_marker());
+
+        let portaled: Vec<_> = doc.children(overlay).collect();
+        assert_eq!(portaled.len(), 1);
+        assert_eq!(
+            doc.get(portaled[0]).expect("failed").as_text(),
+            Some("Tooltip")
+        );
+
+        state.unmount(&mut doc);
+
+        assert_eq!(doc.children(parent).count(), 0);
+        assert_eq!(doc.children(overlay).count(), 0);
+    }
+}
@@ -37,3 +37,25 @@ where
 {
     show(move || !when(), children, ViewFn::default())
 }
+
+/// Renders `children` with the value whenever `value` returns `Some`, and
+/// nothing when it's `None`.
+///
+/// # Example
+/// ```ignore
+/// show_some(move || selected.get(), |item| AnyView::new(text(item.name)))
+/// ```
+pub fn show_some<T, W, C>(value: W, children: C) -> impl Fn() -> Either<AnyView, AnyView>
+where
+    W: Fn() -> Option<T> + 'static,
+    C: Fn(T) -> AnyView + 'static,
+{
+    let fallback = ViewFn::default();
+
+    move || {
+        value().map_or_else(
+            || Either::Right(fallback.call()),
+            |value| Either::Left(children(value)),
+        )
+    }
+}
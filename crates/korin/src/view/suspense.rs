@@ -0,0 +1,190 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll, Waker},
+};
+
+use crate::view::{BuildContext, Either, EitherState, RebuildContext, View};
+
+type PendingFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+enum Loading<T> {
+    Pending(PendingFuture<T>),
+    Ready(T),
+}
+
+/// A value that's still being loaded by a future.
+///
+/// There's no executor behind it: the future is polled whenever the value is
+/// asked for, which for a [`Suspense`] means on every build and rebuild. A
+/// future that relies on a particular runtime's reactor (timers, sockets)
+/// needs to be spawned there, with a channel or the like handing the result
+/// over. Creating the resource on every render would start its future over
+/// each time, so it belongs somewhere that outlives a frame.
+pub struct Resource<T>(Arc<Mutex<Loading<T>>>);
+
+impl<T> Clone for Resource<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for Resource<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Resource")
+            .field("ready", &self.is_ready())
+            .finish()
+    }
+}
+
+impl<T> Resource<T> {
+    pub fn new(future: impl Future<Output = T> + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Loading::Pending(Box::pin(future)))))
+    }
+
+    /// A resource that's already loaded.
+    pub fn ready(value: T) -> Self {
+        Self(Arc::new(Mutex::new(Loading::Ready(value))))
+    }
+
+    #[must_use]
+    pub fn is_ready(&self) -> bool {
+        matches!(
+            *self.0.lock().unwrap_or_else(PoisonError::into_inner),
+            Loading::Ready(_)
+        )
+    }
+
+    /// Poll the future once more, returning the value if it's done.
+    #[must_use]
+    pub fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        let mut loading = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Loading::Pending(future) = &mut *loading {
+            let mut cx = Context::from_waker(Waker::noop());
+
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(value) => *loading = Loading::Ready(value),
+                Poll::Pending => return None,
+            }
+        }
+
+        match &*loading {
+            Loading::Ready(value) => Some(value.clone()),
+            Loading::Pending(_) => None,
+        }
+    }
+}
+
+/// Shows `fallback` until `resource` has loaded, then `children` with its
+/// value.
+///
+/// # Example
+/// ```ignore
+/// Suspense::new(
+///     user.clone(),
+///     || text("Loading..."),
+///     |user| text(user.name),
+/// )
+/// ```
+pub struct Suspense<T, F, C> {
+    resource: Resource<T>,
+    fallback: F,
+    children: C,
+}
+
+impl<T, F, C, W, V> Suspense<T, F, C>
+where
+    T: Clone,
+    F: FnOnce() -> W,
+    C: FnOnce(T) -> V,
+    W: View,
+    V: View,
+{
+    pub const fn new(resource: Resource<T>, fallback: F, children: C) -> Self {
+        Self {
+            resource,
+            fallback,
+            children,
+        }
+    }
+
+    fn into_either(self) -> Either<V, W> {
+        match self.resource.get() {
+            Some(value) => Either::Left((self.children)(value)),
+            None => Either::Right((self.fallback)()),
+        }
+    }
+}
+
+impl<T, F, C, W, V> View for Suspense<T, F, C>
+where
+    T: Clone,
+    F: FnOnce() -> W,
+    C: FnOnce(T) -> V,
+    W: View,
+    V: View,
+{
+    type State = EitherState<V::State, W::State>;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        self.into_either().build(ctx)
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        self.into_either().rebuild(state, ctx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use super::*;
+    use crate::{
+        document::Document,
+        view::{Mountable, TextView},
+    };
+
+    fn texts(doc: &Document) -> Vec<String> {
+        doc.children(doc.root())
+            .filter_map(|child| doc.get(child)?.as_text().map(str::to_owned))
+            .collect()
+    }
+
+    fn suspense(resource: &Resource<&'static str>) -> impl View + use<> {
+        Suspense::new(resource.clone(), || TextView::new("Loading"), TextView::new)
+    }
+
+    #[test]
+    fn resolved_resource_shows_children() {
+        let mut doc = Document::new();
+        let root = doc.root();
+        let resource = Resource::new(future::ready("Loaded"));
+
+        let mut state = suspense(&resource).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        assert!(resource.is_ready());
+        assert_eq!(texts(&doc), ["Loaded"]);
+    }
+
+    #[test]
+    fn pending_resource_stays_on_fallback() {
+        let mut doc = Document::new();
+        let root = doc.root();
+        let resource = Resource::new(future::pending());
+
+        let mut state = suspense(&resource).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+        assert_eq!(texts(&doc), ["Loading"]);
+
+        suspense(&resource).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert!(!resource.is_ready());
+        assert_eq!(texts(&doc), ["Loading"]);
+    }
+}
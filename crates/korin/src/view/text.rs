@@ -50,6 +50,33 @@ impl View for TextView {
     }
 }
 
+/// Pre-rendered text that's laid out exactly as given, never wrapped to fit
+/// its parent.
+pub struct RawTextView {
+    content: String,
+}
+
+impl RawTextView {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+}
+
+impl View for RawTextView {
+    type State = TextViewState;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        let node = ctx.create_raw_text(self.content);
+        TextViewState { node }
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        ctx.set_text(state.node, self.content);
+    }
+}
+
 impl Mountable for TextViewState {
     fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
         match marker {
@@ -66,3 +93,28 @@ impl Mountable for TextViewState {
         Some(self.node)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_text_round_trips() {
+        let mut doc = Document::new();
+        let root = doc.root();
+        let content = "  \x1b[1mbold\x1b[0m\n    indented  ";
+
+        let mut state = RawTextView::new(content).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        let node = doc.get(state.node).expect("failed");
+        assert!(node.is_raw_text());
+        assert_eq!(node.as_text(), Some(content));
+
+        RawTextView::new(content).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+
+        let node = doc.get(state.node).expect("failed");
+        assert!(node.is_raw_text());
+        assert_eq!(node.as_text(), Some(content));
+    }
+}
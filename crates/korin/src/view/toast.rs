@@ -0,0 +1,371 @@
+use std::{
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+use ginyu_force::{Pose, pose};
+use indextree::NodeId;
+
+use crate::{
+    HandlerId,
+    document::Document,
+    view::{
+        BuildContext, ElementView, ElementViewState, Mountable, RebuildContext, TextView,
+        TextViewState, View, div, text,
+    },
+};
+
+/// How urgent a toast is, added as a class next to `toast` for styling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ToastLevel {
+    #[default]
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    #[must_use]
+    pub fn class(self) -> Pose {
+        match self {
+            Self::Info => pose!("info"),
+            Self::Success => pose!("success"),
+            Self::Warning => pose!("warning"),
+            Self::Error => pose!("error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    pub id: ToastId,
+    pub message: String,
+    pub level: ToastLevel,
+    pub duration: Duration,
+    /// When a [`Toasts`] view first showed it, on the document's clock
+    shown_at: Option<Duration>,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+/// The toasts waiting to be dismissed, shown by a [`Toasts`] view.
+///
+/// Event handlers push to a clone of the manager the [`Toasts`] view was
+/// given, and the view picks the new toasts up when it's next rebuilt. A
+/// toast's duration starts counting once it's first shown and is measured
+/// with [`Document::now`], so it follows a clock set with
+/// [`Document::set_clock`]. Keep drawing frames while the manager isn't
+/// empty, the same as for [`Document::tick`], or toasts won't go away on
+/// their own.
+#[derive(Debug, Clone, Default)]
+pub struct ToastManager(Arc<Mutex<Queue>>);
+
+impl ToastManager {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn queue(&self) -> MutexGuard<'_, Queue> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Show `message` for `duration`, below any toasts already showing.
+    pub fn push(
+        &self,
+        message: impl Into<String>,
+        level: ToastLevel,
+        duration: Duration,
+    ) -> ToastId {
+        let mut queue = self.queue();
+        let id = ToastId(queue.next_id);
+        queue.next_id += 1;
+
+        queue.toasts.push(Toast {
+            id,
+            message: message.into(),
+            level,
+            duration,
+            shown_at: None,
+        });
+
+        id
+    }
+
+    /// Take `id` down before its time is up.
+    pub fn dismiss(&self, id: ToastId) {
+        self.queue().toasts.retain(|toast| toast.id != id);
+    }
+
+    /// Start the clock on toasts that haven't been shown yet, and drop the
+    /// ones whose time is up at `now`.
+    pub fn dismiss_expired(&self, now: Duration) {
+        self.queue().toasts.retain_mut(|toast| {
+            let shown_at = *toast.shown_at.get_or_insert(now);
+            now.saturating_sub(shown_at) < toast.duration
+        });
+    }
+
+    /// The toasts still showing, oldest first.
+    #[must_use]
+    pub fn toasts(&self) -> Vec<Toast> {
+        self.queue().toasts.clone()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.queue().toasts.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.queue().toasts.is_empty()
+    }
+}
+
+/// Which corner of the containing block the toasts stack up in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    #[default]
+    BottomRight,
+}
+
+impl Corner {
+    const fn style(self) -> &'static str {
+        match self {
+            Self::TopLeft => "position: absolute; top: 0; left: 0",
+            Self::TopRight => "position: absolute; top: 0; right: 0",
+            Self::BottomLeft => "position: absolute; bottom: 0; left: 0",
+            Self::BottomRight => "position: absolute; bottom: 0; right: 0",
+        }
+    }
+}
+
+/// Renders the toasts in `manager`, stacked in a corner.
+///
+/// Every build and rebuild drops the toasts whose time is up. Clicking a
+/// toast dismisses it, which shows on the next rebuild. The container is a
+/// `div.toasts` and each toast a `div.toast` with its level as a class
+/// (`info`, `success`, `warning` or `error`).
+///
+/// # Example
+/// ```ignore
+/// fragment![
+///     app(toasts.clone()),
+///     Toasts::new(toasts.clone()).corner(Corner::TopRight),
+/// ]
+/// ```
+pub struct Toasts {
+    manager: ToastManager,
+    corner: Corner,
+}
+
+impl Toasts {
+    #[must_use]
+    pub fn new(manager: ToastManager) -> Self {
+        Self {
+            manager,
+            corner: Corner::default(),
+        }
+    }
+
+    #[must_use]
+    pub const fn corner(mut self, corner: Corner) -> Self {
+        self.corner = corner;
+        self
+    }
+}
+
+struct ShownToast {
+    id: ToastId,
+    state: ElementViewState<TextViewState>,
+    handler: HandlerId,
+}
+
+pub struct ToastsState {
+    container: ElementViewState<()>,
+    shown: Vec<ShownToast>,
+}
+
+fn container_view(corner: Corner) -> ElementView<()> {
+    div(())
+        .class(pose!("toasts"))
+        .attribute(pose!("style"), corner.style())
+}
+
+fn toast_view(toast: &Toast) -> ElementView<TextView> {
+    div(text(toast.message.clone()))
+        .class(pose!("toast"))
+        .class(toast.level.class())
+}
+
+fn show(
+    manager: &ToastManager,
+    toast: &Toast,
+    container: NodeId,
+    doc: &mut Document,
+) -> ShownToast {
+    let mut state = toast_view(toast).build(&mut BuildContext::new(doc));
+    state.mount(container, None, doc);
+
+    let handler = doc.add_event_handler({
+        let manager = manager.clone();
+        let id = toast.id;
+        move |_| manager.dismiss(id)
+    });
+    doc.register_event_handler(state.node(), pose!("click"), handler);
+
+    ShownToast {
+        id: toast.id,
+        state,
+        handler,
+    }
+}
+
+impl View for Toasts {
+    type State = ToastsState;
+
+    fn build(self, ctx: &mut BuildContext) -> Self::State {
+        let container = container_view(self.corner).build(ctx);
+
+        let doc = ctx.document_mut();
+        self.manager.dismiss_expired(doc.now());
+
+        let shown = self
+            .manager
+            .toasts()
+            .iter()
+            .map(|toast| show(&self.manager, toast, container.node(), doc))
+            .collect();
+
+        ToastsState { container, shown }
+    }
+
+    fn rebuild(self, state: &mut Self::State, ctx: &mut RebuildContext) {
+        container_view(self.corner).rebuild(&mut state.container, ctx);
+
+        let doc = ctx.document_mut();
+        self.manager.dismiss_expired(doc.now());
+        let toasts = self.manager.toasts();
+
+        state.shown.retain_mut(|shown| {
+            if toasts.iter().any(|toast| toast.id == shown.id) {
+                return true;
+            }
+
+            shown.state.unmount(doc);
+            doc.remove(shown.state.node());
+            doc.remove_event_handler(shown.handler);
+            false
+        });
+
+        // New toasts only ever go on the end, so what's left is in order
+        for toast in &toasts[state.shown.len()..] {
+            let shown = show(&self.manager, toast, state.container.node(), doc);
+            state.shown.push(shown);
+        }
+    }
+}
+
+impl Mountable for ToastsState {
+    fn mount(&mut self, parent: NodeId, marker: Option<NodeId>, document: &mut Document) {
+        self.container.mount(parent, marker, document);
+    }
+
+    fn unmount(&mut self, document: &mut Document) {
+        self.container.unmount(document);
+    }
+
+    fn first_node(&self) -> Option<NodeId> {
+        self.container.first_node()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use dom_events::{
+        ClientPoint, Modifiers, MouseButton, MouseButtons, OffsetPoint, PagePoint, ScreenPoint,
+    };
+
+    use super::*;
+    use crate::{EventType, MouseEvent};
+
+    fn messages(doc: &Document, state: &ToastsState) -> Vec<String> {
+        doc.children(state.container.node())
+            .filter_map(|toast| doc.children(toast).next())
+            .filter_map(|text| doc.get(text)?.as_text().map(str::to_owned))
+            .collect()
+    }
+
+    #[test]
+    fn expired_toasts_are_removed() {
+        let now_ms = Rc::new(Cell::new(0));
+
+        let mut doc = Document::new();
+        let root = doc.root();
+        doc.set_clock(Some(Box::new({
+            let now_ms = now_ms.clone();
+            move || Duration::from_millis(now_ms.get())
+        })));
+
+        let manager = ToastManager::new();
+        manager.push("Saved", ToastLevel::Success, Duration::from_secs(1));
+        manager.push("Offline", ToastLevel::Warning, Duration::from_secs(3));
+
+        let mut state = Toasts::new(manager.clone()).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+        assert_eq!(messages(&doc, &state), ["Saved", "Offline"]);
+
+        now_ms.set(1500);
+        Toasts::new(manager.clone()).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+
+        assert_eq!(messages(&doc, &state), ["Offline"]);
+        assert_eq!(manager.len(), 1);
+    }
+
+    #[test]
+    fn clicking_a_toast_dismisses_it() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let manager = ToastManager::new();
+        manager.push("Copied", ToastLevel::Info, Duration::from_secs(30));
+
+        let mut state = Toasts::new(manager.clone()).build(&mut BuildContext::new(&mut doc));
+        state.mount(root, None, &mut doc);
+
+        let toast = state.shown[0].state.node();
+        doc.dispatch(
+            toast,
+            EventType::Click(MouseEvent {
+                related_target: None,
+                screen: ScreenPoint::new(0, 0),
+                client: ClientPoint::new(0, 0),
+                page: PagePoint::new(0, 0),
+                offset: OffsetPoint::new(0, 0),
+                button: Some(MouseButton::Primary),
+                buttons: MouseButtons::empty(),
+                modifiers: Modifiers::empty(),
+                detail: 1,
+            }),
+        );
+        assert!(manager.is_empty());
+
+        Toasts::new(manager).rebuild(&mut state, &mut RebuildContext::new(&mut doc));
+        assert!(messages(&doc, &state).is_empty());
+        assert!(doc.get(toast).is_none());
+    }
+}
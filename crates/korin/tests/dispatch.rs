@@ -0,0 +1,90 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use capsule_corp::QuerySelector;
+use dom_events::CustomEvent;
+use ginyu_force::pose;
+use korin::{
+    Document, fragment,
+    view::{BuildContext, Mountable, View, div, span, text},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TabChanged {
+    index: usize,
+}
+
+fn mount(doc: &mut Document, view: impl View) {
+    let root = doc.root();
+    let mut ctx = BuildContext::new(doc);
+    let mut state = view.build(&mut ctx);
+    state.mount(root, None, doc);
+}
+
+#[test]
+fn custom_event_reaches_target_and_ancestors() {
+    let mut doc = Document::new();
+    mount(
+        &mut doc,
+        div(fragment![
+            div(span(text("a"))).class(pose!("tabs")),
+            div(span(text("b"))).class(pose!("other")),
+        ])
+        .class(pose!("app")),
+    );
+
+    let tabs = doc.query_selector(".tabs").expect("failed");
+    let app = doc.query_selector(".app").expect("failed");
+    let other = doc.query_selector(".other").expect("failed");
+
+    let received = Rc::new(Cell::new(None));
+    let bubbled = Rc::new(Cell::new(false));
+    let sibling = Rc::new(Cell::new(false));
+
+    let handler = doc.add_event_handler({
+        let received = received.clone();
+        move |event| {
+            let detail = event
+                .as_custom()
+                .and_then(CustomEvent::detail_ref::<TabChanged>)
+                .copied();
+            received.set(detail);
+        }
+    });
+    doc.register_event_handler(tabs, pose!("tabchanged"), handler);
+
+    let handler = doc.add_event_handler({
+        let bubbled = bubbled.clone();
+        move |_| bubbled.set(true)
+    });
+    doc.register_event_handler(app, pose!("tabchanged"), handler);
+
+    let handler = doc.add_event_handler({
+        let sibling = sibling.clone();
+        move |_| sibling.set(true)
+    });
+    doc.register_event_handler(other, pose!("tabchanged"), handler);
+
+    doc.dispatch_custom(
+        tabs,
+        CustomEvent::with_detail("tabchanged", TabChanged { index: 2 }),
+    );
+
+    assert_eq!(received.get(), Some(TabChanged { index: 2 }));
+    assert!(bubbled.get());
+    assert!(!sibling.get());
+}
+
+#[test]
+fn events_are_stamped_with_the_document_clock() {
+    let mut doc = Document::new();
+    mount(&mut doc, div(text("a")).class(pose!("target")));
+    let target = doc.query_selector(".target").expect("failed");
+
+    doc.set_clock(Some(Box::new(|| Duration::from_millis(42))));
+    let event = doc.dispatch_custom(target, CustomEvent::new("ping"));
+    assert_eq!(event.time_stamp, Duration::from_millis(42));
+
+    doc.set_clock(None);
+    let event = doc.dispatch_custom(target, CustomEvent::new("ping"));
+    assert!(event.time_stamp <= doc.now());
+}
@@ -355,6 +355,139 @@ mod focus_navigation {
         );
     }
 
+    #[test]
+    fn no_wrap_stops_at_ends() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        doc.set_focus_wrap(false);
+
+        doc.focus_next();
+        doc.focus_next();
+        assert_eq!(doc.focus_next(), None);
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("b".into())
+        );
+
+        doc.focus_prev();
+        assert_eq!(doc.focus_prev(), None);
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("a".into())
+        );
+
+        // Turning it back on wraps again
+        doc.set_focus_wrap(true);
+        doc.focus_prev();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("b".into())
+        );
+    }
+
+    #[test]
+    fn disabled_middle_is_skipped() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+            button(text("C")).attribute(pose!("name"), "c"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let b = doc.query_selector("button[name='b']").expect("failed");
+        if let Some(el) = doc.get_mut(b).and_then(|n| n.as_element_mut()) {
+            el.add_state(ElementState::DISABLED);
+        }
+
+        doc.focus_next();
+        doc.focus_next();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("c".into())
+        );
+
+        doc.focus_prev();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("a".into())
+        );
+
+        assert!(!doc.focus(b));
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("a".into())
+        );
+    }
+
+    #[test]
+    fn everything_disabled_returns_none() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![button(text("A")), button(text("B"))];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        for id in doc.query_selector_all("button") {
+            if let Some(el) = doc.get_mut(id).and_then(|n| n.as_element_mut()) {
+                el.add_state(ElementState::DISABLED);
+            }
+        }
+
+        assert_eq!(doc.focus_next(), None);
+        assert_eq!(doc.focus_prev(), None);
+        assert!(doc.focused().is_none());
+    }
+
+    #[test]
+    fn focus_first_and_last_jump_to_ends() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+            button(text("C")).attribute(pose!("name"), "c"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        doc.focus_next();
+        doc.focus_next();
+
+        doc.focus_last();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("c".into())
+        );
+
+        doc.focus_first();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("a".into())
+        );
+    }
+
     #[test]
     fn no_tab_order_returns_none() {
         let mut doc = Document::new();
@@ -368,6 +501,8 @@ mod focus_navigation {
 
         assert_eq!(doc.focus_next(), None);
         assert_eq!(doc.focus_prev(), None);
+        assert_eq!(doc.focus_first(), None);
+        assert_eq!(doc.focus_last(), None);
         assert!(doc.focused().is_none());
     }
 }
@@ -510,6 +645,67 @@ mod dynamic_focus {
         reset_frame();
     }
 
+    #[test]
+    fn removing_focused_moves_to_next() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+            button(text("C")).attribute(pose!("name"), "c"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        doc.focus_next();
+        doc.focus_next();
+        let b = doc.focused().expect("failed");
+
+        doc.remove(b);
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("c".into())
+        );
+
+        // Last item falls back to the previous one
+        let c = doc.focused().expect("failed");
+        doc.remove(c);
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("a".into())
+        );
+
+        let a = doc.focused().expect("failed");
+        doc.remove(a);
+        assert!(doc.focused().is_none());
+    }
+
+    #[test]
+    fn removing_unfocused_keeps_focus() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            div(button(text("B")).attribute(pose!("name"), "b")),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        doc.focus_next();
+        let a = doc.focused().expect("failed");
+
+        let wrapper = doc.query_selector("div").expect("failed");
+        doc.remove(wrapper);
+
+        assert_eq!(doc.focused(), Some(a));
+    }
+
     #[test]
     fn focus_preserved_after_rebuild() {
         reset_frame();
@@ -575,3 +771,272 @@ mod focus_pseudo_class {
         assert!(doc.query_selector(":focus").is_none());
     }
 }
+
+mod directional_focus {
+    use std::sync::Arc;
+
+    use super::*;
+    use capsule_corp::{
+        CapsuleDocument, ComputedStyle, CustomPropertiesMap, Display, Size, compute_layout,
+        compute_styles,
+    };
+    use korin::FocusDirection;
+
+    fn cell(name: &'static str) -> impl View {
+        button(text(name))
+            .attribute(pose!("name"), name)
+            .attribute(pose!("style"), "width: 5; height: 1")
+    }
+
+    /// a b
+    /// c d
+    fn grid() -> Document {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        doc.set_style(
+            root,
+            Arc::new(ComputedStyle {
+                display: Display::Block,
+                ..Default::default()
+            }),
+            CustomPropertiesMap::default(),
+        );
+
+        let row = "display: flex; height: 1";
+        let view = fragment![
+            div(fragment![cell("a"), cell("b")]).attribute(pose!("style"), row),
+            div(fragment![cell("c"), cell("d")]).attribute(pose!("style"), row),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        compute_styles(&mut doc);
+        compute_layout(&mut doc, root, Size::new(20, 10));
+
+        doc
+    }
+
+    fn focused_name(doc: &Document) -> Option<String> {
+        get_name(doc, doc.focused()?)
+    }
+
+    #[test]
+    fn arrows_move_through_grid() {
+        let mut doc = grid();
+
+        doc.focus_direction(FocusDirection::Right);
+        assert_eq!(focused_name(&doc), Some("a".into()));
+
+        doc.focus_direction(FocusDirection::Right);
+        assert_eq!(focused_name(&doc), Some("b".into()));
+
+        // c is as close as d, but d shares b's columns
+        doc.focus_direction(FocusDirection::Down);
+        assert_eq!(focused_name(&doc), Some("d".into()));
+
+        doc.focus_direction(FocusDirection::Left);
+        assert_eq!(focused_name(&doc), Some("c".into()));
+
+        doc.focus_direction(FocusDirection::Up);
+        assert_eq!(focused_name(&doc), Some("a".into()));
+    }
+
+    #[test]
+    fn nothing_in_direction_keeps_focus() {
+        let mut doc = grid();
+
+        doc.focus_first();
+        assert_eq!(doc.focus_direction(FocusDirection::Up), None);
+        assert_eq!(doc.focus_direction(FocusDirection::Left), None);
+        assert_eq!(focused_name(&doc), Some("a".into()));
+    }
+}
+
+mod focus_history {
+    use super::*;
+
+    fn mount_buttons(doc: &mut Document) {
+        let root = doc.root();
+        let view = fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+            button(text("C")).attribute(pose!("name"), "c"),
+        ];
+
+        let mut ctx = BuildContext::new(doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, doc);
+    }
+
+    #[test]
+    fn pop_restores_saved_focus() {
+        let mut doc = Document::new();
+        mount_buttons(&mut doc);
+
+        doc.focus_next();
+        doc.focus_next();
+        doc.push_focus();
+
+        doc.focus_last();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("c".into())
+        );
+
+        doc.pop_focus();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("b".into())
+        );
+
+        // Nothing left to restore
+        assert_eq!(doc.pop_focus(), None);
+    }
+
+    #[test]
+    fn pop_falls_back_when_saved_node_removed() {
+        let mut doc = Document::new();
+        mount_buttons(&mut doc);
+
+        doc.focus_next();
+        doc.focus_next();
+        let b = doc.focused().expect("failed");
+        doc.push_focus();
+
+        doc.focus_first();
+        doc.remove(b);
+
+        // c has moved into b's old slot
+        doc.pop_focus();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("c".into())
+        );
+    }
+
+    #[test]
+    fn pop_clamps_to_end_of_order() {
+        let mut doc = Document::new();
+        mount_buttons(&mut doc);
+
+        doc.focus_last();
+        let c = doc.focused().expect("failed");
+        doc.push_focus();
+
+        doc.focus_first();
+        doc.remove(c);
+
+        doc.pop_focus();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("b".into())
+        );
+    }
+}
+
+mod focus_scope {
+    use super::*;
+
+    #[test]
+    fn traversal_stays_inside_scope() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            div(fragment![
+                button(text("S1")).attribute(pose!("name"), "s1"),
+                button(text("S2")).attribute(pose!("name"), "s2"),
+            ])
+            .attribute(pose!("name"), "sidebar"),
+            div(fragment![
+                button(text("M1")).attribute(pose!("name"), "m1"),
+                button(text("M2")).attribute(pose!("name"), "m2"),
+            ])
+            .attribute(pose!("name"), "main"),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let main = doc.query_selector("div[name='main']").expect("failed");
+        doc.set_focus_scope(Some(main));
+
+        let names: Vec<_> = (0..3)
+            .filter_map(|_| {
+                let next = doc.focus_next()?;
+                get_name(&doc, next)
+            })
+            .collect();
+        assert_eq!(names, ["m1", "m2", "m1"]);
+
+        let sidebar = doc.query_selector("div[name='sidebar']").expect("failed");
+        doc.set_focus_scope(Some(sidebar));
+
+        doc.focus_first();
+        assert_eq!(
+            get_name(&doc, doc.focused().expect("failed")),
+            Some("s1".into())
+        );
+
+        let m1 = doc.query_selector("button[name='m1']").expect("failed");
+        let s1 = doc.query_selector("button[name='s1']").expect("failed");
+        assert!(doc.in_tab_order(s1));
+        assert!(!doc.in_tab_order(m1));
+
+        doc.set_focus_scope(None);
+        assert_eq!(doc.tab_order().len(), 4);
+        assert!(doc.in_tab_order(m1));
+        assert!(!doc.in_tab_order(main));
+    }
+}
+
+mod focus_bubbling {
+    use std::{cell::RefCell, rc::Rc};
+
+    use ginyu_force::Pose;
+
+    use super::*;
+
+    #[test]
+    fn container_sees_focusout_then_focusin() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = div(fragment![
+            button(text("A")).attribute(pose!("name"), "a"),
+            button(text("B")).attribute(pose!("name"), "b"),
+        ])
+        .attribute(pose!("name"), "container");
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let container = doc.query_selector("div[name='container']").expect("failed");
+        let a = doc.query_selector("button[name='a']").expect("failed");
+        let b = doc.query_selector("button[name='b']").expect("failed");
+
+        let seen: Rc<RefCell<Vec<Pose>>> = Rc::default();
+        for name in [
+            pose!("focus"),
+            pose!("blur"),
+            pose!("focusin"),
+            pose!("focusout"),
+        ] {
+            let seen = seen.clone();
+            let handler = doc.add_event_handler(move |event| seen.borrow_mut().push(event.name()));
+            doc.register_event_handler(container, name, handler);
+        }
+
+        doc.focus(a);
+        assert_eq!(*seen.borrow(), [pose!("focusin")]);
+
+        seen.borrow_mut().clear();
+        doc.focus(b);
+        assert_eq!(*seen.borrow(), [pose!("focusout"), pose!("focusin")]);
+    }
+}
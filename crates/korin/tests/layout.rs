@@ -0,0 +1,459 @@
+use std::sync::Arc;
+
+use capsule_corp::{
+    CapsuleDocument, ComputedStyle, CustomPropertiesMap, Display, QuerySelector, Size,
+    compute_layout, compute_styles, debug_tree,
+};
+use ginyu_force::pose;
+use korin::{
+    Document, fragment,
+    view::{BuildContext, Mountable, View, div},
+};
+
+fn layout(view: impl View, viewport: Size) -> Document {
+    let mut doc = Document::new();
+    let root = doc.root();
+
+    doc.set_style(
+        root,
+        Arc::new(ComputedStyle {
+            display: Display::Block,
+            ..Default::default()
+        }),
+        CustomPropertiesMap::default(),
+    );
+
+    let mut ctx = BuildContext::new(&mut doc);
+    let mut state = view.build(&mut ctx);
+    state.mount(root, None, &mut doc);
+
+    compute_styles(&mut doc);
+    compute_layout(&mut doc, root, viewport);
+
+    doc
+}
+
+fn content_size(doc: &Document, selector: &str) -> Size {
+    let id = doc.query_selector(selector).expect("failed");
+    doc.get(id)
+        .expect("failed")
+        .layout
+        .resolved_box
+        .content_size
+}
+
+mod aspect_ratio {
+    use super::*;
+
+    fn boxed(name: &'static str, style: &'static str) -> impl View {
+        div(()).class(pose!(name)).attribute(pose!("style"), style)
+    }
+
+    #[test]
+    fn derives_the_missing_dimension() {
+        let doc = layout(
+            fragment![
+                boxed("wide", "width: 20; aspect-ratio: 2"),
+                boxed("tall", "height: 4; aspect-ratio: 3 / 1"),
+                boxed("stretched", "aspect-ratio: 4"),
+                boxed("both", "width: 6; height: 6; aspect-ratio: 2"),
+            ],
+            Size::new(40, 100),
+        );
+
+        assert_eq!(content_size(&doc, ".wide"), Size::new(20, 10));
+        assert_eq!(content_size(&doc, ".tall"), Size::new(12, 4));
+        // Block boxes stretch to the container, the height follows
+        assert_eq!(content_size(&doc, ".stretched"), Size::new(40, 10));
+        // Explicit sizes win over the ratio
+        assert_eq!(content_size(&doc, ".both"), Size::new(6, 6));
+    }
+
+    #[test]
+    fn respects_min_and_max() {
+        let doc = layout(
+            boxed("capped", "width: 20; max-height: 3; aspect-ratio: 2"),
+            Size::new(40, 100),
+        );
+
+        assert_eq!(content_size(&doc, ".capped"), Size::new(20, 3));
+    }
+}
+
+mod gap {
+    use capsule_corp::Point;
+
+    use super::*;
+
+    fn location(doc: &Document, selector: &str) -> Point {
+        let id = doc.query_selector(selector).expect("failed");
+        doc.get(id).expect("failed").layout.location
+    }
+
+    fn container(direction: &'static str) -> impl View {
+        let style = match direction {
+            "row" => "display: flex; width: 40; height: 20; gap: 10% 25%",
+            _ => "display: flex; flex-direction: column; width: 40; height: 20; gap: 10% 25%",
+        };
+
+        div(fragment![
+            div(())
+                .class(pose!("a"))
+                .attribute(pose!("style"), "width: 5; height: 2"),
+            div(())
+                .class(pose!("b"))
+                .attribute(pose!("style"), "width: 5; height: 2"),
+        ])
+        .attribute(pose!("style"), style)
+    }
+
+    #[test]
+    fn percentages_resolve_against_the_container() {
+        // 25% of the 40 cell width between columns
+        let doc = layout(container("row"), Size::new(80, 100));
+        assert_eq!(location(&doc, ".b"), Point::new(15, 0));
+
+        // 10% of the 20 cell height between rows
+        let doc = layout(container("column"), Size::new(80, 100));
+        assert_eq!(location(&doc, ".b"), Point::new(0, 4));
+    }
+}
+
+mod calc {
+    use super::*;
+
+    #[test]
+    fn resolves_against_the_container_at_layout_time() {
+        let doc = layout(
+            div(fragment![
+                div(())
+                    .class(pose!("body"))
+                    .attribute(pose!("style"), "height: calc(100% - 4)"),
+                div(())
+                    .class(pose!("footer"))
+                    .attribute(pose!("style"), "height: 4"),
+                div(())
+                    .class(pose!("negative"))
+                    .attribute(pose!("style"), "width: calc(10 - 100%)"),
+            ])
+            .attribute(pose!("style"), "width: 30; height: 20"),
+            Size::new(80, 100),
+        );
+
+        // Leaves exactly enough room for the footer
+        assert_eq!(content_size(&doc, ".body"), Size::new(30, 16));
+        assert_eq!(content_size(&doc, ".footer"), Size::new(30, 4));
+        // Results below zero clamp to an empty box
+        assert_eq!(content_size(&doc, ".negative").width, 0);
+    }
+}
+
+#[test]
+fn debug_tree_dumps_boxes() {
+    let doc = layout(
+        div(div(()).class(pose!("inner")).attribute(
+            pose!("style"),
+            "width: 10; height: 2; padding: 1 2; border: solid",
+        ))
+        .class(pose!("outer"))
+        .attribute(pose!("style"), "width: 30; height: 5"),
+        Size::new(80, 24),
+    );
+
+    let outer = doc.query_selector(".outer").expect("failed");
+    let dump = debug_tree(&doc, outer);
+
+    assert_eq!(
+        dump,
+        "div.outer @ (0, 0) 30x5 padding 0 0 0 0 border 0 0 0 0 content 30x5\n  \
+         div.inner @ (0, 0) 16x6 padding 1 2 1 2 border 1 1 1 1 content 10x2\n"
+    );
+}
+
+mod position {
+    use capsule_corp::Point;
+
+    use super::*;
+
+    fn styled(name: &'static str, style: &'static str) -> korin::view::ElementView<()> {
+        div(()).class(pose!(name)).attribute(pose!("style"), style)
+    }
+
+    fn location(doc: &Document, selector: &str) -> Point {
+        let id = doc.query_selector(selector).expect("failed");
+        doc.get(id).expect("failed").layout.location
+    }
+
+    #[test]
+    fn absolute_is_placed_by_insets() {
+        let doc = layout(
+            div(fragment![
+                styled(
+                    "badge",
+                    "position: absolute; top: 2; right: 0; width: 5; height: 1"
+                ),
+                styled("body", "height: 3"),
+            ])
+            .attribute(pose!("style"), "position: relative; width: 30; height: 10"),
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".badge"), Point::new(25, 2));
+        // Out of flow, so it doesn't push its siblings around
+        assert_eq!(location(&doc, ".body"), Point::new(0, 0));
+    }
+
+    #[test]
+    fn absolute_uses_nearest_positioned_ancestor() {
+        let doc = layout(
+            div(div(styled(
+                "badge",
+                "position: absolute; bottom: 0; left: 10; width: 5; height: 1",
+            ))
+            .attribute(pose!("style"), "margin-left: 4; height: 2"))
+            .attribute(
+                pose!("style"),
+                "position: relative; width: 30; height: 10; padding: 1",
+            ),
+            Size::new(80, 24),
+        );
+
+        // Bottom of the 12 row padding box, relative to the static wrapper
+        // which sits 4 cells into the content box
+        assert_eq!(location(&doc, ".badge"), Point::new(5, 10));
+    }
+
+    #[test]
+    fn root_is_the_fallback_containing_block() {
+        let doc = layout(
+            styled(
+                "footer",
+                "position: absolute; bottom: 0; left: 0; right: 0; height: 1",
+            ),
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".footer"), Point::new(0, 23));
+        assert_eq!(content_size(&doc, ".footer"), Size::new(80, 1));
+    }
+
+    #[test]
+    fn relative_is_offset_from_flow() {
+        let doc = layout(
+            fragment![
+                styled("first", "height: 2"),
+                styled("nudged", "position: relative; top: 1; left: 2; height: 1"),
+            ],
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".nudged"), Point::new(2, 3));
+    }
+}
+
+mod grid {
+    use capsule_corp::Point;
+
+    use super::*;
+
+    fn cell(name: &'static str, style: &'static str) -> korin::view::ElementView<()> {
+        div(()).class(pose!(name)).attribute(pose!("style"), style)
+    }
+
+    fn area(doc: &Document, selector: &str) -> (Point, Size) {
+        let id = doc.query_selector(selector).expect("failed");
+        let layout = doc.get(id).expect("failed").layout;
+        (layout.location, layout.resolved_box.content_size)
+    }
+
+    #[test]
+    fn three_columns_with_a_spanning_item() {
+        let doc = layout(
+            div(fragment![
+                cell("a", "grid-column: 1 / span 2; height: 2"),
+                cell("b", "height: 2"),
+                cell("c", "height: 2"),
+                cell("d", "height: 2"),
+                cell("e", "height: 2"),
+            ])
+            .attribute(
+                pose!("style"),
+                "display: grid; grid-template-columns: 10 1fr 2fr; gap: 1; width: 40",
+            ),
+            Size::new(80, 24),
+        );
+
+        // 28 cells left for 3fr once the fixed track and gaps are taken
+        assert_eq!(area(&doc, ".a"), (Point::new(0, 0), Size::new(20, 2)));
+        assert_eq!(area(&doc, ".b"), (Point::new(21, 0), Size::new(19, 2)));
+        assert_eq!(area(&doc, ".c"), (Point::new(0, 3), Size::new(10, 2)));
+        assert_eq!(area(&doc, ".d"), (Point::new(11, 3), Size::new(9, 2)));
+        assert_eq!(area(&doc, ".e"), (Point::new(21, 3), Size::new(19, 2)));
+    }
+
+    #[test]
+    fn explicit_lines_are_placed_first() {
+        let doc = layout(
+            div(fragment![
+                cell("a", ""),
+                cell("b", ""),
+                cell("pinned", "grid-column: 1; grid-row: 1"),
+            ])
+            .attribute(
+                pose!("style"),
+                "display: grid; grid-template-columns: repeat(2, 5); grid-template-rows: 1 1; width: 10",
+            ),
+            Size::new(80, 24),
+        );
+
+        assert_eq!(area(&doc, ".pinned").0, Point::new(0, 0));
+        assert_eq!(area(&doc, ".a").0, Point::new(5, 0));
+        assert_eq!(area(&doc, ".b").0, Point::new(0, 1));
+    }
+
+    #[test]
+    fn auto_rows_fit_their_tallest_item() {
+        let doc = layout(
+            div(fragment![
+                cell("stretched", ""),
+                cell("tall", "height: 3"),
+                cell("next", ""),
+            ])
+            .attribute(
+                pose!("style"),
+                "display: grid; grid-template-columns: 1fr 1fr; width: 20",
+            ),
+            Size::new(80, 24),
+        );
+
+        // Items without a height stretch to fill their row
+        assert_eq!(area(&doc, ".stretched").1, Size::new(10, 3));
+        assert_eq!(area(&doc, ".next").0, Point::new(0, 3));
+    }
+}
+
+mod intrinsic {
+    use korin::view::text;
+
+    use super::*;
+
+    fn item(name: &'static str, style: &'static str) -> impl View {
+        div(text("hello wonderful world"))
+            .class(pose!(name))
+            .attribute(pose!("style"), style)
+    }
+
+    #[test]
+    fn min_content_stops_shrinking_at_the_widest_word() {
+        let doc = layout(
+            div(fragment![
+                item(
+                    "floor",
+                    "flex-basis: 20; flex-shrink: 1; min-width: min-content"
+                ),
+                item("squashed", "flex-basis: 20; flex-shrink: 1"),
+            ])
+            .attribute(pose!("style"), "display: flex; width: 12"),
+            Size::new(80, 100),
+        );
+
+        // "wonderful" can't be broken, so that's as narrow as it goes
+        assert_eq!(content_size(&doc, ".floor").width, 9);
+        assert_eq!(content_size(&doc, ".squashed").width, 3);
+    }
+
+    #[test]
+    fn max_content_fits_the_unwrapped_text() {
+        let doc = layout(
+            div(item("wide", "flex-basis: max-content"))
+                .attribute(pose!("style"), "display: flex; width: 40"),
+            Size::new(80, 100),
+        );
+
+        assert_eq!(content_size(&doc, ".wide"), Size::new(21, 1));
+    }
+}
+
+mod raw_text {
+    use korin::view::{raw_text, text};
+
+    use super::*;
+
+    #[test]
+    fn is_never_wrapped() {
+        let doc = layout(
+            fragment![
+                div(text("hello world"))
+                    .class(pose!("wrapped"))
+                    .attribute(pose!("style"), "width: 5"),
+                div(raw_text("hello world"))
+                    .class(pose!("raw"))
+                    .attribute(pose!("style"), "width: 5"),
+            ],
+            Size::new(80, 100),
+        );
+
+        assert_eq!(content_size(&doc, ".wrapped").height, 2);
+        assert_eq!(content_size(&doc, ".raw").height, 1);
+    }
+}
+
+mod margin {
+    use capsule_corp::Point;
+
+    use super::*;
+
+    fn styled(name: &'static str, style: &'static str) -> korin::view::ElementView<()> {
+        div(()).class(pose!(name)).attribute(pose!("style"), style)
+    }
+
+    fn location(doc: &Document, selector: &str) -> Point {
+        let id = doc.query_selector(selector).expect("failed");
+        doc.get(id).expect("failed").layout.location
+    }
+
+    #[test]
+    fn negative_top_pulls_a_block_up() {
+        let doc = layout(
+            fragment![
+                styled("first", "height: 3"),
+                styled("pulled", "margin-top: -2; height: 1"),
+                styled("after", "height: 1"),
+            ],
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".pulled"), Point::new(0, 1));
+        assert_eq!(location(&doc, ".after"), Point::new(0, 2));
+    }
+
+    #[test]
+    fn negative_location_is_clamped() {
+        let doc = layout(
+            fragment![
+                styled("first", "margin-top: -2; height: 3"),
+                styled("second", "height: 1"),
+            ],
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".first"), Point::new(0, 0));
+        // The cursor isn't clamped, so the next block still starts where
+        // the pulled one really ended
+        assert_eq!(location(&doc, ".second"), Point::new(0, 1));
+    }
+
+    #[test]
+    fn negative_left_overlaps_flex_items() {
+        let doc = layout(
+            div(fragment![
+                styled("a", "width: 5; height: 1"),
+                styled("b", "margin-left: -2; width: 5; height: 1"),
+            ])
+            .attribute(pose!("style"), "display: flex"),
+            Size::new(80, 24),
+        );
+
+        assert_eq!(location(&doc, ".b"), Point::new(3, 0));
+    }
+}
@@ -0,0 +1,232 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    sync::Arc,
+    time::Duration,
+};
+
+use capsule_corp::{
+    CapsuleDocument, ComputedStyle, CustomPropertiesMap, Display, QuerySelector, Size,
+    compute_layout, compute_styles,
+};
+use dom_events::{
+    ClientPoint, Modifiers, MouseButton, MouseButtons, OffsetPoint, PagePoint, ScreenPoint,
+};
+use ginyu_force::pose;
+use korin::{
+    Document, EventLogEntry, EventType, MouseEvent, Node, fragment,
+    view::{BuildContext, Mountable, View, div, text},
+};
+
+const fn mouse(x: u16, y: u16, button: Option<MouseButton>) -> MouseEvent {
+    MouseEvent {
+        related_target: None,
+        screen: ScreenPoint::new(x, y),
+        client: ClientPoint::new(x, y),
+        page: PagePoint::new(x, y),
+        offset: OffsetPoint::new(0, 0),
+        button,
+        buttons: MouseButtons::empty(),
+        modifiers: Modifiers::empty(),
+        detail: 0,
+    }
+}
+
+const fn mouse_move(x: u16, y: u16) -> EventType {
+    EventType::MouseMove(mouse(x, y, None))
+}
+
+/// Two 5x1 boxes side by side, `.a` then `.b`.
+fn side_by_side() -> Document {
+    let mut doc = Document::new();
+    let root = doc.root();
+
+    doc.set_style(
+        root,
+        Arc::new(ComputedStyle {
+            display: Display::Block,
+            ..Default::default()
+        }),
+        CustomPropertiesMap::default(),
+    );
+
+    let cell = "width: 5; height: 1";
+    let view = div(fragment![
+        div(text("a"))
+            .class(pose!("a"))
+            .attribute(pose!("style"), cell),
+        div(text("b"))
+            .class(pose!("b"))
+            .attribute(pose!("style"), cell),
+    ])
+    .attribute(pose!("style"), "display: flex; height: 1");
+
+    let mut ctx = BuildContext::new(&mut doc);
+    let mut state = view.build(&mut ctx);
+    state.mount(root, None, &mut doc);
+
+    compute_styles(&mut doc);
+    compute_layout(&mut doc, root, Size::new(20, 10));
+
+    doc
+}
+
+fn is_hovered(doc: &Document, selector: &str) -> bool {
+    doc.query_selector(&format!("{selector}:hover")).is_some()
+}
+
+#[test]
+fn crossing_a_boundary_leaves_then_enters() {
+    let mut doc = side_by_side();
+    let a = doc.query_selector(".a").expect("failed");
+    let b = doc.query_selector(".b").expect("failed");
+
+    // Would mark the events handled if they bubbled up from the boxes
+    let parent = doc.parent(a).expect("failed");
+    for name in [pose!("mouseenter"), pose!("mouseleave")] {
+        let handler = doc.add_event_handler(|_| {});
+        doc.register_event_handler(parent, name, handler);
+    }
+
+    doc.process_event(mouse_move(1, 0));
+    assert!(is_hovered(&doc, ".a"));
+
+    doc.enable_event_log(16);
+    doc.process_event(mouse_move(6, 0));
+
+    assert!(!is_hovered(&doc, ".a"));
+    assert!(is_hovered(&doc, ".b"));
+
+    let log: Vec<_> = doc.event_log().copied().collect();
+    let position = |entry: EventLogEntry| log.iter().position(|e| *e == entry);
+
+    let leave = position(EventLogEntry {
+        name: pose!("mouseleave"),
+        target: a,
+        handled: false,
+    })
+    .expect("failed");
+    let enter = position(EventLogEntry {
+        name: pose!("mouseenter"),
+        target: b,
+        handled: false,
+    })
+    .expect("failed");
+    assert!(leave < enter);
+}
+
+#[test]
+fn drag_follows_the_pressed_node() {
+    let mut doc = side_by_side();
+    let a = doc.query_selector(".a").expect("failed");
+
+    let drags: Rc<RefCell<Vec<(u16, u16)>>> = Rc::default();
+    let handler = doc.add_event_handler({
+        let drags = drags.clone();
+        move |event| {
+            let drag = event.as_drag().expect("failed");
+            assert_eq!(drag.drag_button, MouseButton::Primary);
+            assert_eq!(drag.start, ClientPoint::new(1, 0));
+
+            drags.borrow_mut().push((drag.client.x, drag.client.y));
+        }
+    });
+    doc.register_event_handler(a, pose!("drag"), handler);
+
+    doc.process_event(mouse_move(0, 0));
+    doc.process_event(EventType::MouseDown(mouse(
+        1,
+        0,
+        Some(MouseButton::Primary),
+    )));
+    doc.process_event(mouse_move(2, 0));
+    // Still goes to `.a` once the cursor is over `.b`
+    doc.process_event(mouse_move(7, 0));
+    doc.process_event(EventType::MouseUp(mouse(7, 0, Some(MouseButton::Primary))));
+    doc.process_event(mouse_move(3, 0));
+
+    assert_eq!(*drags.borrow(), [(2, 0), (7, 0)]);
+    assert!(doc.drag_origin().is_none());
+}
+
+#[test]
+fn double_click_follows_the_document_clock() {
+    let now_ms = Rc::new(Cell::new(0));
+
+    let mut doc = side_by_side();
+    let a = doc.query_selector(".a").expect("failed");
+    doc.set_clock(Some(Box::new({
+        let now_ms = now_ms.clone();
+        move || Duration::from_millis(now_ms.get())
+    })));
+
+    let double_clicks = Rc::new(Cell::new(0));
+    let handler = doc.add_event_handler({
+        let double_clicks = double_clicks.clone();
+        move |_| double_clicks.set(double_clicks.get() + 1)
+    });
+    doc.register_event_handler(a, pose!("dblclick"), handler);
+
+    let mut press_at = |ms| {
+        now_ms.set(ms);
+        doc.process_event(EventType::MouseDown(mouse(
+            1,
+            0,
+            Some(MouseButton::Primary),
+        )));
+        doc.process_event(EventType::MouseUp(mouse(1, 0, Some(MouseButton::Primary))));
+    };
+
+    // Back to back in real time, but too far apart on the document's clock
+    press_at(0);
+    press_at(600);
+    assert_eq!(double_clicks.get(), 0);
+
+    press_at(700);
+    assert_eq!(double_clicks.get(), 1);
+}
+
+#[test]
+fn clear_forgets_saved_focus_and_drag() {
+    let focusable = |doc: &mut Document, node| {
+        doc.get_mut(node)
+            .and_then(Node::as_element_mut)
+            .expect("failed")
+            .set_attribute(pose!("tabindex"), "0");
+    };
+
+    let mut doc = side_by_side();
+    let a = doc.query_selector(".a").expect("failed");
+
+    focusable(&mut doc, a);
+    doc.focus(a);
+    doc.push_focus();
+    doc.process_event(EventType::MouseDown(mouse(
+        1,
+        0,
+        Some(MouseButton::Primary),
+    )));
+    assert!(doc.drag_origin().is_some());
+
+    doc.clear();
+
+    // Removed slots get reused, so stale ids would land on the new nodes
+    let root = doc.root();
+    let fresh = doc.create_element(pose!("div"));
+    doc.append_child(root, fresh);
+    focusable(&mut doc, fresh);
+
+    let drags = Rc::new(Cell::new(0));
+    let handler = doc.add_event_handler({
+        let drags = drags.clone();
+        move |_| drags.set(drags.get() + 1)
+    });
+    doc.register_event_handler(fresh, pose!("drag"), handler);
+
+    assert_eq!(doc.pop_focus(), None);
+    assert_eq!(doc.focused(), None);
+    assert!(doc.drag_origin().is_none());
+
+    doc.process_event(mouse_move(2, 0));
+    assert_eq!(drags.get(), 0);
+}
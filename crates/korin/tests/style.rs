@@ -0,0 +1,613 @@
+use std::sync::Arc;
+
+use capsule_corp::{
+    Bulma, CapsuleDocument, Color, ComputedStyle, CustomPropertiesMap, QuerySelector, RestyleHint,
+    Stylesheet, compute_styles, restyle_for_attribute_change, restyle_for_class_change,
+    restyle_subtree,
+};
+use ginyu_force::{Pose, pose};
+use korin::{
+    Document, Node, NodeId, fragment,
+    view::{BuildContext, Mountable, View, div, span, text},
+};
+
+/// Wraps a [`Document`] and counts how many times a style gets written.
+struct CountingDocument {
+    inner: Document,
+    styled: usize,
+}
+
+impl CountingDocument {
+    fn new(css: &str) -> Self {
+        let mut inner = Document::new();
+        let stylesheet = Stylesheet::parse(css).expect("failed");
+        inner.stylist_mut().add_stylesheet(&stylesheet);
+
+        Self { inner, styled: 0 }
+    }
+
+    fn mount(&mut self, view: impl View) {
+        let root = self.inner.root();
+        let mut ctx = BuildContext::new(&mut self.inner);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut self.inner);
+    }
+
+    fn add_class(&mut self, id: NodeId, class: &str) {
+        let element = self
+            .inner
+            .get_mut(id)
+            .and_then(Node::as_element_mut)
+            .expect("failed");
+        element.add_class(Pose::from(class));
+    }
+}
+
+impl CapsuleDocument for CountingDocument {
+    type Element = <Document as CapsuleDocument>::Element;
+    type Node = Node;
+    type NodeId = NodeId;
+
+    fn root(&self) -> NodeId {
+        CapsuleDocument::root(&self.inner)
+    }
+
+    fn get_element(&self, node: NodeId) -> Option<Self::Element> {
+        self.inner.get_element(node)
+    }
+
+    fn get_node(&self, node: NodeId) -> &Node {
+        self.inner.get_node(node)
+    }
+
+    fn get_node_mut(&mut self, node: NodeId) -> &mut Node {
+        self.inner.get_node_mut(node)
+    }
+
+    fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.inner.parent(node)
+    }
+
+    fn children(&self, node: NodeId) -> impl Iterator<Item = NodeId> {
+        self.inner.children(node)
+    }
+
+    fn element_children(&self, node: NodeId) -> impl Iterator<Item = NodeId> {
+        self.inner.element_children(node)
+    }
+
+    fn descendants(&self, node: NodeId) -> impl Iterator<Item = NodeId> {
+        self.inner.descendants(node)
+    }
+
+    fn next_siblings(&self, node: NodeId) -> impl Iterator<Item = NodeId> {
+        self.inner.next_siblings(node)
+    }
+
+    fn computed_style(&self, node: NodeId) -> Option<&ComputedStyle> {
+        CapsuleDocument::computed_style(&self.inner, node)
+    }
+
+    fn custom_properties(&self, node: NodeId) -> Option<&CustomPropertiesMap> {
+        CapsuleDocument::custom_properties(&self.inner, node)
+    }
+
+    fn set_style(&mut self, node: NodeId, style: Arc<ComputedStyle>, custom: CustomPropertiesMap) {
+        self.styled += 1;
+        CapsuleDocument::set_style(&mut self.inner, node, style, custom);
+    }
+
+    fn take_stylist(&mut self) -> Bulma {
+        self.inner.take_stylist()
+    }
+
+    fn set_stylist(&mut self, stylist: Bulma) {
+        self.inner.set_stylist(stylist);
+    }
+}
+
+mod restyle {
+    use super::*;
+
+    const CSS: &str = r"
+        .gone { display: none }
+        .invisible { visibility: hidden }
+        .loud { color: red }
+        .visible { visibility: visible }
+    ";
+
+    fn setup() -> (CountingDocument, NodeId) {
+        let mut doc = CountingDocument::new(CSS);
+        doc.mount(div(fragment![span(text("a")), span(text("b"))]).class(pose!("parent")));
+
+        compute_styles(&mut doc);
+
+        let parent = doc.inner.query_selector(".parent").expect("failed");
+        doc.styled = 0;
+
+        (doc, parent)
+    }
+
+    #[test]
+    fn display_none_skips_descendants() {
+        let (mut doc, parent) = setup();
+
+        doc.add_class(parent, "gone");
+        restyle_subtree(
+            &mut doc,
+            parent,
+            RestyleHint::RESTYLE_SELF | RestyleHint::RESTYLE_DESCENDANTS,
+        );
+
+        // Only the parent itself gets recomputed
+        assert_eq!(doc.styled, 1);
+        let style = CapsuleDocument::computed_style(&doc, parent).expect("failed");
+        assert!(style.is_hidden());
+    }
+
+    #[test]
+    fn visibility_hidden_restyles_descendants() {
+        let (mut doc, parent) = setup();
+
+        doc.add_class(parent, "invisible");
+        restyle_subtree(
+            &mut doc,
+            parent,
+            RestyleHint::RESTYLE_SELF | RestyleHint::RESTYLE_DESCENDANTS,
+        );
+
+        // Parent plus both spans
+        assert_eq!(doc.styled, 3);
+
+        let spans: Vec<_> = doc.inner.children(parent).collect();
+        for span in spans {
+            let style = CapsuleDocument::computed_style(&doc, span).expect("failed");
+            assert!(style.is_hidden());
+        }
+    }
+
+    #[test]
+    fn unreferenced_class_skips_restyling() {
+        let (mut doc, parent) = setup();
+
+        doc.add_class(parent, "nobody-cares");
+        let hint = restyle_for_class_change(&mut doc, parent, Pose::from("nobody-cares"));
+
+        assert!(hint.is_empty());
+        assert_eq!(doc.styled, 0);
+    }
+
+    #[test]
+    fn unchanged_style_leaves_children_alone() {
+        let (mut doc, parent) = setup();
+
+        doc.add_class(parent, "visible");
+        let hint = restyle_for_class_change(&mut doc, parent, Pose::from("visible"));
+
+        // The rule matches, but visible is what it was already
+        assert_eq!(hint, RestyleHint::RESTYLE_SELF);
+        assert_eq!(doc.styled, 1);
+    }
+
+    #[test]
+    fn inherited_change_reaches_children() {
+        let (mut doc, parent) = setup();
+
+        doc.add_class(parent, "loud");
+        restyle_for_class_change(&mut doc, parent, Pose::from("loud"));
+
+        assert_eq!(doc.styled, 3);
+
+        let spans: Vec<_> = doc.inner.children(parent).collect();
+        for span in spans {
+            let style = CapsuleDocument::computed_style(&doc, span).expect("failed");
+            assert_eq!(style.color, Color::RED);
+        }
+    }
+
+    #[test]
+    fn style_attribute_restyles_the_element() {
+        let (mut doc, parent) = setup();
+
+        doc.inner
+            .get_mut(parent)
+            .and_then(Node::as_element_mut)
+            .expect("failed")
+            .set_attribute(pose!("style"), "display: none");
+        let hint = restyle_for_attribute_change(&mut doc, parent, pose!("style"));
+
+        assert!(hint.affects_self());
+        let style = CapsuleDocument::computed_style(&doc, parent).expect("failed");
+        assert!(style.is_hidden());
+    }
+}
+
+mod structural {
+    use capsule_corp::Display;
+    use korin::view::{li, ul};
+
+    use super::*;
+
+    fn setup(css: &str) -> (CountingDocument, Vec<NodeId>) {
+        let mut doc = CountingDocument::new(css);
+        doc.mount(ul(fragment![
+            li(text("1")),
+            li(text("2")),
+            li(text("3")),
+            span(text("4")),
+            li(text("5")),
+            li(text("6")),
+        ]));
+
+        compute_styles(&mut doc);
+
+        let list = doc.inner.query_selector("ul").expect("failed");
+        let items = doc.inner.element_children(list).collect();
+
+        (doc, items)
+    }
+
+    fn displayed(doc: &CountingDocument, items: &[NodeId]) -> Vec<bool> {
+        items
+            .iter()
+            .map(|&item| {
+                let style = CapsuleDocument::computed_style(doc, item).expect("failed");
+                !matches!(style.display, Display::None)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nth_child_even() {
+        let (doc, items) = setup("li:nth-child(2n) { display: none }");
+
+        assert_eq!(
+            displayed(&doc, &items),
+            [true, false, true, true, true, false]
+        );
+    }
+
+    #[test]
+    fn nth_child_index() {
+        let (doc, items) = setup("li:nth-child(3) { display: none }");
+
+        assert_eq!(
+            displayed(&doc, &items),
+            [true, true, false, true, true, true]
+        );
+    }
+
+    #[test]
+    fn nth_of_type_skips_other_tags() {
+        let (doc, items) = setup("li:nth-of-type(even) { display: none }");
+
+        // The `span` doesn't count, so the 4th `li` is the 5th child
+        assert_eq!(
+            displayed(&doc, &items),
+            [true, false, true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn first_and_last_child() {
+        let (doc, items) = setup(
+            "li:first-child { display: none }
+             :last-child { display: none }
+             li:last-of-type { display: none }",
+        );
+
+        assert_eq!(
+            displayed(&doc, &items),
+            [false, true, true, true, true, false]
+        );
+    }
+}
+
+mod attributes {
+    use capsule_corp::{Color, Display};
+
+    use super::*;
+
+    const CSS: &str = r#"
+        [disabled] { display: none }
+        [data-state="open"] { visibility: hidden }
+        [data-kind^="pre"] { color: red }
+        [data-kind$="fix"] { color: green }
+        [data-kind*="dd"] { color: blue }
+    "#;
+
+    fn style(view: impl View) -> ComputedStyle {
+        let mut doc = CountingDocument::new(CSS);
+        doc.mount(view);
+
+        compute_styles(&mut doc);
+
+        let node = doc.inner.query_selector("div").expect("failed");
+        CapsuleDocument::computed_style(&doc, node)
+            .cloned()
+            .expect("failed")
+    }
+
+    #[test]
+    fn presence() {
+        let disabled = style(div(text("a")).attribute(pose!("disabled"), ""));
+        assert!(matches!(disabled.display, Display::None));
+
+        let enabled = style(div(text("a")));
+        assert!(!matches!(enabled.display, Display::None));
+    }
+
+    #[test]
+    fn exact_match() {
+        let open = style(div(text("a")).attribute(Pose::from("data-state"), "open"));
+        assert!(open.is_hidden());
+
+        let closed = style(div(text("a")).attribute(Pose::from("data-state"), "closed"));
+        assert!(!closed.is_hidden());
+    }
+
+    #[test]
+    fn substring_matches() {
+        let kind = Pose::from("data-kind");
+
+        assert_eq!(
+            style(div(text("a")).attribute(kind, "prelude")).color,
+            Color::RED
+        );
+        assert_eq!(
+            style(div(text("a")).attribute(kind, "suffix")).color,
+            Color::GREEN
+        );
+        assert_eq!(
+            style(div(text("a")).attribute(kind, "middle")).color,
+            Color::BLUE
+        );
+    }
+}
+
+mod combinators {
+    use capsule_corp::Display;
+    use korin::view::{h1, li, p, ul};
+
+    use super::*;
+
+    fn hidden(doc: &CountingDocument, selector: &str) -> Vec<bool> {
+        doc.inner
+            .query_selector_all(selector)
+            .into_iter()
+            .map(|node| {
+                let style = CapsuleDocument::computed_style(doc, node).expect("failed");
+                matches!(style.display, Display::None)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn child_skips_grandchildren() {
+        let mut doc = CountingDocument::new(".top > li { display: none }");
+        doc.mount(ul(fragment![li(text("a")), li(ul(li(text("nested")))),]).class(pose!("top")));
+
+        compute_styles(&mut doc);
+
+        // Both direct children match, the nested item doesn't
+        assert_eq!(hidden(&doc, "li"), [true, true, false]);
+    }
+
+    #[test]
+    fn adjacent_sibling() {
+        let mut doc = CountingDocument::new("h1 + p { display: none }");
+        doc.mount(div(fragment![
+            h1(text("title")),
+            text(" "),
+            p(text("lead")),
+            p(text("body")),
+            h1(text("other")),
+            span(text("aside")),
+            p(text("tail")),
+        ]));
+
+        compute_styles(&mut doc);
+
+        // Text between elements doesn't break adjacency
+        assert_eq!(hidden(&doc, "p"), [true, false, false]);
+    }
+
+    #[test]
+    fn general_sibling() {
+        let mut doc = CountingDocument::new("h1 ~ p { display: none }");
+        doc.mount(div(fragment![
+            p(text("before")),
+            h1(text("title")),
+            p(text("lead")),
+            span(text("aside")),
+            p(text("tail")),
+        ]));
+
+        compute_styles(&mut doc);
+
+        assert_eq!(hidden(&doc, "p"), [false, true, true]);
+    }
+}
+
+mod sharing {
+    use korin::{
+        AnyView,
+        view::{Fragment, li, ul},
+    };
+
+    use super::*;
+
+    #[test]
+    fn many_siblings_share_styles() {
+        let mut doc = CountingDocument::new(
+            "li { color: blue }
+             .even { color: red }",
+        );
+        let items: Fragment = (0..200)
+            .map(|i| {
+                let item = li(text(i.to_string()));
+                AnyView::new(item.when(i % 2 == 0, |item| item.class(pose!("even"))))
+            })
+            .collect();
+        doc.mount(ul(items));
+
+        compute_styles(&mut doc);
+
+        // The list, plus one of each kind of item
+        let stylist = doc.inner.stylist();
+        assert_eq!(stylist.num_computed_styles(), 3);
+        assert_eq!(stylist.num_shared_styles(), 198);
+
+        let items = doc.inner.query_selector_all("li");
+        let style = |index: usize| doc.inner.get(items[index]).and_then(|n| n.style.clone());
+        let (first, second, third) = (style(0), style(1), style(2));
+
+        assert!(Arc::ptr_eq(
+            first.as_ref().expect("failed"),
+            third.as_ref().expect("failed")
+        ));
+        assert!(!Arc::ptr_eq(
+            first.as_ref().expect("failed"),
+            second.as_ref().expect("failed")
+        ));
+        assert_eq!(first.expect("failed").color, capsule_corp::Color::RED);
+        assert_eq!(second.expect("failed").color, capsule_corp::Color::BLUE);
+    }
+}
+
+mod transitions {
+    use std::time::Duration;
+
+    use super::*;
+
+    const CSS: &str = "
+        .box { background-color: rgb(0, 0, 0); transition: background-color 1s }
+        .box.lit { background-color: rgb(200, 100, 0) }
+    ";
+
+    fn setup() -> (CountingDocument, NodeId) {
+        let mut doc = CountingDocument::new(CSS);
+        doc.mount(div(()).class(pose!("box")));
+        compute_styles(&mut doc);
+
+        let node = doc.inner.query_selector(".box").expect("failed");
+        (doc, node)
+    }
+
+    fn background(doc: &CountingDocument, node: NodeId) -> Color {
+        CapsuleDocument::computed_style(doc, node)
+            .expect("failed")
+            .background_color
+    }
+
+    #[test]
+    fn background_color_eases_over_the_duration() {
+        let (mut doc, node) = setup();
+
+        // The first style is applied as is
+        assert_eq!(background(&doc, node), Color::Rgb(0, 0, 0));
+        assert!(!doc.inner.is_transitioning());
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert_eq!(background(&doc, node), Color::Rgb(0, 0, 0));
+
+        assert!(doc.inner.tick(Duration::from_millis(500)));
+        assert_eq!(background(&doc, node), Color::Rgb(100, 50, 0));
+
+        assert!(!doc.inner.tick(Duration::from_millis(500)));
+        assert_eq!(background(&doc, node), Color::Rgb(200, 100, 0));
+    }
+
+    #[test]
+    fn unrelated_restyles_keep_the_transition_going() {
+        let (mut doc, node) = setup();
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        doc.inner.tick(Duration::from_millis(250));
+
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert_eq!(background(&doc, node), Color::Rgb(50, 25, 0));
+
+        doc.inner.tick(Duration::from_millis(250));
+        assert_eq!(background(&doc, node), Color::Rgb(100, 50, 0));
+    }
+
+    #[test]
+    fn interrupting_turns_back_from_the_current_color() {
+        let (mut doc, node) = setup();
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        doc.inner.tick(Duration::from_millis(500));
+
+        doc.inner
+            .get_mut(node)
+            .and_then(Node::as_element_mut)
+            .expect("failed")
+            .remove_class(pose!("lit"));
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert_eq!(background(&doc, node), Color::Rgb(100, 50, 0));
+
+        doc.inner.tick(Duration::from_millis(500));
+        assert_eq!(background(&doc, node), Color::Rgb(50, 25, 0));
+    }
+
+    #[test]
+    fn children_inherit_the_transitioning_color() {
+        let mut doc = CountingDocument::new(
+            ".box { color: rgb(0, 0, 0); transition: color 1s }
+             .box.lit { color: rgb(200, 100, 0) }",
+        );
+        doc.mount(div(span(())).class(pose!("box")));
+        compute_styles(&mut doc);
+
+        let node = doc.inner.query_selector(".box").expect("failed");
+        let child = doc.inner.query_selector("span").expect("failed");
+        let color = |doc: &CountingDocument| {
+            CapsuleDocument::computed_style(doc, child)
+                .expect("failed")
+                .color
+        };
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert_eq!(color(&doc), Color::Rgb(0, 0, 0));
+
+        doc.inner.tick(Duration::from_millis(500));
+        assert_eq!(color(&doc), Color::Rgb(100, 50, 0));
+
+        doc.inner.tick(Duration::from_millis(500));
+        assert_eq!(color(&doc), Color::Rgb(200, 100, 0));
+    }
+
+    #[test]
+    fn removing_a_subtree_drops_its_transitions() {
+        let mut doc = CountingDocument::new(CSS);
+        doc.mount(div(div(()).class(pose!("box"))).class(pose!("wrapper")));
+        compute_styles(&mut doc);
+
+        let wrapper = doc.inner.query_selector(".wrapper").expect("failed");
+        let node = doc.inner.query_selector(".box").expect("failed");
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert!(doc.inner.is_transitioning());
+
+        doc.inner.remove(wrapper);
+        assert!(!doc.inner.is_transitioning());
+    }
+
+    #[test]
+    fn clearing_drops_every_transition() {
+        let (mut doc, node) = setup();
+
+        doc.add_class(node, "lit");
+        restyle_subtree(&mut doc, node, RestyleHint::RESTYLE_SELF);
+        assert!(doc.inner.is_transitioning());
+
+        doc.inner.clear();
+        assert!(!doc.inner.is_transitioning());
+    }
+}
@@ -9,7 +9,7 @@ use korin::{
     Document, fragment,
     view::{
         AnyView, BuildContext, Either, Mountable, RebuildContext, TextView, View, div, footer,
-        for_each, h1, h2, header, li, main, p, show_if, span, text, ul,
+        for_each, h1, h2, header, li, main, p, show_if, show_some, span, text, ul,
     },
 };
 use potara::{reset_frame, use_state_at, with_scope};
@@ -154,6 +154,31 @@ mod basic_cycle {
         assert_eq!(elem.get_attribute("data-test".into()), Some("value"));
     }
 
+    #[test]
+    fn element_when_applies_conditionally() {
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let view = fragment![
+            div(text("Focused")).when(true, |d| d.class(pose!("focused"))),
+            div(text("Blurred")).when(false, |d| d.class(pose!("focused"))),
+        ];
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = view.build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        let divs: Vec<_> = doc.children(root).collect();
+        let has_class = |id| {
+            doc.get(id)
+                .and_then(|n| n.as_element())
+                .is_some_and(|e| e.has_class("focused"))
+        };
+
+        assert!(has_class(divs[0]));
+        assert!(!has_class(divs[1]));
+    }
+
     #[test]
     fn fragment_creates_siblings() {
         let mut doc = Document::new();
@@ -511,6 +536,41 @@ mod either_conditional {
         assert_eq!(collect_text_content(&doc, root), vec!["Visible"]);
         reset_frame();
     }
+
+    #[test]
+    fn show_some_toggle() {
+        reset_frame();
+        let mut doc = Document::new();
+        let root = doc.root();
+
+        let make_view = || {
+            let selected = test_state(4, || Some("First"));
+            show_some(move || selected.get(), |name| AnyView::new(text(name)))()
+        };
+
+        let mut ctx = BuildContext::new(&mut doc);
+        let mut state = make_view().build(&mut ctx);
+        state.mount(root, None, &mut doc);
+
+        assert_eq!(collect_text_content(&doc, root), vec!["First"]);
+
+        test_state(4, || None::<&str>).set(None);
+        reset_frame();
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        make_view().rebuild(&mut state, &mut ctx);
+
+        assert_eq!(collect_text_content(&doc, root), Vec::<String>::new());
+
+        test_state(4, || None).set(Some("Second"));
+        reset_frame();
+
+        let mut ctx = RebuildContext::new(&mut doc);
+        make_view().rebuild(&mut state, &mut ctx);
+
+        assert_eq!(collect_text_content(&doc, root), vec!["Second"]);
+        reset_frame();
+    }
 }
 
 mod for_loop {
@@ -2,11 +2,19 @@ use std::hash::Hash;
 
 use crate::runtime::{ScopeKey, pop_scope, push_scope};
 
+struct ScopeGuard;
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        pop_scope();
+    }
+}
+
 pub fn with_scope<R>(key: impl Hash, f: impl FnOnce() -> R) -> R {
     push_scope(ScopeKey::new(key));
-    let result = f();
-    pop_scope();
-    result
+    // Popped on drop, so a panic caught further up doesn't leave it behind
+    let _guard = ScopeGuard;
+    f()
 }
 
 #[cfg(test)]
@@ -66,6 +74,20 @@ mod tests {
         reset_frame();
     }
 
+    #[test]
+    fn panicking_inside_a_scope_leaves_it() {
+        let state = use_test_state(|| 0);
+        state.set(7);
+        reset_frame();
+
+        let result = std::panic::catch_unwind(|| with_scope("item", || panic!("broken item")));
+        assert!(result.is_err());
+
+        assert_eq!(use_test_state(|| 0).get(), 7);
+
+        reset_frame();
+    }
+
     #[test]
     fn nested_scopes() {
         with_scope("outer", || {